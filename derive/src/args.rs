@@ -1,7 +1,8 @@
 use crate::utils::{
-    get_rustdoc, parse_default, parse_default_with, parse_guards, parse_post_guards,
-    parse_validator,
+    field_side_args, get_rustdoc, parse_default, parse_default_with, parse_duration, parse_guards,
+    parse_post_guards, parse_validator,
 };
+use inflector::Inflector;
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Attribute, AttributeArgs, Error, Lit, Meta, MetaList, NestedMeta, Result, Type};
@@ -61,12 +62,167 @@ impl CacheControl {
     }
 }
 
+pub struct Node {
+    pub loader: TokenStream,
+}
+
+impl Node {
+    pub fn parse(ls: &MetaList) -> Result<Self> {
+        let mut loader = None;
+
+        for meta in &ls.nested {
+            if let NestedMeta::Meta(Meta::NameValue(nv)) = meta {
+                if nv.path.is_ident("loader") {
+                    if let syn::Lit::Str(lit) = &nv.lit {
+                        loader = Some(lit.value().parse::<TokenStream>()?);
+                    } else {
+                        return Err(Error::new_spanned(
+                            &nv.lit,
+                            "Attribute 'loader' should be a string.",
+                        ));
+                    }
+                }
+            }
+        }
+
+        match loader {
+            Some(loader) => Ok(Self { loader }),
+            None => Err(Error::new_spanned(ls, "Missing loader")),
+        }
+    }
+}
+
+pub struct ConcreteType {
+    pub name: String,
+    pub input_name: Option<String>,
+    pub params: Vec<Type>,
+}
+
+impl ConcreteType {
+    pub fn parse(ls: &MetaList) -> Result<Self> {
+        let mut name = None;
+        let mut input_name = None;
+        let mut params = None;
+
+        for meta in &ls.nested {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(nv)) => {
+                    if nv.path.is_ident("name") {
+                        if let syn::Lit::Str(lit) = &nv.lit {
+                            name = Some(lit.value());
+                        } else {
+                            return Err(Error::new_spanned(
+                                &nv.lit,
+                                "Attribute 'name' should be a string.",
+                            ));
+                        }
+                    } else if nv.path.is_ident("input_name") {
+                        if let syn::Lit::Str(lit) = &nv.lit {
+                            input_name = Some(lit.value());
+                        } else {
+                            return Err(Error::new_spanned(
+                                &nv.lit,
+                                "Attribute 'input_name' should be a string.",
+                            ));
+                        }
+                    }
+                }
+                NestedMeta::Meta(Meta::List(ls)) if ls.path.is_ident("params") => {
+                    let mut types = Vec::new();
+                    for meta in &ls.nested {
+                        match meta {
+                            NestedMeta::Meta(Meta::Path(p)) => {
+                                types.push(Type::Path(syn::TypePath {
+                                    qself: None,
+                                    path: p.clone(),
+                                }));
+                            }
+                            NestedMeta::Lit(Lit::Str(lit)) => {
+                                types.push(syn::parse_str::<Type>(&lit.value())?);
+                            }
+                            _ => return Err(Error::new_spanned(meta, "Expect type")),
+                        }
+                    }
+                    params = Some(types);
+                }
+                _ => {}
+            }
+        }
+
+        match (name, params) {
+            (Some(name), Some(params)) => Ok(Self {
+                name,
+                input_name,
+                params,
+            }),
+            (None, _) => Err(Error::new_spanned(ls, "Missing name")),
+            (_, None) => Err(Error::new_spanned(ls, "Missing params")),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub enum RenameRule {
+    CamelCase,
+    SnakeCase,
+    PascalCase,
+    ScreamingSnakeCase,
+    Lowercase,
+}
+
+impl RenameRule {
+    fn parse(lit: &Lit) -> Result<Self> {
+        if let Lit::Str(lit) = lit {
+            match lit.value().as_str() {
+                "camelCase" => Ok(RenameRule::CamelCase),
+                "snake_case" => Ok(RenameRule::SnakeCase),
+                "PascalCase" => Ok(RenameRule::PascalCase),
+                "SCREAMING_SNAKE_CASE" => Ok(RenameRule::ScreamingSnakeCase),
+                "lowercase" => Ok(RenameRule::Lowercase),
+                _ => Err(Error::new_spanned(
+                    lit,
+                    "Expect one of \"camelCase\", \"snake_case\", \"PascalCase\", \"SCREAMING_SNAKE_CASE\" or \"lowercase\".",
+                )),
+            }
+        } else {
+            Err(Error::new_spanned(lit, "Rename rule should be a string."))
+        }
+    }
+
+    /// The names under every rule, in the order of the variants of `RenameRule` in the main crate.
+    pub fn all_names(name: &str) -> [String; 5] {
+        [
+            RenameRule::CamelCase.rename(name),
+            RenameRule::SnakeCase.rename(name),
+            RenameRule::PascalCase.rename(name),
+            RenameRule::ScreamingSnakeCase.rename(name),
+            RenameRule::Lowercase.rename(name),
+        ]
+    }
+
+    pub fn rename(self, name: impl AsRef<str>) -> String {
+        let name = name.as_ref();
+        match self {
+            RenameRule::CamelCase => name.to_camel_case(),
+            RenameRule::SnakeCase => name.to_snake_case(),
+            RenameRule::PascalCase => name.to_pascal_case(),
+            RenameRule::ScreamingSnakeCase => name.to_screaming_snake_case(),
+            RenameRule::Lowercase => name.to_lowercase(),
+        }
+    }
+}
+
 pub struct Object {
     pub internal: bool,
     pub name: Option<String>,
     pub desc: Option<String>,
     pub cache_control: CacheControl,
     pub extends: bool,
+    pub node: Option<Node>,
+    pub complex: bool,
+    pub rename_fields: Option<RenameRule>,
+    pub rename_args: Option<RenameRule>,
+    pub concretes: Vec<ConcreteType>,
 }
 
 impl Object {
@@ -76,6 +232,11 @@ impl Object {
         let mut desc = None;
         let mut cache_control = CacheControl::default();
         let mut extends = false;
+        let mut node = None;
+        let mut complex = false;
+        let mut rename_fields = None;
+        let mut rename_args = None;
+        let mut concretes = Vec::new();
 
         for arg in args {
             match arg {
@@ -85,6 +246,9 @@ impl Object {
                 NestedMeta::Meta(Meta::Path(p)) if p.is_ident("extends") => {
                     extends = true;
                 }
+                NestedMeta::Meta(Meta::Path(p)) if p.is_ident("complex") => {
+                    complex = true;
+                }
                 NestedMeta::Meta(Meta::NameValue(nv)) => {
                     if nv.path.is_ident("name") {
                         if let syn::Lit::Str(lit) = nv.lit {
@@ -104,11 +268,19 @@ impl Object {
                                 "Attribute 'desc' should be a string.",
                             ));
                         }
+                    } else if nv.path.is_ident("rename_fields") {
+                        rename_fields = Some(RenameRule::parse(&nv.lit)?);
+                    } else if nv.path.is_ident("rename_args") {
+                        rename_args = Some(RenameRule::parse(&nv.lit)?);
                     }
                 }
                 NestedMeta::Meta(Meta::List(ls)) => {
                     if ls.path.is_ident("cache_control") {
                         cache_control = CacheControl::parse(&ls)?;
+                    } else if ls.path.is_ident("node") {
+                        node = Some(Node::parse(&ls)?);
+                    } else if ls.path.is_ident("concrete") {
+                        concretes.push(ConcreteType::parse(&ls)?);
                     }
                 }
                 _ => {}
@@ -121,6 +293,11 @@ impl Object {
             desc,
             cache_control,
             extends,
+            node,
+            complex,
+            rename_fields,
+            rename_args,
+            concretes,
         })
     }
 }
@@ -128,18 +305,22 @@ impl Object {
 pub struct Argument {
     pub name: Option<String>,
     pub desc: Option<String>,
+    pub deprecation: Option<String>,
     pub default: Option<TokenStream>,
     pub validator: TokenStream,
     pub key: bool, // for entity
+    pub secret: bool,
 }
 
 impl Argument {
     pub fn parse(crate_name: &TokenStream, attrs: &[Attribute]) -> Result<Self> {
         let mut name = None;
         let mut desc = None;
+        let mut deprecation = None;
         let mut default = None;
         let mut validator = quote! { None };
         let mut key = false;
+        let mut secret = false;
 
         for attr in attrs {
             match attr.parse_meta()? {
@@ -150,6 +331,8 @@ impl Argument {
                                 default = Some(quote! { Default::default() });
                             } else if p.is_ident("key") {
                                 key = true;
+                            } else if p.is_ident("secret") {
+                                secret = true;
                             }
                         } else if let NestedMeta::Meta(Meta::NameValue(nv)) = meta {
                             if nv.path.is_ident("name") {
@@ -170,6 +353,15 @@ impl Argument {
                                         "Attribute 'desc' should be a string.",
                                     ));
                                 }
+                            } else if nv.path.is_ident("deprecation") {
+                                if let syn::Lit::Str(lit) = &nv.lit {
+                                    deprecation = Some(lit.value());
+                                } else {
+                                    return Err(Error::new_spanned(
+                                        &nv.lit,
+                                        "Attribute 'deprecation' should be a string.",
+                                    ));
+                                }
                             } else if nv.path.is_ident("default") {
                                 default = Some(parse_default(&nv.lit)?);
                             } else if nv.path.is_ident("default_with") {
@@ -187,9 +379,11 @@ impl Argument {
         Ok(Self {
             name,
             desc,
+            deprecation,
             default,
             validator,
             key,
+            secret,
         })
     }
 }
@@ -205,6 +399,9 @@ pub struct Field {
     pub owned: bool,
     pub guard: Option<TokenStream>,
     pub post_guard: Option<TokenStream>,
+    pub timeout: Option<u64>,
+    pub error_policy: Option<TokenStream>,
+    pub secret: bool,
 }
 
 impl Field {
@@ -219,10 +416,14 @@ impl Field {
         let mut owned = false;
         let mut guard = None;
         let mut post_guard = None;
+        let mut timeout = None;
+        let mut error_policy = None;
+        let mut secret = false;
 
         for attr in attrs {
             match attr.parse_meta()? {
                 Meta::List(ls) if ls.path.is_ident("field") => {
+                    let ls = field_side_args(&ls, "output");
                     guard = parse_guards(crate_name, &ls)?;
                     post_guard = parse_post_guards(crate_name, &ls)?;
                     for meta in &ls.nested {
@@ -236,6 +437,9 @@ impl Field {
                             NestedMeta::Meta(Meta::Path(p)) if p.is_ident("owned") => {
                                 owned = true;
                             }
+                            NestedMeta::Meta(Meta::Path(p)) if p.is_ident("secret") => {
+                                secret = true;
+                            }
                             NestedMeta::Meta(Meta::Path(p)) if p.is_ident("ref") => {
                                 return Err(Error::new_spanned(
                                     &p,
@@ -288,6 +492,31 @@ impl Field {
                                             "Attribute 'requires' should be a string.",
                                         ));
                                     }
+                                } else if nv.path.is_ident("timeout") {
+                                    timeout = Some(parse_duration(&nv.lit)?);
+                                } else if nv.path.is_ident("error_policy") {
+                                    let policy = match &nv.lit {
+                                        syn::Lit::Str(lit) => match lit.value().as_str() {
+                                            "terminate" => quote! { Terminate },
+                                            "skip" => quote! { Skip },
+                                            "emit" => quote! { Emit },
+                                            _ => {
+                                                return Err(Error::new_spanned(
+                                                    &nv.lit,
+                                                    "Attribute 'error_policy' should be one of \"terminate\", \"skip\" or \"emit\".",
+                                                ));
+                                            }
+                                        },
+                                        _ => {
+                                            return Err(Error::new_spanned(
+                                                &nv.lit,
+                                                "Attribute 'error_policy' should be a string.",
+                                            ));
+                                        }
+                                    };
+                                    error_policy = Some(
+                                        quote! { #crate_name::SubscriptionErrorPolicy::#policy },
+                                    );
                                 }
                             }
                             NestedMeta::Meta(Meta::List(ls)) => {
@@ -318,6 +547,9 @@ impl Field {
             owned,
             guard,
             post_guard,
+            timeout,
+            error_policy,
+            secret,
         }))
     }
 }
@@ -327,6 +559,7 @@ pub struct Enum {
     pub name: Option<String>,
     pub desc: Option<String>,
     pub remote: Option<String>,
+    pub rename_items: Option<RenameRule>,
 }
 
 impl Enum {
@@ -335,6 +568,7 @@ impl Enum {
         let mut name = None;
         let mut desc = None;
         let mut remote = None;
+        let mut rename_items = None;
 
         for arg in args {
             match arg {
@@ -360,6 +594,8 @@ impl Enum {
                                 "Attribute 'desc' should be a string.",
                             ));
                         }
+                    } else if nv.path.is_ident("rename_items") {
+                        rename_items = Some(RenameRule::parse(&nv.lit)?);
                     } else if nv.path.is_ident("remote") {
                         if let syn::Lit::Str(lit) = nv.lit {
                             remote = Some(lit.value());
@@ -380,6 +616,7 @@ impl Enum {
             name,
             desc,
             remote,
+            rename_items,
         })
     }
 }
@@ -476,22 +713,27 @@ impl UnionItem {
 pub struct InputField {
     pub name: Option<String>,
     pub desc: Option<String>,
+    pub deprecation: Option<String>,
     pub default: Option<TokenStream>,
     pub validator: TokenStream,
     pub flatten: bool,
+    pub secret: bool,
 }
 
 impl InputField {
     pub fn parse(crate_name: &TokenStream, attrs: &[Attribute]) -> Result<Self> {
         let mut name = None;
         let mut desc = None;
+        let mut deprecation = None;
         let mut default = None;
         let mut validator = quote! { None };
         let mut flatten = false;
+        let mut secret = false;
 
         for attr in attrs {
             if attr.path.is_ident("field") {
                 if let Meta::List(args) = &attr.parse_meta()? {
+                    let args = field_side_args(args, "input");
                     for meta in &args.nested {
                         match meta {
                             NestedMeta::Meta(Meta::Path(p)) if p.is_ident("skip") => {
@@ -506,6 +748,9 @@ impl InputField {
                             NestedMeta::Meta(Meta::Path(p)) if p.is_ident("flatten") => {
                                 flatten = true;
                             }
+                            NestedMeta::Meta(Meta::Path(p)) if p.is_ident("secret") => {
+                                secret = true;
+                            }
                             NestedMeta::Meta(Meta::NameValue(nv)) => {
                                 if nv.path.is_ident("name") {
                                     if let syn::Lit::Str(lit) = &nv.lit {
@@ -525,6 +770,15 @@ impl InputField {
                                             "Attribute 'desc' should be a string.",
                                         ));
                                     }
+                                } else if nv.path.is_ident("deprecation") {
+                                    if let syn::Lit::Str(lit) = &nv.lit {
+                                        deprecation = Some(lit.value());
+                                    } else {
+                                        return Err(Error::new_spanned(
+                                            &nv.lit,
+                                            "Attribute 'deprecation' should be a string.",
+                                        ));
+                                    }
                                 } else if nv.path.is_ident("default") {
                                     default = Some(parse_default(&nv.lit)?);
                                 } else if nv.path.is_ident("default_with") {
@@ -547,9 +801,11 @@ impl InputField {
         Ok(Self {
             name,
             desc,
+            deprecation,
             default,
             validator,
             flatten,
+            secret,
         })
     }
 }
@@ -557,14 +813,20 @@ impl InputField {
 pub struct InputObject {
     pub internal: bool,
     pub name: Option<String>,
+    pub input_name: Option<String>,
     pub desc: Option<String>,
+    pub rename_fields: Option<RenameRule>,
+    pub concretes: Vec<ConcreteType>,
 }
 
 impl InputObject {
     pub fn parse(args: AttributeArgs) -> Result<Self> {
         let mut internal = false;
         let mut name = None;
+        let mut input_name = None;
         let mut desc = None;
+        let mut rename_fields = None;
+        let mut concretes = Vec::new();
 
         for arg in args {
             match arg {
@@ -581,6 +843,15 @@ impl InputObject {
                                 "Attribute 'name' should be a string.",
                             ));
                         }
+                    } else if nv.path.is_ident("input_name") {
+                        if let syn::Lit::Str(lit) = nv.lit {
+                            input_name = Some(lit.value());
+                        } else {
+                            return Err(Error::new_spanned(
+                                &nv.lit,
+                                "Attribute 'input_name' should be a string.",
+                            ));
+                        }
                     } else if nv.path.is_ident("desc") {
                         if let syn::Lit::Str(lit) = nv.lit {
                             desc = Some(lit.value());
@@ -590,8 +861,13 @@ impl InputObject {
                                 "Attribute 'desc' should be a string.",
                             ));
                         }
+                    } else if nv.path.is_ident("rename_fields") {
+                        rename_fields = Some(RenameRule::parse(&nv.lit)?);
                     }
                 }
+                NestedMeta::Meta(Meta::List(ls)) if ls.path.is_ident("concrete") => {
+                    concretes.push(ConcreteType::parse(&ls)?);
+                }
                 _ => {}
             }
         }
@@ -599,7 +875,94 @@ impl InputObject {
         Ok(Self {
             internal,
             name,
+            input_name,
             desc,
+            rename_fields,
+            concretes,
+        })
+    }
+}
+
+pub struct OneofObjectItem {
+    pub name: Option<String>,
+    pub desc: Option<String>,
+    pub deprecation: Option<String>,
+    pub validator: TokenStream,
+    pub secret: bool,
+}
+
+impl OneofObjectItem {
+    pub fn parse(crate_name: &TokenStream, attrs: &[Attribute]) -> Result<Self> {
+        let mut name = None;
+        let mut desc = None;
+        let mut deprecation = None;
+        let mut validator = quote! { None };
+        let mut secret = false;
+
+        for attr in attrs {
+            if attr.path.is_ident("item") {
+                if let Meta::List(args) = &attr.parse_meta()? {
+                    for meta in &args.nested {
+                        match meta {
+                            NestedMeta::Meta(Meta::Path(p)) if p.is_ident("secret") => {
+                                secret = true;
+                            }
+                            NestedMeta::Meta(Meta::NameValue(nv)) => {
+                                if nv.path.is_ident("name") {
+                                    if let syn::Lit::Str(lit) = &nv.lit {
+                                        name = Some(lit.value());
+                                    } else {
+                                        return Err(Error::new_spanned(
+                                            &nv.lit,
+                                            "Attribute 'name' should be a string.",
+                                        ));
+                                    }
+                                } else if nv.path.is_ident("desc") {
+                                    if let syn::Lit::Str(lit) = &nv.lit {
+                                        desc = Some(lit.value());
+                                    } else {
+                                        return Err(Error::new_spanned(
+                                            &nv.lit,
+                                            "Attribute 'desc' should be a string.",
+                                        ));
+                                    }
+                                } else if nv.path.is_ident("deprecation") {
+                                    if let syn::Lit::Str(lit) = &nv.lit {
+                                        deprecation = Some(lit.value());
+                                    } else {
+                                        return Err(Error::new_spanned(
+                                            &nv.lit,
+                                            "Attribute 'deprecation' should be a string.",
+                                        ));
+                                    }
+                                } else if nv.path.is_ident("default")
+                                    || nv.path.is_ident("default_with")
+                                {
+                                    return Err(Error::new_spanned(
+                                        meta,
+                                        "Fields on OneofObject are not allowed to have default values",
+                                    ));
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+
+                    validator = parse_validator(crate_name, &args)?;
+                }
+            }
+        }
+
+        if desc.is_none() {
+            desc = get_rustdoc(attrs)?;
+        }
+
+        Ok(Self {
+            name,
+            desc,
+            deprecation,
+            validator,
+            secret,
         })
     }
 }
@@ -607,21 +970,27 @@ impl InputObject {
 pub struct InterfaceFieldArgument {
     pub name: String,
     pub desc: Option<String>,
+    pub deprecation: Option<String>,
     pub ty: Type,
     pub default: Option<TokenStream>,
+    pub secret: bool,
 }
 
 impl InterfaceFieldArgument {
     pub fn parse(ls: &MetaList) -> Result<Self> {
         let mut name = None;
         let mut desc = None;
+        let mut deprecation = None;
         let mut ty = None;
         let mut default = None;
+        let mut secret = false;
 
         for meta in &ls.nested {
             if let NestedMeta::Meta(Meta::Path(p)) = meta {
                 if p.is_ident("default") {
                     default = Some(quote! { Default::default() });
+                } else if p.is_ident("secret") {
+                    secret = true;
                 }
             } else if let NestedMeta::Meta(Meta::NameValue(nv)) = meta {
                 if nv.path.is_ident("name") {
@@ -642,6 +1011,15 @@ impl InterfaceFieldArgument {
                             "Attribute 'desc' should be a string.",
                         ));
                     }
+                } else if nv.path.is_ident("deprecation") {
+                    if let syn::Lit::Str(lit) = &nv.lit {
+                        deprecation = Some(lit.value());
+                    } else {
+                        return Err(Error::new_spanned(
+                            &nv.lit,
+                            "Attribute 'deprecation' should be a string.",
+                        ));
+                    }
                 } else if nv.path.is_ident("type") {
                     if let syn::Lit::Str(lit) = &nv.lit {
                         if let Ok(ty2) = syn::parse_str::<syn::Type>(&lit.value()) {
@@ -674,8 +1052,10 @@ impl InterfaceFieldArgument {
         Ok(Self {
             name: name.unwrap(),
             desc,
+            deprecation,
             ty: ty.unwrap(),
             default,
+            secret,
         })
     }
 }
@@ -813,7 +1193,10 @@ pub struct Interface {
     pub name: Option<String>,
     pub desc: Option<String>,
     pub fields: Vec<InterfaceField>,
+    pub implements: Vec<Type>,
     pub extends: bool,
+    pub rename_fields: Option<RenameRule>,
+    pub rename_args: Option<RenameRule>,
 }
 
 impl Interface {
@@ -822,7 +1205,10 @@ impl Interface {
         let mut name = None;
         let mut desc = None;
         let mut fields = Vec::new();
+        let mut implements = Vec::new();
         let mut extends = false;
+        let mut rename_fields = None;
+        let mut rename_args = None;
 
         for arg in args {
             match arg {
@@ -851,6 +1237,23 @@ impl Interface {
                                 "Attribute 'desc' should be a string.",
                             ));
                         }
+                    } else if nv.path.is_ident("rename_fields") {
+                        rename_fields = Some(RenameRule::parse(&nv.lit)?);
+                    } else if nv.path.is_ident("rename_args") {
+                        rename_args = Some(RenameRule::parse(&nv.lit)?);
+                    } else if nv.path.is_ident("implements") {
+                        if let syn::Lit::Str(lit) = &nv.lit {
+                            if let Ok(ty) = syn::parse_str::<syn::Type>(&lit.value()) {
+                                implements.push(ty);
+                            } else {
+                                return Err(Error::new_spanned(&lit, "Expect type"));
+                            }
+                        } else {
+                            return Err(Error::new_spanned(
+                                &nv.lit,
+                                "Attribute 'implements' should be a string.",
+                            ));
+                        }
                     }
                 }
                 NestedMeta::Meta(Meta::List(ls)) if ls.path.is_ident("field") => {
@@ -865,7 +1268,10 @@ impl Interface {
             name,
             desc,
             fields,
+            implements,
             extends,
+            rename_fields,
+            rename_args,
         })
     }
 }
@@ -874,6 +1280,7 @@ pub struct Scalar {
     pub internal: bool,
     pub name: Option<String>,
     pub desc: Option<String>,
+    pub specified_by_url: Option<String>,
 }
 
 impl Scalar {
@@ -881,6 +1288,7 @@ impl Scalar {
         let mut internal = false;
         let mut name = None;
         let mut desc = None;
+        let mut specified_by_url = None;
 
         for arg in args {
             match arg {
@@ -908,6 +1316,15 @@ impl Scalar {
                                 "Attribute 'desc' should be a string.",
                             ));
                         }
+                    } else if nv.path.is_ident("specified_by_url") {
+                        if let syn::Lit::Str(lit) = nv.lit {
+                            specified_by_url = Some(lit.value());
+                        } else {
+                            return Err(Error::new_spanned(
+                                &nv.lit,
+                                "Attribute 'specified_by_url' should be a string.",
+                            ));
+                        }
                     }
                 }
                 _ => {}
@@ -918,6 +1335,7 @@ impl Scalar {
             internal,
             name,
             desc,
+            specified_by_url,
         })
     }
 }
@@ -0,0 +1,60 @@
+use crate::args;
+use crate::object::generate_field;
+use crate::utils::get_crate_name;
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Error, ImplItem, ItemImpl, Result};
+
+pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<TokenStream> {
+    let crate_name = get_crate_name(object_args.internal);
+    let self_ty = &item_impl.self_ty;
+    let generics = &item_impl.generics;
+    let where_clause = &item_impl.generics.where_clause;
+
+    let mut resolvers = Vec::new();
+    let mut schema_fields = Vec::new();
+
+    for item in &mut item_impl.items {
+        if let ImplItem::Method(method) = item {
+            if args::Entity::parse(&crate_name, &method.attrs)?.is_some() {
+                return Err(Error::new_spanned(
+                    &method,
+                    "Entities are not supported on a ComplexObject.",
+                ));
+            } else if let Some(field) = args::Field::parse(&crate_name, &method.attrs)? {
+                let (schema_field, resolver) =
+                    generate_field(&crate_name, object_args, method, field, true)?;
+                schema_fields.push(schema_field);
+                resolvers.push(resolver);
+            } else if let Some((idx, _)) = method
+                .attrs
+                .iter()
+                .enumerate()
+                .find(|(_, a)| a.path.is_ident("field"))
+            {
+                method.attrs.remove(idx);
+            }
+        }
+    }
+
+    let expanded = quote! {
+        #item_impl
+
+        #[allow(clippy::all, clippy::pedantic, clippy::suspicious_else_formatting)]
+        #[allow(unused_braces, unused_variables, unused_parens, unused_mut)]
+        #[#crate_name::async_trait::async_trait]
+        impl #generics #crate_name::resolver_utils::ComplexObject for #self_ty #where_clause {
+            fn fields(registry: &mut #crate_name::registry::Registry) -> #crate_name::indexmap::IndexMap<String, #crate_name::registry::MetaField> {
+                let mut fields = #crate_name::indexmap::IndexMap::new();
+                #(#schema_fields)*
+                fields
+            }
+
+            async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<::std::option::Option<#crate_name::Value>> {
+                #(#resolvers)*
+                Ok(None)
+            }
+        }
+    };
+    Ok(expanded.into())
+}
@@ -1,6 +1,5 @@
 use crate::args;
-use crate::utils::{get_crate_name, get_rustdoc};
-use inflector::Inflector;
+use crate::utils::{generate_default_names, get_crate_name, get_rustdoc};
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::ext::IdentExt;
@@ -40,10 +39,17 @@ pub fn generate(enum_args: &args::Enum, input: &DeriveInput) -> Result<TokenStre
 
         let item_ident = &variant.ident;
         let mut item_args = args::EnumItem::parse(&variant.attrs)?;
-        let gql_item_name = item_args
-            .name
-            .take()
-            .unwrap_or_else(|| variant.ident.unraw().to_string().to_screaming_snake_case());
+        let default_names = generate_default_names(
+            &variant.ident.unraw().to_string(),
+            item_args.name.is_some() || enum_args.rename_items.is_some(),
+            enum_args.internal,
+        );
+        let gql_item_name = item_args.name.take().unwrap_or_else(|| {
+            enum_args
+                .rename_items
+                .unwrap_or(args::RenameRule::ScreamingSnakeCase)
+                .rename(variant.ident.unraw().to_string())
+        });
         let item_deprecation = item_args
             .deprecation
             .as_ref()
@@ -66,6 +72,7 @@ pub fn generate(enum_args: &args::Enum, input: &DeriveInput) -> Result<TokenStre
                 name: #gql_item_name,
                 description: #item_desc,
                 deprecation: #item_deprecation,
+                default_names: #default_names,
             });
         });
     }
@@ -153,8 +160,8 @@ pub fn generate(enum_args: &args::Enum, input: &DeriveInput) -> Result<TokenStre
 
         #[#crate_name::async_trait::async_trait]
         impl #crate_name::OutputValueType for #ident {
-            async fn resolve(&self, _: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::Result<#crate_name::serde_json::Value> {
-                Ok(#crate_name::resolver_utils::enum_value(*self).into_json().unwrap())
+            async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::Result<#crate_name::Value> {
+                Ok(ctx.schema_env.registry.value_to_schema(#gql_typename, #crate_name::resolver_utils::enum_value(*self)))
             }
         }
 
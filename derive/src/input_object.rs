@@ -1,6 +1,5 @@
 use crate::args;
-use crate::utils::{get_crate_name, get_rustdoc};
-use inflector::Inflector;
+use crate::utils::{generate_default_names, get_crate_name, get_rustdoc};
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::ext::IdentExt;
@@ -9,6 +8,7 @@ use syn::{Data, DeriveInput, Error, Result};
 pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<TokenStream> {
     let crate_name = get_crate_name(object_args.internal);
     let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
     let s = match &input.data {
         Data::Struct(s) => s,
         _ => return Err(Error::new_spanned(input, "It should be a struct.")),
@@ -30,11 +30,6 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
         });
     }
 
-    let gql_typename = object_args
-        .name
-        .clone()
-        .unwrap_or_else(|| ident.to_string());
-
     let desc = object_args
         .desc
         .clone()
@@ -52,18 +47,26 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
         let field_args = args::InputField::parse(&crate_name, &field.attrs)?;
         let ident = field.ident.as_ref().unwrap();
         let ty = &field.ty;
-        let name = field_args
-            .name
-            .unwrap_or_else(|| ident.unraw().to_string().to_camel_case());
+        let default_names = generate_default_names(
+            &ident.unraw().to_string(),
+            field_args.name.is_some() || object_args.rename_fields.is_some(),
+            object_args.internal,
+        );
+        let name = field_args.name.unwrap_or_else(|| {
+            object_args
+                .rename_fields
+                .unwrap_or(args::RenameRule::CamelCase)
+                .rename(ident.unraw().to_string())
+        });
 
         if field_args.flatten {
             flatten_fields.push((ident, ty));
 
             schema_fields.push(quote! {
                 #crate_name::static_assertions::assert_impl_one!(#ty: #crate_name::type_mark::TypeMarkInputObject);
-                #ty::create_type_info(registry);
+                <#ty as #crate_name::InputValueType>::create_input_type_info(registry);
                 if let Some(#crate_name::registry::MetaType::InputObject{ input_fields, .. }) =
-                    registry.types.remove(&*<#ty as #crate_name::Type>::type_name()) {
+                    registry.types.remove(&*<#ty as #crate_name::InputValueType>::input_type_name()) {
                     fields.extend(input_fields);
                 }
             });
@@ -83,11 +86,17 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
         }
 
         let validator = &field_args.validator;
+        let secret = field_args.secret;
         let desc = field_args
             .desc
             .as_ref()
             .map(|s| quote! {Some(#s)})
             .unwrap_or_else(|| quote! {None});
+        let deprecation = field_args
+            .deprecation
+            .as_ref()
+            .map(|s| quote! {Some(#s)})
+            .unwrap_or_else(|| quote! {None});
         let schema_default = field_args
             .default
             .as_ref()
@@ -123,36 +132,128 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
             fields.insert(#name.to_string(), #crate_name::registry::MetaInputValue {
                 name: #name,
                 description: #desc,
-                ty: <#ty as #crate_name::Type>::create_type_info(registry),
+                ty: <#ty as #crate_name::InputValueType>::create_input_type_info(registry),
                 default_value: #schema_default,
+                deprecation: #deprecation,
                 validator: #validator,
+                is_secret: #secret,
+                default_names: #default_names,
             });
         })
     }
 
-    let expanded = quote! {
-        #[allow(clippy::all, clippy::pedantic)]
-        impl #crate_name::Type for #ident {
-            fn type_name() -> ::std::borrow::Cow<'static, str> {
-                ::std::borrow::Cow::Borrowed(#gql_typename)
+    // The fields are registered, parsed and serialized by generic helpers, so that each concrete
+    // instantiation of a generic struct only needs its own `Type` and `InputValueType` impls.
+    let mut type_impls = Vec::new();
+    let mut add_type_impls = |gql_typename: &str,
+                              input_name: bool,
+                              impl_generics: proc_macro2::TokenStream,
+                              self_ty: proc_macro2::TokenStream,
+                              where_clause: proc_macro2::TokenStream| {
+        let meta_type = quote! {
+            #crate_name::registry::MetaType::InputObject {
+                name: #gql_typename.to_string(),
+                description: #desc,
+                input_fields: Self::__internal_create_input_fields(registry),
+                oneof: false,
             }
+        };
+
+        // With `input_name`, the `Type` implementation comes from the output side (e.g. `SimpleObject`),
+        // so the input side is registered through `InputValueType` under its own name.
+        let (type_impl, input_type_info) = if input_name {
+            (
+                quote! {},
+                quote! {
+                    fn input_type_name() -> ::std::borrow::Cow<'static, str> {
+                        ::std::borrow::Cow::Borrowed(#gql_typename)
+                    }
+
+                    fn qualified_input_type_name() -> String {
+                        format!("{}!", #gql_typename)
+                    }
+
+                    fn create_input_type_info(registry: &mut #crate_name::registry::Registry) -> String {
+                        registry.create_input_type::<Self, _>(|registry| #meta_type)
+                    }
+                },
+            )
+        } else {
+            (
+                quote! {
+                    #[allow(clippy::all, clippy::pedantic)]
+                    impl #impl_generics #crate_name::Type for #self_ty #where_clause {
+                        fn type_name() -> ::std::borrow::Cow<'static, str> {
+                            ::std::borrow::Cow::Borrowed(#gql_typename)
+                        }
 
-            fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
-                registry.create_type::<Self, _>(|registry| #crate_name::registry::MetaType::InputObject {
-                    name: #gql_typename.to_string(),
-                    description: #desc,
-                    input_fields: {
-                        let mut fields = #crate_name::indexmap::IndexMap::new();
-                        #(#schema_fields)*
-                        fields
+                        fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
+                            registry.create_type::<Self, _>(|registry| #meta_type)
+                        }
                     }
-                })
+                },
+                quote! {},
+            )
+        };
+
+        type_impls.push(quote! {
+            #type_impl
+
+            #[allow(clippy::all, clippy::pedantic)]
+            impl #impl_generics #crate_name::InputValueType for #self_ty #where_clause {
+                #input_type_info
+
+                fn parse(value: Option<#crate_name::Value>) -> #crate_name::InputValueResult<Self> {
+                    Self::__internal_parse(value)
+                }
+
+                fn to_value(&self) -> #crate_name::Value {
+                    self.__internal_to_value()
+                }
             }
+
+            impl #impl_generics #crate_name::type_mark::TypeMarkInputObject for #self_ty #where_clause {}
+        });
+    };
+
+    if object_args.concretes.is_empty() {
+        let gql_typename = object_args
+            .input_name
+            .clone()
+            .or_else(|| object_args.name.clone())
+            .unwrap_or_else(|| ident.to_string());
+        add_type_impls(
+            &gql_typename,
+            object_args.input_name.is_some(),
+            quote! { #impl_generics },
+            quote! { #ident #ty_generics },
+            quote! { #where_clause },
+        );
+    } else {
+        for concrete in &object_args.concretes {
+            let params = &concrete.params;
+            add_type_impls(
+                concrete.input_name.as_ref().unwrap_or(&concrete.name),
+                concrete.input_name.is_some(),
+                quote! {},
+                quote! { #ident<#(#params),*> },
+                quote! {},
+            );
         }
+    }
 
+    let expanded = quote! {
         #[allow(clippy::all, clippy::pedantic)]
-        impl #crate_name::InputValueType for #ident {
-            fn parse(value: Option<#crate_name::Value>) -> #crate_name::InputValueResult<Self> {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #[doc(hidden)]
+            fn __internal_create_input_fields(registry: &mut #crate_name::registry::Registry) -> #crate_name::indexmap::IndexMap<String, #crate_name::registry::MetaInputValue> {
+                let mut fields = #crate_name::indexmap::IndexMap::new();
+                #(#schema_fields)*
+                fields
+            }
+
+            #[doc(hidden)]
+            fn __internal_parse(value: Option<#crate_name::Value>) -> #crate_name::InputValueResult<Self> {
                 if let Some(#crate_name::Value::Object(obj)) = value {
                     #(#get_fields)*
                     Ok(Self { #(#fields),* })
@@ -161,14 +262,15 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
                 }
             }
 
-            fn to_value(&self) -> #crate_name::Value {
-                let mut map = ::std::collections::BTreeMap::new();
+            #[doc(hidden)]
+            fn __internal_to_value(&self) -> #crate_name::Value {
+                let mut map = #crate_name::indexmap::IndexMap::new();
                 #(#put_fields)*
                 #crate_name::Value::Object(map)
             }
         }
 
-        impl #crate_name::type_mark::TypeMarkInputObject for #ident {}
+        #(#type_impls)*
     };
     Ok(expanded.into())
 }
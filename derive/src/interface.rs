@@ -1,8 +1,7 @@
 use crate::args;
 use crate::args::{InterfaceField, InterfaceFieldArgument};
 use crate::output_type::OutputType;
-use crate::utils::{get_crate_name, get_rustdoc};
-use inflector::Inflector;
+use crate::utils::{generate_default_names, get_crate_name, get_rustdoc};
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
 use quote::quote;
@@ -106,6 +105,13 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
         }
     }
 
+    for implement in &interface_args.implements {
+        registry_types.push(quote! {
+            <#implement as #crate_name::Type>::create_type_info(registry);
+            registry.add_implements(#gql_typename, &<#implement as #crate_name::Type>::type_name());
+        });
+    }
+
     let mut methods = Vec::new();
     let mut schema_fields = Vec::new();
     let mut resolvers = Vec::new();
@@ -122,11 +128,22 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
         requires,
     } in &interface_args.fields
     {
+        let field_default_names = generate_default_names(
+            name,
+            method.is_some() || interface_args.rename_fields.is_some(),
+            interface_args.internal,
+        );
         let (name, method_name) = if let Some(method) = method {
             (name.to_string(), Ident::new(method, Span::call_site()))
         } else {
             let method_name = Ident::new(&name, Span::call_site());
-            (name.to_camel_case(), method_name)
+            (
+                interface_args
+                    .rename_fields
+                    .unwrap_or(args::RenameRule::CamelCase)
+                    .rename(name),
+                method_name,
+            )
         };
         let mut calls = Vec::new();
         let mut use_params = Vec::new();
@@ -148,12 +165,22 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
         for InterfaceFieldArgument {
             name,
             desc,
+            deprecation,
             ty,
             default,
+            secret,
         } in args
         {
             let ident = Ident::new(name, Span::call_site());
-            let name = name.to_camel_case();
+            let default_names = generate_default_names(
+                name,
+                interface_args.rename_args.is_some(),
+                interface_args.internal,
+            );
+            let name = interface_args
+                .rename_args
+                .unwrap_or(args::RenameRule::CamelCase)
+                .rename(name);
             decl_params.push(quote! { #ident: #ty });
             use_params.push(quote! { #ident });
 
@@ -169,6 +196,10 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
                 .as_ref()
                 .map(|s| quote! {Some(#s)})
                 .unwrap_or_else(|| quote! {None});
+            let deprecation = deprecation
+                .as_ref()
+                .map(|s| quote! {Some(#s)})
+                .unwrap_or_else(|| quote! {None});
             let schema_default = default
                 .as_ref()
                 .map(|value| {
@@ -179,9 +210,12 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
                 args.insert(#name, #crate_name::registry::MetaInputValue {
                     name: #name,
                     description: #desc,
-                    ty: <#ty as #crate_name::Type>::create_type_info(registry),
+                    ty: <#ty as #crate_name::InputValueType>::create_input_type_info(registry),
                     default_value: #schema_default,
+                    deprecation: #deprecation,
                     validator: None,
+                    is_secret: #secret,
+                    default_names: #default_names,
                 });
             });
         }
@@ -232,6 +266,9 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
                 external: #external,
                 provides: #provides,
                 requires: #requires,
+                timeout: None,
+                is_secret: false,
+                default_names: #field_default_names,
             });
         });
 
@@ -241,7 +278,7 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
         };
 
         resolvers.push(quote! {
-            if ctx.item.node.name.node == #name {
+            if ctx.field_code_name() == #name {
                 #(#get_params)*
                 let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
                 return #crate_name::OutputValueType::resolve(&#resolve_obj, &ctx_obj, ctx.item).await;
@@ -304,7 +341,7 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
         #[allow(clippy::all, clippy::pedantic)]
         #[#crate_name::async_trait::async_trait]
         impl #generics #crate_name::resolver_utils::ObjectType for #ident #generics {
-            async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#crate_name::serde_json::Value> {
+            async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#crate_name::Value> {
                 #(#resolvers)*
                 Err(#crate_name::QueryError::FieldNotFound {
                     field_name: ctx.item.node.name.to_string(),
@@ -322,7 +359,7 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
         #[allow(clippy::all, clippy::pedantic)]
         #[#crate_name::async_trait::async_trait]
         impl #generics #crate_name::OutputValueType for #ident #generics {
-            async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::Result<#crate_name::serde_json::Value> {
+            async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::Result<#crate_name::Value> {
                 #crate_name::resolver_utils::resolve_object(ctx, self).await
             }
         }
@@ -83,10 +83,14 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
 
         #[allow(clippy::all, clippy::pedantic)]
         impl #crate_name::SubscriptionType for #ident {
+            fn applies_error_policy(field_name: &str) -> bool {
+                false #(|| <#types as #crate_name::SubscriptionType>::applies_error_policy(field_name))*
+            }
+
             fn create_field_stream<'a>(
                 &'a self,
                 ctx: &'a #crate_name::Context<'a>
-            ) -> ::std::pin::Pin<::std::boxed::Box<dyn #crate_name::futures::Stream<Item = #crate_name::Result<#crate_name::serde_json::Value>> + Send + 'a>> {
+            ) -> ::std::pin::Pin<::std::boxed::Box<dyn #crate_name::futures::Stream<Item = #crate_name::Result<#crate_name::Value>> + Send + 'a>> {
                 ::std::boxed::Box::pin(#crate_name::async_stream::stream! {
                     let obj = #create_merged_obj;
                     let mut stream = obj.create_field_stream(ctx);
@@ -1,11 +1,15 @@
 use crate::args;
 use crate::output_type::OutputType;
-use crate::utils::{get_cfg_attrs, get_crate_name, get_param_getter_ident, get_rustdoc};
-use inflector::Inflector;
+use crate::utils::{
+    generate_default_names, get_cfg_attrs, get_crate_name, get_param_getter_ident, get_rustdoc,
+};
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::ext::IdentExt;
-use syn::{Block, Error, FnArg, ImplItem, ItemImpl, Pat, Result, ReturnType, Type, TypeReference};
+use syn::{
+    Block, Error, FnArg, ImplItem, ImplItemMethod, ItemImpl, Pat, Result, ReturnType, Type,
+    TypeReference,
+};
 
 pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<TokenStream> {
     let crate_name = get_crate_name(object_args.internal);
@@ -128,9 +132,12 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
 
                 for (ident, ty, args::Argument { name, key, .. }) in &args {
                     let is_key = all_key || *key;
-                    let name = name
-                        .clone()
-                        .unwrap_or_else(|| ident.ident.unraw().to_string().to_camel_case());
+                    let name = name.clone().unwrap_or_else(|| {
+                        object_args
+                            .rename_args
+                            .unwrap_or(args::RenameRule::CamelCase)
+                            .rename(ident.ident.unraw().to_string())
+                    });
 
                     if is_key {
                         if !keys_str.is_empty() {
@@ -153,7 +160,7 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         // requires
                         requires_getter.push(quote! {
                             let #ident: #ty = #crate_name::InputValueType::parse(params.get(#name).cloned()).
-                                map_err(|err| err.into_error(ctx.item.pos, <#ty as #crate_name::Type>::qualified_type_name()))?;
+                                map_err(|err| err.into_error(ctx.item.pos, <#ty as #crate_name::InputValueType>::qualified_input_type_name()))?;
                         });
                         use_keys.push(ident);
                     }
@@ -205,235 +212,10 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         .unwrap(),
                 );
             } else if let Some(field) = args::Field::parse(&crate_name, &method.attrs)? {
-                if method.sig.asyncness.is_none() {
-                    return Err(Error::new_spanned(&method, "Must be asynchronous"));
-                }
-
-                let field_name = field
-                    .name
-                    .clone()
-                    .unwrap_or_else(|| method.sig.ident.unraw().to_string().to_camel_case());
-                let field_desc = field
-                    .desc
-                    .as_ref()
-                    .map(|s| quote! {Some(#s)})
-                    .unwrap_or_else(|| quote! {None});
-                let field_deprecation = field
-                    .deprecation
-                    .as_ref()
-                    .map(|s| quote! {Some(#s)})
-                    .unwrap_or_else(|| quote! {None});
-                let external = field.external;
-                let requires = match &field.requires {
-                    Some(requires) => quote! { Some(#requires) },
-                    None => quote! { None },
-                };
-                let provides = match &field.provides {
-                    Some(provides) => quote! { Some(#provides) },
-                    None => quote! { None },
-                };
-                let ty = match &method.sig.output {
-                    ReturnType::Type(_, ty) => OutputType::parse(ty)?,
-                    ReturnType::Default => {
-                        return Err(Error::new_spanned(&method.sig.output, "Missing type"))
-                    }
-                };
-                let cache_control = {
-                    let public = field.cache_control.public;
-                    let max_age = field.cache_control.max_age;
-                    quote! {
-                        #crate_name::CacheControl {
-                            public: #public,
-                            max_age: #max_age,
-                        }
-                    }
-                };
-                let cfg_attrs = get_cfg_attrs(&method.attrs);
-
-                let mut create_ctx = true;
-                let mut args = Vec::new();
-
-                for (idx, arg) in method.sig.inputs.iter_mut().enumerate() {
-                    if let FnArg::Receiver(receiver) = arg {
-                        if idx != 0 {
-                            return Err(Error::new_spanned(
-                                receiver,
-                                "The self receiver must be the first parameter.",
-                            ));
-                        }
-                    } else if let FnArg::Typed(pat) = arg {
-                        if idx == 0 {
-                            return Err(Error::new_spanned(
-                                pat,
-                                "The self receiver must be the first parameter.",
-                            ));
-                        }
-
-                        match (&*pat.pat, &*pat.ty) {
-                            (Pat::Ident(arg_ident), Type::Path(arg_ty)) => {
-                                args.push((
-                                    arg_ident.clone(),
-                                    arg_ty.clone(),
-                                    args::Argument::parse(&crate_name, &pat.attrs)?,
-                                ));
-                                pat.attrs.clear();
-                            }
-                            (arg, Type::Reference(TypeReference { elem, .. })) => {
-                                if let Type::Path(path) = elem.as_ref() {
-                                    if idx != 1
-                                        || path.path.segments.last().unwrap().ident != "Context"
-                                    {
-                                        return Err(Error::new_spanned(
-                                            arg,
-                                            "The Context must be the second argument.",
-                                        ));
-                                    }
-
-                                    create_ctx = false;
-                                }
-                            }
-                            _ => return Err(Error::new_spanned(arg, "Invalid argument type.")),
-                        }
-                    }
-                }
-
-                if create_ctx {
-                    let arg =
-                        syn::parse2::<FnArg>(quote! { _: &#crate_name::Context<'_> }).unwrap();
-                    method.sig.inputs.insert(1, arg);
-                }
-
-                let mut schema_args = Vec::new();
-                let mut use_params = Vec::new();
-                let mut get_params = Vec::new();
-
-                for (
-                    ident,
-                    ty,
-                    args::Argument {
-                        name,
-                        desc,
-                        default,
-                        validator,
-                        ..
-                    },
-                ) in args
-                {
-                    let name = name
-                        .clone()
-                        .unwrap_or_else(|| ident.ident.unraw().to_string().to_camel_case());
-                    let desc = desc
-                        .as_ref()
-                        .map(|s| quote! {Some(#s)})
-                        .unwrap_or_else(|| quote! {None});
-                    let schema_default = default
-                        .as_ref()
-                        .map(|value| {
-                            quote! {Some( <#ty as #crate_name::InputValueType>::to_value(&#value).to_string() )}
-                        })
-                        .unwrap_or_else(|| quote! {None});
-
-                    schema_args.push(quote! {
-                        args.insert(#name, #crate_name::registry::MetaInputValue {
-                            name: #name,
-                            description: #desc,
-                            ty: <#ty as #crate_name::Type>::create_type_info(registry),
-                            default_value: #schema_default,
-                            validator: #validator,
-                        });
-                    });
-
-                    let param_ident = &ident.ident;
-                    use_params.push(quote! { #param_ident });
-
-                    let default = match default {
-                        Some(default) => quote! { Some(|| -> #ty { #default }) },
-                        None => quote! { None },
-                    };
-                    let param_getter_name = get_param_getter_ident(&ident.ident.to_string());
-                    get_params.push(quote! {
-                        let #param_getter_name = || -> #crate_name::Result<#ty> { ctx.param_value(#name, #default) };
-                        let #ident: #ty = #param_getter_name()?;
-                    });
-                }
-
-                let schema_ty = ty.value_type();
-
-                schema_fields.push(quote! {
-                    #(#cfg_attrs)*
-                    fields.insert(#field_name.to_string(), #crate_name::registry::MetaField {
-                        name: #field_name.to_string(),
-                        description: #field_desc,
-                        args: {
-                            let mut args = #crate_name::indexmap::IndexMap::new();
-                            #(#schema_args)*
-                            args
-                        },
-                        ty: <#schema_ty as #crate_name::Type>::create_type_info(registry),
-                        deprecation: #field_deprecation,
-                        cache_control: #cache_control,
-                        external: #external,
-                        provides: #provides,
-                        requires: #requires,
-                    });
-                });
-
-                let field_ident = &method.sig.ident;
-                if let OutputType::Value(inner_ty) = &ty {
-                    let block = &method.block;
-                    let new_block = quote!({
-                        {
-                            let value:#inner_ty = async move #block.await;
-                            Ok(value)
-                        }
-                    });
-                    method.block = syn::parse2::<Block>(new_block).expect("invalid block");
-                    method.sig.output = syn::parse2::<ReturnType>(
-                        quote! { -> #crate_name::FieldResult<#inner_ty> },
-                    )
-                    .expect("invalid result type");
-                }
-
-                let resolve_obj = quote! {
-                    {
-                        let res = self.#field_ident(ctx, #(#use_params),*).await;
-                        res.map_err(|err| err.into_error_with_path(ctx.item.pos, ctx.path_node.as_ref()))?
-                    }
-                };
-
-                let guard = field
-                    .guard
-                    .map(|guard| quote! {
-                        #guard.check(ctx).await
-                            .map_err(|err| err.into_error_with_path(ctx.item.pos, ctx.path_node.as_ref()))?;
-                    });
-                let post_guard = field
-                    .post_guard
-                    .map(|guard| quote! {
-                        #guard.check(ctx, &res).await
-                            .map_err(|err| err.into_error_with_path(ctx.item.pos, ctx.path_node.as_ref()))?;
-                    });
-
-                resolvers.push(quote! {
-                    #(#cfg_attrs)*
-                    if ctx.item.node.name.node == #field_name {
-                        #(#get_params)*
-                        #guard
-                        let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
-                        let res = #resolve_obj;
-                        #post_guard
-                        return #crate_name::OutputValueType::resolve(&res, &ctx_obj, ctx.item).await;
-                    }
-                });
-
-                if let Some((idx, _)) = method
-                    .attrs
-                    .iter()
-                    .enumerate()
-                    .find(|(_, a)| a.path.is_ident("field"))
-                {
-                    method.attrs.remove(idx);
-                }
+                let (schema_field, resolver) =
+                    generate_field(&crate_name, object_args, method, field, false)?;
+                schema_fields.push(schema_field);
+                resolvers.push(resolver);
             } else if let Some((idx, _)) = method
                 .attrs
                 .iter()
@@ -456,6 +238,23 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
         }
     };
 
+    let add_node = object_args.node.as_ref().map(|node| {
+        let loader = &node.loader;
+        quote! {
+            registry.add_node(#gql_typename, |ctx, ctx_obj, id| ::std::boxed::Box::pin(async move {
+                let node: ::std::option::Option<Self> = #loader(ctx, id)
+                    .await
+                    .map_err(|err| err.into_error_with_path(ctx.item.pos, ctx.path_node.as_ref()))?;
+                match node {
+                    ::std::option::Option::Some(node) => #crate_name::OutputValueType::resolve(&node, ctx_obj, ctx.item)
+                        .await
+                        .map(::std::option::Option::Some),
+                    ::std::option::Option::None => ::std::result::Result::Ok(::std::option::Option::None),
+                }
+            }));
+        }
+    });
+
     find_entities.sort_by(|(a, _), (b, _)| b.cmp(a));
     let find_entities_iter = find_entities.iter().map(|(_, code)| code);
 
@@ -483,6 +282,7 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                 });
                 #(#create_entity_types)*
                 #(#add_keys)*
+                #add_node
                 ty
             }
         }
@@ -491,7 +291,7 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
         #[allow(unused_braces, unused_variables, unused_parens, unused_mut)]
         #[#crate_name::async_trait::async_trait]
         impl#generics #crate_name::resolver_utils::ObjectType for #self_ty #where_clause {
-            async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#crate_name::serde_json::Value> {
+            async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#crate_name::Value> {
                 #(#resolvers)*
                 Err(#crate_name::QueryError::FieldNotFound {
                     field_name: ctx.item.node.name.to_string(),
@@ -499,7 +299,7 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                 }.into_error(ctx.item.pos))
             }
 
-            async fn find_entity(&self, ctx: &#crate_name::Context<'_>, params: &#crate_name::Value) -> #crate_name::Result<#crate_name::serde_json::Value> {
+            async fn find_entity(&self, ctx: &#crate_name::Context<'_>, params: &#crate_name::Value) -> #crate_name::Result<#crate_name::Value> {
                 let params = match params {
                     #crate_name::Value::Object(params) => params,
                     _ => return Err(#crate_name::QueryError::EntityNotFound.into_error(ctx.item.pos)),
@@ -517,7 +317,7 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
         #[allow(clippy::all, clippy::pedantic)]
         #[#crate_name::async_trait::async_trait]
         impl #generics #crate_name::OutputValueType for #self_ty #where_clause {
-            async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::Result<#crate_name::serde_json::Value> {
+            async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::Result<#crate_name::Value> {
                 #crate_name::resolver_utils::resolve_object(ctx, self).await
             }
         }
@@ -526,3 +326,278 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
     };
     Ok(expanded.into())
 }
+
+/// Generates the schema field and the resolver of a field defined by a method.
+///
+/// For a `ComplexObject`, the resolver returns `Some(value)` instead of the value, since the
+/// fields of the `SimpleObject` are tried first.
+pub fn generate_field(
+    crate_name: &proc_macro2::TokenStream,
+    object_args: &args::Object,
+    method: &mut ImplItemMethod,
+    field: args::Field,
+    complex: bool,
+) -> Result<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    if method.sig.asyncness.is_none() {
+        return Err(Error::new_spanned(&method, "Must be asynchronous"));
+    }
+
+    let field_name = field.name.clone().unwrap_or_else(|| {
+        object_args
+            .rename_fields
+            .unwrap_or(args::RenameRule::CamelCase)
+            .rename(method.sig.ident.unraw().to_string())
+    });
+    let field_default_names = generate_default_names(
+        &method.sig.ident.unraw().to_string(),
+        field.name.is_some() || object_args.rename_fields.is_some(),
+        object_args.internal,
+    );
+    let field_desc = field
+        .desc
+        .as_ref()
+        .map(|s| quote! {Some(#s)})
+        .unwrap_or_else(|| quote! {None});
+    let field_deprecation = field
+        .deprecation
+        .as_ref()
+        .map(|s| quote! {Some(#s)})
+        .unwrap_or_else(|| quote! {None});
+    let external = field.external;
+    let field_secret = field.secret;
+    let requires = match &field.requires {
+        Some(requires) => quote! { Some(#requires) },
+        None => quote! { None },
+    };
+    let provides = match &field.provides {
+        Some(provides) => quote! { Some(#provides) },
+        None => quote! { None },
+    };
+    let ty = match &method.sig.output {
+        ReturnType::Type(_, ty) => OutputType::parse(ty)?,
+        ReturnType::Default => return Err(Error::new_spanned(&method.sig.output, "Missing type")),
+    };
+    let cache_control = {
+        let public = field.cache_control.public;
+        let max_age = field.cache_control.max_age;
+        quote! {
+            #crate_name::CacheControl {
+                public: #public,
+                max_age: #max_age,
+            }
+        }
+    };
+    let timeout = match field.timeout {
+        Some(ms) => quote! { Some(::std::time::Duration::from_millis(#ms)) },
+        None => quote! { None },
+    };
+    let cfg_attrs = get_cfg_attrs(&method.attrs);
+
+    let mut create_ctx = true;
+    let mut args = Vec::new();
+
+    for (idx, arg) in method.sig.inputs.iter_mut().enumerate() {
+        if let FnArg::Receiver(receiver) = arg {
+            if idx != 0 {
+                return Err(Error::new_spanned(
+                    receiver,
+                    "The self receiver must be the first parameter.",
+                ));
+            }
+        } else if let FnArg::Typed(pat) = arg {
+            if idx == 0 {
+                return Err(Error::new_spanned(
+                    pat,
+                    "The self receiver must be the first parameter.",
+                ));
+            }
+
+            match (&*pat.pat, &*pat.ty) {
+                (Pat::Ident(arg_ident), Type::Path(arg_ty)) => {
+                    args.push((
+                        arg_ident.clone(),
+                        arg_ty.clone(),
+                        args::Argument::parse(&crate_name, &pat.attrs)?,
+                    ));
+                    pat.attrs.clear();
+                }
+                (arg, Type::Reference(TypeReference { elem, .. })) => {
+                    if let Type::Path(path) = elem.as_ref() {
+                        if idx != 1 || path.path.segments.last().unwrap().ident != "Context" {
+                            return Err(Error::new_spanned(
+                                arg,
+                                "The Context must be the second argument.",
+                            ));
+                        }
+
+                        create_ctx = false;
+                    }
+                }
+                _ => return Err(Error::new_spanned(arg, "Invalid argument type.")),
+            }
+        }
+    }
+
+    if create_ctx {
+        let arg = syn::parse2::<FnArg>(quote! { _: &#crate_name::Context<'_> }).unwrap();
+        method.sig.inputs.insert(1, arg);
+    }
+
+    let mut schema_args = Vec::new();
+    let mut use_params = Vec::new();
+    let mut get_params = Vec::new();
+
+    for (
+        ident,
+        ty,
+        args::Argument {
+            name,
+            desc,
+            deprecation,
+            default,
+            validator,
+            secret,
+            ..
+        },
+    ) in args
+    {
+        let default_names = generate_default_names(
+            &ident.ident.unraw().to_string(),
+            name.is_some() || object_args.rename_args.is_some(),
+            object_args.internal,
+        );
+        let name = name.clone().unwrap_or_else(|| {
+            object_args
+                .rename_args
+                .unwrap_or(args::RenameRule::CamelCase)
+                .rename(ident.ident.unraw().to_string())
+        });
+        let desc = desc
+            .as_ref()
+            .map(|s| quote! {Some(#s)})
+            .unwrap_or_else(|| quote! {None});
+        let deprecation = deprecation
+            .as_ref()
+            .map(|s| quote! {Some(#s)})
+            .unwrap_or_else(|| quote! {None});
+        let schema_default = default
+            .as_ref()
+            .map(|value| {
+                quote! {Some( <#ty as #crate_name::InputValueType>::to_value(&#value).to_string() )}
+            })
+            .unwrap_or_else(|| quote! {None});
+
+        schema_args.push(quote! {
+            args.insert(#name, #crate_name::registry::MetaInputValue {
+                name: #name,
+                description: #desc,
+                ty: <#ty as #crate_name::InputValueType>::create_input_type_info(registry),
+                default_value: #schema_default,
+                deprecation: #deprecation,
+                validator: #validator,
+                is_secret: #secret,
+                default_names: #default_names,
+            });
+        });
+
+        let param_ident = &ident.ident;
+        use_params.push(quote! { #param_ident });
+
+        let default = match default {
+            Some(default) => quote! { Some(|| -> #ty { #default }) },
+            None => quote! { None },
+        };
+        let param_getter_name = get_param_getter_ident(&ident.ident.to_string());
+        get_params.push(quote! {
+            let #param_getter_name = || -> #crate_name::Result<#ty> { ctx.param_value(#name, #default) };
+            let #ident: #ty = #param_getter_name()?;
+        });
+    }
+
+    let schema_ty = ty.value_type();
+
+    let schema_field = quote! {
+        #(#cfg_attrs)*
+        fields.insert(#field_name.to_string(), #crate_name::registry::MetaField {
+            name: #field_name.to_string(),
+            description: #field_desc,
+            args: {
+                let mut args = #crate_name::indexmap::IndexMap::new();
+                #(#schema_args)*
+                args
+            },
+            ty: <#schema_ty as #crate_name::Type>::create_type_info(registry),
+            deprecation: #field_deprecation,
+            cache_control: #cache_control,
+            external: #external,
+            provides: #provides,
+            requires: #requires,
+            timeout: #timeout,
+            is_secret: #field_secret,
+            default_names: #field_default_names,
+        });
+    };
+
+    let field_ident = &method.sig.ident;
+    if let OutputType::Value(inner_ty) = &ty {
+        let block = &method.block;
+        let new_block = quote!({
+            {
+                let value:#inner_ty = async move #block.await;
+                Ok(value)
+            }
+        });
+        method.block = syn::parse2::<Block>(new_block).expect("invalid block");
+        method.sig.output =
+            syn::parse2::<ReturnType>(quote! { -> #crate_name::FieldResult<#inner_ty> })
+                .expect("invalid result type");
+    }
+
+    let resolve_obj = quote! {
+        {
+            let res = self.#field_ident(ctx, #(#use_params),*).await;
+            res.map_err(|err| err.into_error_with_path(ctx.item.pos, ctx.path_node.as_ref()))?
+        }
+    };
+
+    let guard = field.guard.map(|guard| {
+        quote! {
+            #guard.check(ctx).await
+                .map_err(|err| err.into_error_with_path(ctx.item.pos, ctx.path_node.as_ref()))?;
+        }
+    });
+    let post_guard = field.post_guard.map(|guard| {
+        quote! {
+            #guard.check(ctx, &res).await
+                .map_err(|err| err.into_error_with_path(ctx.item.pos, ctx.path_node.as_ref()))?;
+        }
+    });
+
+    let resolve = if complex {
+        quote! { #crate_name::OutputValueType::resolve(&res, &ctx_obj, ctx.item).await.map(Some) }
+    } else {
+        quote! { #crate_name::OutputValueType::resolve(&res, &ctx_obj, ctx.item).await }
+    };
+    let resolver = quote! {
+        #(#cfg_attrs)*
+        if ctx.field_code_name() == #field_name {
+            #(#get_params)*
+            #guard
+            let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+            let res = #resolve_obj;
+            #post_guard
+            return #resolve;
+        }
+    };
+
+    if let Some((idx, _)) = method
+        .attrs
+        .iter()
+        .enumerate()
+        .find(|(_, a)| a.path.is_ident("field"))
+    {
+        method.attrs.remove(idx);
+    }
+
+    Ok((schema_field, resolver))
+}
@@ -0,0 +1,164 @@
+use crate::args;
+use crate::utils::{generate_default_names, get_crate_name, get_rustdoc};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{Data, DeriveInput, Error, Fields, Result};
+
+pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<TokenStream> {
+    let crate_name = get_crate_name(object_args.internal);
+    let ident = &input.ident;
+    let e = match &input.data {
+        Data::Enum(e) => e,
+        _ => {
+            return Err(Error::new_spanned(
+                input,
+                "OneofObject can only be applied to an enum.",
+            ))
+        }
+    };
+
+    let gql_typename = object_args
+        .name
+        .clone()
+        .unwrap_or_else(|| ident.to_string());
+
+    let desc = object_args
+        .desc
+        .clone()
+        .or_else(|| get_rustdoc(&input.attrs).ok().flatten())
+        .map(|s| quote! { Some(#s) })
+        .unwrap_or_else(|| quote! {None});
+
+    let mut parse_fields = Vec::new();
+    let mut put_fields = Vec::new();
+    let mut schema_fields = Vec::new();
+
+    for variant in &e.variants {
+        let enum_name = &variant.ident;
+        let ty = match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                &fields.unnamed.first().unwrap().ty
+            }
+            Fields::Unnamed(_) => {
+                return Err(Error::new_spanned(
+                    variant,
+                    "Only single value variants are supported",
+                ))
+            }
+            Fields::Unit => {
+                return Err(Error::new_spanned(
+                    variant,
+                    "Empty variants are not supported",
+                ))
+            }
+            Fields::Named(_) => {
+                return Err(Error::new_spanned(
+                    variant,
+                    "Variants with named fields are not supported",
+                ))
+            }
+        };
+
+        let item_args = args::OneofObjectItem::parse(&crate_name, &variant.attrs)?;
+        let default_names = generate_default_names(
+            &enum_name.unraw().to_string(),
+            item_args.name.is_some() || object_args.rename_fields.is_some(),
+            object_args.internal,
+        );
+        let name = item_args.name.clone().unwrap_or_else(|| {
+            object_args
+                .rename_fields
+                .unwrap_or(args::RenameRule::CamelCase)
+                .rename(enum_name.unraw().to_string())
+        });
+        let validator = &item_args.validator;
+        let secret = item_args.secret;
+        let desc = item_args
+            .desc
+            .as_ref()
+            .map(|s| quote! {Some(#s)})
+            .unwrap_or_else(|| quote! {None});
+        let deprecation = item_args
+            .deprecation
+            .as_ref()
+            .map(|s| quote! {Some(#s)})
+            .unwrap_or_else(|| quote! {None});
+
+        parse_fields.push(quote! {
+            #name => return Ok(#ident::#enum_name(#crate_name::InputValueType::parse(Some(value.clone()))?)),
+        });
+
+        put_fields.push(quote! {
+            #ident::#enum_name(value) => {
+                map.insert(
+                    #crate_name::parser::types::Name::new_unchecked(#name.to_owned()),
+                    #crate_name::InputValueType::to_value(value)
+                );
+            }
+        });
+
+        // All fields of a oneof input object are nullable, since only one of them is provided.
+        schema_fields.push(quote! {
+            fields.insert(#name.to_string(), #crate_name::registry::MetaInputValue {
+                name: #name,
+                description: #desc,
+                ty: <::std::option::Option<#ty> as #crate_name::InputValueType>::create_input_type_info(registry),
+                default_value: None,
+                deprecation: #deprecation,
+                validator: #validator,
+                is_secret: #secret,
+                default_names: #default_names,
+            });
+        });
+    }
+
+    let expanded = quote! {
+        #[allow(clippy::all, clippy::pedantic)]
+        impl #crate_name::Type for #ident {
+            fn type_name() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed(#gql_typename)
+            }
+
+            fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
+                registry.create_type::<Self, _>(|registry| #crate_name::registry::MetaType::InputObject {
+                    name: #gql_typename.to_string(),
+                    description: #desc,
+                    input_fields: {
+                        let mut fields = #crate_name::indexmap::IndexMap::new();
+                        #(#schema_fields)*
+                        fields
+                    },
+                    oneof: true,
+                })
+            }
+        }
+
+        #[allow(clippy::all, clippy::pedantic)]
+        impl #crate_name::InputValueType for #ident {
+            fn parse(value: Option<#crate_name::Value>) -> #crate_name::InputValueResult<Self> {
+                if let Some(#crate_name::Value::Object(obj)) = &value {
+                    if obj.len() == 1 {
+                        let (name, value) = obj.iter().next().unwrap();
+                        if !matches!(value, #crate_name::Value::Null) {
+                            match name.as_str() {
+                                #(#parse_fields)*
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                Err(#crate_name::InputValueError::ExpectedType(value.unwrap_or_default()))
+            }
+
+            fn to_value(&self) -> #crate_name::Value {
+                let mut map = #crate_name::indexmap::IndexMap::new();
+                match self {
+                    #(#put_fields)*
+                }
+                #crate_name::Value::Object(map)
+            }
+        }
+    };
+    Ok(expanded.into())
+}
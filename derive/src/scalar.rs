@@ -24,10 +24,20 @@ pub fn generate(scalar_args: &args::Scalar, item_impl: &mut ItemImpl) -> Result<
         .or_else(|| get_rustdoc(&item_impl.attrs).ok().flatten())
         .map(|s| quote! { Some(#s) })
         .unwrap_or_else(|| quote! {None});
+    let specified_by_url = scalar_args
+        .specified_by_url
+        .as_ref()
+        .map(|url| quote! { Some(#url) })
+        .unwrap_or_else(|| quote! {None});
     let self_ty = &item_impl.self_ty;
     let generic = &item_impl.generics;
     let where_clause = &item_impl.generics.where_clause;
     let crate_name = get_crate_name(scalar_args.internal);
+    let create_type = if scalar_args.internal {
+        quote! { create_builtin_type }
+    } else {
+        quote! { create_type }
+    };
     let expanded = quote! {
         #item_impl
 
@@ -38,10 +48,11 @@ pub fn generate(scalar_args: &args::Scalar, item_impl: &mut ItemImpl) -> Result<
             }
 
             fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
-                registry.create_type::<#self_ty, _>(|_| #crate_name::registry::MetaType::Scalar {
+                registry.#create_type::<#self_ty, _>(|_| #crate_name::registry::MetaType::Scalar {
                     name: #gql_typename.to_string(),
                     description: #desc,
                     is_valid: |value| <#self_ty as #crate_name::ScalarType>::is_valid(value),
+                    specified_by_url: #specified_by_url,
                 })
             }
         }
@@ -64,8 +75,8 @@ pub fn generate(scalar_args: &args::Scalar, item_impl: &mut ItemImpl) -> Result<
                 &self,
                 _: &#crate_name::ContextSelectionSet<'_>,
                 _field: &#crate_name::Positioned<#crate_name::parser::types::Field>
-            ) -> #crate_name::Result<#crate_name::serde_json::Value> {
-                Ok(#crate_name::ScalarType::to_value(self).into_json().unwrap())
+            ) -> #crate_name::Result<#crate_name::Value> {
+                Ok(#crate_name::ScalarType::to_value(self))
             }
         }
 
@@ -1,6 +1,5 @@
 use crate::args;
-use crate::utils::{get_crate_name, get_rustdoc};
-use inflector::Inflector;
+use crate::utils::{generate_default_names, get_crate_name, get_rustdoc};
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::ext::IdentExt;
@@ -9,8 +8,7 @@ use syn::{Data, DeriveInput, Error, Fields, Result};
 pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<TokenStream> {
     let crate_name = get_crate_name(object_args.internal);
     let ident = &input.ident;
-    let generics = &input.generics;
-    let where_clause = &generics.where_clause;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
     let extends = object_args.extends;
     let gql_typename = object_args
         .name
@@ -41,13 +39,16 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
         for item in &fields.named {
             if let Some(field) = args::Field::parse(&crate_name, &item.attrs)? {
                 let field_name = field.name.clone().unwrap_or_else(|| {
-                    item.ident
-                        .as_ref()
-                        .unwrap()
-                        .unraw()
-                        .to_string()
-                        .to_camel_case()
+                    object_args
+                        .rename_fields
+                        .unwrap_or(args::RenameRule::CamelCase)
+                        .rename(item.ident.as_ref().unwrap().unraw().to_string())
                 });
+                let default_names = generate_default_names(
+                    &item.ident.as_ref().unwrap().unraw().to_string(),
+                    field.name.is_some() || object_args.rename_fields.is_some(),
+                    object_args.internal,
+                );
                 let field_desc = field
                     .desc
                     .as_ref()
@@ -59,6 +60,7 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
                     .map(|s| quote! {Some(#s)})
                     .unwrap_or_else(|| quote! {None});
                 let external = field.external;
+                let secret = field.secret;
                 let requires = match &field.requires {
                     Some(requires) => quote! { Some(#requires) },
                     None => quote! { None },
@@ -92,6 +94,9 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
                         external: #external,
                         provides: #provides,
                         requires: #requires,
+                        timeout: None,
+                        is_secret: #secret,
+                        default_names: #default_names,
                     });
                 });
 
@@ -122,12 +127,12 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
                 });
 
                 resolvers.push(quote! {
-                    if ctx.item.node.name.node == #field_name {
+                    if ctx.field_code_name() == #field_name {
                         #guard
                         let res = self.#ident(ctx).await.map_err(|err| err.into_error_with_path(ctx.item.pos, ctx.path_node.as_ref()))?;
                         let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
                         #post_guard
-                        return #crate_name::OutputValueType::resolve(&res, &ctx_obj, ctx.item).await;
+                        return #crate_name::OutputValueType::resolve(&res, &ctx_obj, ctx.item).await.map(::std::option::Option::Some);
                     }
                 });
             }
@@ -145,55 +150,139 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
         }
     };
 
-    let expanded = quote! {
-        #[allow(clippy::all, clippy::pedantic)]
-        impl #generics #ident #where_clause {
-            #(#getters)*
-        }
+    let add_node = |gql_typename: &str| {
+        object_args.node.as_ref().map(|node| {
+            let loader = &node.loader;
+            quote! {
+                registry.add_node(#gql_typename, |ctx, ctx_obj, id| ::std::boxed::Box::pin(async move {
+                let node: ::std::option::Option<Self> = #loader(ctx, id)
+                    .await
+                    .map_err(|err| err.into_error_with_path(ctx.item.pos, ctx.path_node.as_ref()))?;
+                match node {
+                    ::std::option::Option::Some(node) => #crate_name::OutputValueType::resolve(&node, ctx_obj, ctx.item)
+                        .await
+                        .map(::std::option::Option::Some),
+                    ::std::option::Option::None => ::std::result::Result::Ok(::std::option::Option::None),
+                }
+            }));
+            }
+        })
+    };
 
-        #[allow(clippy::all, clippy::pedantic)]
-        impl #generics #crate_name::Type for #ident #generics #where_clause {
-            fn type_name() -> ::std::borrow::Cow<'static, str> {
-                ::std::borrow::Cow::Borrowed(#gql_typename)
+    let (complex_fields, complex_resolver) = if object_args.complex {
+        (
+            quote! {
+                fields.extend(<Self as #crate_name::resolver_utils::ComplexObject>::fields(registry));
+            },
+            quote! {
+                if let Some(value) = #crate_name::resolver_utils::ComplexObject::resolve_field(self, ctx).await? {
+                    return Ok(value);
+                }
+            },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+
+    // The fields are registered and resolved by generic helpers, so that each concrete
+    // instantiation of a generic struct only needs its own `Type` and `ObjectType` impls.
+    let mut type_impls = Vec::new();
+    let mut add_type_impls = |gql_typename: &str,
+                              impl_generics: proc_macro2::TokenStream,
+                              self_ty: proc_macro2::TokenStream,
+                              where_clause: proc_macro2::TokenStream| {
+        let add_node = add_node(gql_typename);
+        type_impls.push(quote! {
+            #[allow(clippy::all, clippy::pedantic)]
+            impl #impl_generics #crate_name::Type for #self_ty #where_clause {
+                fn type_name() -> ::std::borrow::Cow<'static, str> {
+                    ::std::borrow::Cow::Borrowed(#gql_typename)
+                }
+
+                fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
+                    let ty = registry.create_type::<Self, _>(|registry| #crate_name::registry::MetaType::Object {
+                        name: #gql_typename.to_string(),
+                        description: #desc,
+                        fields: {
+                            let mut fields = Self::__internal_create_fields(registry);
+                            #complex_fields
+                            fields
+                        },
+                        cache_control: #cache_control,
+                        extends: #extends,
+                        keys: None,
+                    });
+                    #add_node
+                    ty
+                }
+            }
+
+            #[allow(clippy::all, clippy::pedantic)]
+            #[#crate_name::async_trait::async_trait]
+            impl #impl_generics #crate_name::resolver_utils::ObjectType for #self_ty #where_clause {
+                async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#crate_name::Value> {
+                    if let Some(value) = self.__internal_resolve_field(ctx).await? {
+                        return Ok(value);
+                    }
+                    #complex_resolver
+                    Err(#crate_name::QueryError::FieldNotFound {
+                        field_name: ctx.item.node.name.to_string(),
+                        object: #gql_typename.to_string(),
+                    }.into_error(ctx.item.pos))
+                }
             }
 
-            fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
-                registry.create_type::<Self, _>(|registry| #crate_name::registry::MetaType::Object {
-                    name: #gql_typename.to_string(),
-                    description: #desc,
-                    fields: {
-                        let mut fields = #crate_name::indexmap::IndexMap::new();
-                        #(#schema_fields)*
-                        fields
-                    },
-                    cache_control: #cache_control,
-                    extends: #extends,
-                    keys: None,
-                })
+            #[allow(clippy::all, clippy::pedantic)]
+            #[#crate_name::async_trait::async_trait]
+            impl #impl_generics #crate_name::OutputValueType for #self_ty #where_clause {
+                async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::Result<#crate_name::Value> {
+                    #crate_name::resolver_utils::resolve_object(ctx, self).await
+                }
             }
+
+            impl #impl_generics #crate_name::type_mark::TypeMarkObject for #self_ty #where_clause {}
+        });
+    };
+
+    if object_args.concretes.is_empty() {
+        add_type_impls(
+            &gql_typename,
+            quote! { #impl_generics },
+            quote! { #ident #ty_generics },
+            quote! { #where_clause },
+        );
+    } else {
+        for concrete in &object_args.concretes {
+            let params = &concrete.params;
+            add_type_impls(
+                &concrete.name,
+                quote! {},
+                quote! { #ident<#(#params),*> },
+                quote! {},
+            );
         }
+    }
 
+    let expanded = quote! {
         #[allow(clippy::all, clippy::pedantic)]
-        #[#crate_name::async_trait::async_trait]
-        impl #generics #crate_name::resolver_utils::ObjectType for #ident #generics #where_clause {
-            async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#crate_name::serde_json::Value> {
-                #(#resolvers)*
-                Err(#crate_name::QueryError::FieldNotFound {
-                    field_name: ctx.item.node.name.to_string(),
-                    object: #gql_typename.to_string(),
-                }.into_error(ctx.item.pos))
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #(#getters)*
+
+            #[doc(hidden)]
+            fn __internal_create_fields(registry: &mut #crate_name::registry::Registry) -> #crate_name::indexmap::IndexMap<String, #crate_name::registry::MetaField> {
+                let mut fields = #crate_name::indexmap::IndexMap::new();
+                #(#schema_fields)*
+                fields
             }
-        }
 
-        #[allow(clippy::all, clippy::pedantic)]
-        #[#crate_name::async_trait::async_trait]
-        impl #generics #crate_name::OutputValueType for #ident #generics #where_clause {
-            async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::Result<#crate_name::serde_json::Value> {
-                #crate_name::resolver_utils::resolve_object(ctx, self).await
+            #[doc(hidden)]
+            async fn __internal_resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<::std::option::Option<#crate_name::Value>> {
+                #(#resolvers)*
+                Ok(None)
             }
         }
 
-        impl #generics #crate_name::type_mark::TypeMarkObject for #ident #generics #where_clause {}
+        #(#type_impls)*
     };
     Ok(expanded.into())
 }
@@ -1,13 +1,14 @@
 use crate::args;
 use crate::output_type::OutputType;
-use crate::utils::{get_cfg_attrs, get_crate_name, get_param_getter_ident, get_rustdoc};
-use inflector::Inflector;
+use crate::utils::{
+    generate_default_names, get_cfg_attrs, get_crate_name, get_param_getter_ident, get_rustdoc,
+};
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::ext::IdentExt;
 use syn::{
     Block, Error, FnArg, ImplItem, ItemImpl, Pat, Result, ReturnType, Type, TypeImplTrait,
-    TypeReference,
+    TypeParamBound, TypeReference,
 };
 
 pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<TokenStream> {
@@ -39,16 +40,24 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
         .unwrap_or_else(|| quote! {None});
 
     let mut create_stream = Vec::new();
+    let mut policy_fields = Vec::new();
     let mut schema_fields = Vec::new();
 
     for item in &mut item_impl.items {
         if let ImplItem::Method(method) = item {
             if let Some(field) = args::Field::parse(&crate_name, &method.attrs)? {
                 let ident = &method.sig.ident;
-                let field_name = field
-                    .name
-                    .clone()
-                    .unwrap_or_else(|| method.sig.ident.unraw().to_string().to_camel_case());
+                let field_name = field.name.clone().unwrap_or_else(|| {
+                    object_args
+                        .rename_fields
+                        .unwrap_or(args::RenameRule::CamelCase)
+                        .rename(method.sig.ident.unraw().to_string())
+                });
+                let field_default_names = generate_default_names(
+                    &method.sig.ident.unraw().to_string(),
+                    field.name.is_some() || object_args.rename_fields.is_some(),
+                    object_args.internal,
+                );
                 let field_desc = field
                     .desc
                     .as_ref()
@@ -59,6 +68,7 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                     .as_ref()
                     .map(|s| quote! {Some(#s)})
                     .unwrap_or_else(|| quote! {None});
+                let field_secret = field.secret;
                 let cfg_attrs = get_cfg_attrs(&method.attrs);
 
                 if method.sig.asyncness.is_none() {
@@ -142,19 +152,33 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                     args::Argument {
                         name,
                         desc,
+                        deprecation,
                         default,
                         validator,
+                        secret,
                         ..
                     },
                 ) in args
                 {
-                    let name = name
-                        .clone()
-                        .unwrap_or_else(|| ident.ident.unraw().to_string().to_camel_case());
+                    let default_names = generate_default_names(
+                        &ident.ident.unraw().to_string(),
+                        name.is_some() || object_args.rename_args.is_some(),
+                        object_args.internal,
+                    );
+                    let name = name.clone().unwrap_or_else(|| {
+                        object_args
+                            .rename_args
+                            .unwrap_or(args::RenameRule::CamelCase)
+                            .rename(ident.ident.unraw().to_string())
+                    });
                     let desc = desc
                         .as_ref()
                         .map(|s| quote! {Some(#s)})
                         .unwrap_or_else(|| quote! {None});
+                    let deprecation = deprecation
+                        .as_ref()
+                        .map(|s| quote! {Some(#s)})
+                        .unwrap_or_else(|| quote! {None});
                     let schema_default = default
                         .as_ref()
                         .map(|value| {
@@ -166,9 +190,12 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         args.insert(#name, #crate_name::registry::MetaInputValue {
                             name: #name,
                             description: #desc,
-                            ty: <#ty as #crate_name::Type>::create_type_info(registry),
+                            ty: <#ty as #crate_name::InputValueType>::create_input_type_info(registry),
                             default_value: #schema_default,
+                            deprecation: #deprecation,
                             validator: #validator,
+                            is_secret: #secret,
+                            default_names: #default_names,
                         });
                     });
 
@@ -187,7 +214,11 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
 
                 let res_ty = ty.value_type();
                 let stream_ty = if let Type::ImplTrait(TypeImplTrait { bounds, .. }) = &res_ty {
-                    quote! { #bounds }
+                    // Lifetime bounds such as `+ 'a` cannot be named outside of the method.
+                    let bounds = bounds
+                        .iter()
+                        .filter(|bound| matches!(bound, TypeParamBound::Trait(_)));
+                    quote! { #(#bounds)+* }
                 } else {
                     quote! { #res_ty }
                 };
@@ -223,6 +254,9 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         external: false,
                         requires: None,
                         provides: None,
+                        timeout: None,
+                        is_secret: #field_secret,
+                        default_names: #field_default_names,
                     });
                 });
 
@@ -244,9 +278,15 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                     ));
                 }
 
+                let error_policy = match &field.error_policy {
+                    Some(error_policy) => quote! { #error_policy },
+                    None => quote! { ctx.schema_env.subscription_error_policy },
+                };
+
                 let stream_fn = quote! {
                     #(#get_params)*
                     #guard
+                    let error_policy = #error_policy;
                     let field_name = ::std::sync::Arc::new(ctx.item.node.response_key().node.clone());
                     let field = ::std::sync::Arc::new(ctx.item.clone());
 
@@ -266,7 +306,7 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                                     current: 1,
                                 };
                                 let inc_resolve_id = ::std::sync::atomic::AtomicUsize::new(1);
-                                let ctx_selection_set = query_env.create_context(
+                                let mut ctx_selection_set = query_env.create_context(
                                     &schema_env,
                                     Some(#crate_name::QueryPathNode {
                                         parent: None,
@@ -276,7 +316,9 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                                     resolve_id,
                                     &inc_resolve_id,
                                 );
+                                ctx_selection_set.secret = #field_secret;
 
+                                #crate_name::extensions::Extension::subscription_event_start(&mut *query_env.extensions.lock(), &field_name);
                                 #crate_name::extensions::Extension::execution_start(&mut *query_env.extensions.lock());
 
                                 #[allow(bare_trait_objects)]
@@ -285,6 +327,7 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                                     path_node: ctx_selection_set.path_node.as_ref().unwrap(),
                                     parent_type: #gql_typename,
                                     return_type: &<<#stream_ty as #crate_name::futures::stream::Stream>::Item as #crate_name::Type>::qualified_type_name(),
+                                    is_secret: #field_secret,
                                     schema_env: &schema_env,
                                     query_env: &query_env,
                                 };
@@ -294,35 +337,32 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                                 let res = #crate_name::OutputValueType::resolve(&msg, &ctx_selection_set, &*field)
                                     .await
                                     .map(|value| {
-                                        #crate_name::serde_json::json!({
-                                            field_name.as_str(): value
-                                        })
+                                        let mut map = #crate_name::indexmap::IndexMap::new();
+                                        map.insert(::std::clone::Clone::clone(&*field_name), value);
+                                        #crate_name::Value::Object(map)
                                     });
 
                                 #crate_name::extensions::Extension::resolve_end(&mut *query_env.extensions.lock(), &ri);
                                 #crate_name::extensions::Extension::execution_end(&mut *query_env.extensions.lock());
+                                let res = schema_env.log_resolver_error(res, &query_env.extensions);
+                                #crate_name::extensions::Extension::subscription_event_end(&mut *query_env.extensions.lock(), &field_name);
                                 res
                             }
                         }
                     });
-                    #crate_name::Result::Ok(#crate_name::futures::StreamExt::scan(
-                        stream,
-                        false,
-                        |errored, item| {
-                            if *errored {
-                                return #crate_name::futures::future::ready(None);
-                            }
-                            if item.is_err() {
-                                *errored = true;
-                            }
-                            #crate_name::futures::future::ready(Some(item))
-                        },
-                    ))
+                    #crate_name::Result::Ok(error_policy.apply(stream))
                 };
 
+                policy_fields.push(quote! {
+                    #(#cfg_attrs)*
+                    if field_name == #field_name {
+                        return true;
+                    }
+                });
+
                 create_stream.push(quote! {
                     #(#cfg_attrs)*
-                    if ctx.item.node.name.node == #field_name {
+                    if ctx.field_code_name() == #field_name {
                         return ::std::boxed::Box::pin(
                             #crate_name::futures::TryStreamExt::try_flatten(
                                 #crate_name::futures::stream::once((move || async move { #stream_fn })())
@@ -372,10 +412,15 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
         #[allow(clippy::all, clippy::pedantic)]
         #[allow(unused_braces, unused_variables)]
         impl #crate_name::SubscriptionType for #self_ty #where_clause {
+            fn applies_error_policy(field_name: &str) -> bool {
+                #(#policy_fields)*
+                false
+            }
+
             fn create_field_stream<'a>(
                 &'a self,
                 ctx: &'a #crate_name::Context<'a>,
-            ) -> ::std::pin::Pin<::std::boxed::Box<dyn #crate_name::futures::Stream<Item = #crate_name::Result<#crate_name::serde_json::Value>> + Send + 'a>> {
+            ) -> ::std::pin::Pin<::std::boxed::Box<dyn #crate_name::futures::Stream<Item = #crate_name::Result<#crate_name::Value>> + Send + 'a>> {
                 #(#create_stream)*
                 let error = #crate_name::QueryError::FieldNotFound {
                     field_name: ctx.item.node.name.to_string(),
@@ -166,7 +166,7 @@ pub fn generate(union_args: &args::Interface, input: &DeriveInput) -> Result<Tok
         #[allow(clippy::all, clippy::pedantic)]
         #[#crate_name::async_trait::async_trait]
         impl #generics #crate_name::resolver_utils::ObjectType for #ident #generics {
-            async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#crate_name::serde_json::Value> {
+            async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#crate_name::Value> {
                 Err(#crate_name::QueryError::FieldNotFound {
                     field_name: ctx.item.node.name.to_string(),
                     object: #gql_typename.to_string(),
@@ -183,7 +183,7 @@ pub fn generate(union_args: &args::Interface, input: &DeriveInput) -> Result<Tok
         #[allow(clippy::all, clippy::pedantic)]
         #[#crate_name::async_trait::async_trait]
         impl #generics #crate_name::OutputValueType for #ident #generics {
-            async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::Result<#crate_name::serde_json::Value> {
+            async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::Result<#crate_name::Value> {
                 #crate_name::resolver_utils::resolve_object(ctx, self).await
             }
         }
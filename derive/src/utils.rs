@@ -1,3 +1,4 @@
+use crate::args::RenameRule;
 use itertools::Itertools;
 use proc_macro2::{Span, TokenStream, TokenTree};
 use proc_macro_crate::crate_name;
@@ -274,6 +275,38 @@ pub fn parse_default_with(lit: &Lit) -> Result<TokenStream> {
     }
 }
 
+pub fn parse_duration(lit: &Lit) -> Result<u64> {
+    if let Lit::Str(str) = lit {
+        let value = str.value();
+        let value = value.trim();
+        let unit_pos = value
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(value.len());
+        let n = value[..unit_pos]
+            .parse::<u64>()
+            .map_err(|_| Error::new_spanned(&lit, "Invalid duration."))?;
+        let millis_per_unit = match &value[unit_pos..] {
+            "ms" => 1,
+            "s" => 1000,
+            "m" => 60 * 1000,
+            "h" => 60 * 60 * 1000,
+            _ => {
+                return Err(Error::new_spanned(
+                    &lit,
+                    "Duration must be an integer followed by `ms`, `s`, `m` or `h`.",
+                ))
+            }
+        };
+        n.checked_mul(millis_per_unit)
+            .ok_or_else(|| Error::new_spanned(lit, "Duration is too large."))
+    } else {
+        Err(Error::new_spanned(
+            &lit,
+            "Attribute 'timeout' should be a string.",
+        ))
+    }
+}
+
 pub fn get_param_getter_ident(name: &str) -> Ident {
     Ident::new(&format!("__{}_getter", name), Span::call_site())
 }
@@ -285,3 +318,37 @@ pub fn get_cfg_attrs(attrs: &[Attribute]) -> Vec<Attribute> {
         .cloned()
         .collect()
 }
+
+/// Expands the `input(...)` or `output(...)` group of a `#[field(...)]` attribute for the given side
+/// of a type that is both an input and an output object, and drops the group of the other side.
+pub fn field_side_args(args: &MetaList, side: &str) -> MetaList {
+    let mut args = args.clone();
+    args.nested = args
+        .nested
+        .into_iter()
+        .flat_map(|meta| match meta {
+            NestedMeta::Meta(Meta::List(ls))
+                if ls.path.is_ident("input") || ls.path.is_ident("output") =>
+            {
+                if ls.path.is_ident(side) {
+                    ls.nested.into_iter().collect()
+                } else {
+                    Vec::new()
+                }
+            }
+            meta => vec![meta],
+        })
+        .collect();
+    args
+}
+
+/// Generates the `default_names` of a field, argument or enum item named after the Rust item
+/// `ident`. They are `None` if the name is explicit or the type chose a rename rule, and for the
+/// types of this crate, so the rename rules of the schema don't apply to them.
+pub fn generate_default_names(ident: &str, explicit: bool, internal: bool) -> TokenStream {
+    if explicit || internal {
+        return quote! { None };
+    }
+    let names = RenameRule::all_names(ident);
+    quote! { Some(&[#(#names),*]) }
+}
@@ -8,7 +8,7 @@ pub use subscription::WSSubscription;
 use actix_web::dev::{Payload, PayloadStream};
 use actix_web::http::StatusCode;
 use actix_web::{http, web, Error, FromRequest, HttpRequest, HttpResponse, Responder, Result};
-use async_graphql::http::MultipartOptions;
+use async_graphql::http::{Encoding, MultipartOptions};
 use async_graphql::ParseRequestError;
 use futures::channel::mpsc;
 use futures::future::{self, FutureExt, Ready};
@@ -114,6 +114,9 @@ impl FromRequest for BatchRequest {
 ///
 /// This contains a batch response, but since regular responses are a type of batch response it
 /// works for both.
+///
+/// The response is encoded as negotiated with the `Accept` header of the request, see
+/// `async_graphql::http::Encoding::negotiate`.
 pub struct Response(pub async_graphql::BatchResponse);
 
 impl From<async_graphql::Response> for Response {
@@ -132,14 +135,30 @@ impl Responder for Response {
     type Error = Error;
     type Future = Ready<Result<HttpResponse>>;
 
-    fn respond_to(self, _req: &HttpRequest) -> Self::Future {
+    fn respond_to(self, req: &HttpRequest) -> Self::Future {
+        let encoding = Encoding::negotiate(
+            req.headers()
+                .get(http::header::ACCEPT)
+                .and_then(|value| value.to_str().ok()),
+        );
+        let body = match encoding.encode(&self.0) {
+            Ok(body) => body,
+            Err(err) => {
+                return futures::future::err(actix_web::error::ErrorInternalServerError(err))
+            }
+        };
+
         let mut res = HttpResponse::build(StatusCode::OK);
-        res.content_type("application/json");
+        res.content_type(encoding.content_type());
+        res.header(http::header::VARY, "accept");
         if self.0.is_ok() {
             if let Some(cache_control) = self.0.cache_control().value() {
                 res.header("cache-control", cache_control);
             }
         }
-        futures::future::ok(res.body(serde_json::to_string(&self.0).unwrap()))
+        for (name, value) in &self.0.http_headers() {
+            res.header(name.clone(), value.clone());
+        }
+        futures::future::ok(res.body(body))
     }
 }
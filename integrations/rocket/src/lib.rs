@@ -3,7 +3,7 @@
 #![warn(missing_docs)]
 #![forbid(unsafe_code)]
 
-use async_graphql::http::MultipartOptions;
+use async_graphql::http::{Encoding, MultipartOptions};
 use async_graphql::{resolver_utils::ObjectType, Schema, SubscriptionType, Variables};
 use log::{error, info};
 use rocket::{
@@ -264,17 +264,28 @@ impl FromData for Request {
 /// Wrapper around `async-graphql::Response` for implementing the trait
 /// `rocket::response::responder::Responder`, so that `Response` can directly be returned
 /// from a Rocket Route function.
+///
+/// The response is encoded as negotiated with the `Accept` header of the request, see
+/// `async_graphql::http::Encoding::negotiate`.
 pub struct Response(pub async_graphql::Response);
 
 impl<'r> Responder<'r, 'static> for Response {
-    fn respond_to(self, _: &'r RocketRequest<'_>) -> response::Result<'static> {
-        let body = serde_json::to_string(&self.0).unwrap();
+    fn respond_to(self, req: &'r RocketRequest<'_>) -> response::Result<'static> {
+        let encoding = Encoding::negotiate(req.headers().get_one("Accept"));
+        let body = encoding.encode(&self.0).map_err(|e| {
+            error!("{}", e);
+            Status::InternalServerError
+        })?;
 
         RocketResponse::build()
-            .header(ContentType::new("application", "json"))
+            .header(
+                ContentType::parse_flexible(encoding.content_type()).unwrap_or(ContentType::JSON),
+            )
+            .header(Header::new("vary", "accept"))
             .status(Status::Ok)
             .sized_body(body.len(), Cursor::new(body))
             .cache_control(&self.0)
+            .http_headers(&self.0)
             .ok()
     }
 }
@@ -295,3 +306,21 @@ impl<'r> CacheControl for ResponseBuilder<'r> {
         self
     }
 }
+
+/// Extension trait, to allow the use of the HTTP headers set by resolvers with for example
+/// `async_graphql::Response`.
+pub trait HttpHeaders {
+    /// Add the `async-graphql::Response` HTTP headers to the Rocket response.
+    fn http_headers(&mut self, resp: &async_graphql::Response) -> &mut Self;
+}
+
+impl<'r> HttpHeaders for ResponseBuilder<'r> {
+    fn http_headers(&mut self, resp: &async_graphql::Response) -> &mut ResponseBuilder<'r> {
+        for (name, value) in &resp.http_headers {
+            if let Ok(value) = value.to_str() {
+                self.header_adjoin(Header::new(name.as_str().to_string(), value.to_string()));
+            }
+        }
+        self
+    }
+}
@@ -8,7 +8,7 @@
 #![allow(clippy::needless_doctest_main)]
 #![forbid(unsafe_code)]
 
-use async_graphql::http::MultipartOptions;
+use async_graphql::http::{Encoding, MultipartOptions};
 use async_graphql::{resolver_utils::ObjectType, ParseRequestError, Schema, SubscriptionType};
 use async_trait::async_trait;
 use tide::{
@@ -21,7 +21,8 @@ use tide::{
 
 /// Create a new GraphQL endpoint with the schema.
 ///
-/// Default multipart options are used and batch operations are supported.
+/// Default multipart options are used and batch operations are supported. Responses are encoded
+/// as negotiated with the `Accept` header of the request.
 pub fn endpoint<Query, Mutation, Subscription>(
     schema: Schema<Query, Mutation, Subscription>,
 ) -> Endpoint<Query, Mutation, Subscription> {
@@ -63,7 +64,7 @@ impl<Query, Mutation, Subscription> Clone for Endpoint<Query, Mutation, Subscrip
     fn clone(&self) -> Self {
         Self {
             schema: self.schema.clone(),
-            opts: self.opts,
+            opts: self.opts.clone(),
             batch: self.batch,
         }
     }
@@ -79,17 +80,21 @@ where
     TideState: Clone + Send + Sync + 'static,
 {
     async fn call(&self, request: Request<TideState>) -> tide::Result {
-        respond(
+        let encoding = negotiate_encoding(&request);
+        let mut response = respond_encoded(
             self.schema
                 .execute_batch(if self.batch {
-                    receive_batch_request_opts(request, self.opts).await
+                    receive_batch_request_opts(request, self.opts.clone()).await
                 } else {
-                    receive_request_opts(request, self.opts)
+                    receive_request_opts(request, self.opts.clone())
                         .await
                         .map(Into::into)
                 }?)
                 .await,
-        )
+            encoding,
+        )?;
+        response.insert_header(headers::VARY, "accept");
+        Ok(response)
     }
 }
 
@@ -146,8 +151,28 @@ pub async fn receive_batch_request_opts<State: Clone + Send + Sync + 'static>(
     }
 }
 
+/// Choose the encoding of the response from the `Accept` header of a Tide request.
+pub fn negotiate_encoding<State: Clone + Send + Sync + 'static>(
+    request: &Request<State>,
+) -> Encoding {
+    Encoding::negotiate(
+        request
+            .header(headers::ACCEPT)
+            .and_then(|values| values.get(0))
+            .map(HeaderValue::as_str),
+    )
+}
+
 /// Convert a GraphQL response to a Tide response.
 pub fn respond(gql: impl Into<async_graphql::BatchResponse>) -> tide::Result {
+    respond_encoded(gql, Encoding::Json)
+}
+
+/// Convert a GraphQL response to a Tide response in an encoding.
+pub fn respond_encoded(
+    gql: impl Into<async_graphql::BatchResponse>,
+    encoding: Encoding,
+) -> tide::Result {
     let gql = gql.into();
 
     let mut response = Response::new(StatusCode::Ok);
@@ -156,6 +181,12 @@ pub fn respond(gql: impl Into<async_graphql::BatchResponse>) -> tide::Result {
             response.insert_header(headers::CACHE_CONTROL, cache_control);
         }
     }
-    response.set_body(Body::from_json(&gql)?);
+    for (name, value) in &gql.http_headers() {
+        if let Ok(value) = value.to_str() {
+            response.append_header(name.as_str(), value);
+        }
+    }
+    response.set_body(Body::from(encoding.encode(&gql)?));
+    response.insert_header(headers::CONTENT_TYPE, encoding.content_type());
     Ok(response)
 }
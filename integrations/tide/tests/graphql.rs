@@ -249,3 +249,93 @@ fn upload() -> Result<()> {
         Ok(())
     })
 }
+
+#[test]
+fn encodings() -> Result<()> {
+    use async_graphql::http::Encoding;
+
+    smol::run(async {
+        let listen_addr = test_utils::find_listen_addr().await;
+
+        let server = Task::<Result<()>>::spawn(async move {
+            struct QueryRoot;
+            #[Object]
+            impl QueryRoot {
+                async fn echo(&self, value: i64) -> i64 {
+                    value
+                }
+
+                async fn max(&self) -> u64 {
+                    u64::MAX
+                }
+            }
+
+            let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription).finish();
+
+            let mut app = tide::new();
+            app.at("/").post(async_graphql_tide::endpoint(schema));
+            app.listen(listen_addr).await?;
+
+            Ok(())
+        });
+
+        let client = Task::<Result<()>>::spawn(async move {
+            Timer::after(Duration::from_millis(300)).await;
+
+            for &encoding in &[Encoding::Cbor, Encoding::MessagePack] {
+                let body = encoding.encode(&json!({
+                    "query": format!("{{ echo(value: {}) max }}", i64::MIN),
+                }))?;
+                let resp = reqwest::Client::builder()
+                    .no_proxy()
+                    .build()
+                    .unwrap()
+                    .post(format!("http://{}", listen_addr).as_str())
+                    .body(body)
+                    .header(reqwest::header::CONTENT_TYPE, encoding.content_type())
+                    .header(reqwest::header::ACCEPT, encoding.content_type())
+                    .send()
+                    .await?;
+
+                assert_eq!(resp.status(), reqwest::StatusCode::OK);
+                assert_eq!(
+                    resp.headers()[reqwest::header::CONTENT_TYPE],
+                    encoding.content_type()
+                );
+                let data: serde_json::Value = encoding.decode(&resp.bytes().await?)?;
+                assert_eq!(
+                    data,
+                    json!({"data": {"echo": i64::MIN, "max": u64::MAX}}),
+                    "{:?}",
+                    encoding
+                );
+            }
+
+            // Without an `Accept` header the response is JSON.
+            let resp = reqwest::Client::builder()
+                .no_proxy()
+                .build()
+                .unwrap()
+                .post(format!("http://{}", listen_addr).as_str())
+                .body(Encoding::Cbor.encode(&json!({ "query": "{ max }" }))?)
+                .header(reqwest::header::CONTENT_TYPE, "application/cbor")
+                .send()
+                .await?;
+            assert_eq!(
+                resp.headers()[reqwest::header::CONTENT_TYPE],
+                "application/json"
+            );
+            assert_eq!(
+                resp.text().await?,
+                json!({"data": {"max": u64::MAX}}).to_string()
+            );
+
+            Ok(())
+        });
+
+        client.await?;
+        server.cancel().await;
+
+        Ok(())
+    })
+}
@@ -1,5 +1,6 @@
+use crate::request::encoded_reply;
 use crate::BadRequest;
-use async_graphql::http::MultipartOptions;
+use async_graphql::http::{Encoding, MultipartOptions};
 use async_graphql::{ObjectType, Schema, SubscriptionType};
 use futures::TryStreamExt;
 use std::io;
@@ -69,11 +70,21 @@ where
 }
 
 /// Reply for `async_graphql::BatchRequest`.
-pub struct BatchResponse(async_graphql::BatchResponse);
+///
+/// It is encoded as JSON unless another encoding is set with `encoding`.
+pub struct BatchResponse(async_graphql::BatchResponse, Option<Encoding>);
+
+impl BatchResponse {
+    /// Set the encoding of the response, usually from the `accept_encoding` filter.
+    #[must_use]
+    pub fn encoding(self, encoding: Encoding) -> Self {
+        BatchResponse(self.0, Some(encoding))
+    }
+}
 
 impl From<async_graphql::BatchResponse> for BatchResponse {
     fn from(resp: async_graphql::BatchResponse) -> Self {
-        BatchResponse(resp)
+        BatchResponse(resp, None)
     }
 }
 
@@ -87,15 +98,17 @@ fn add_cache_control(http_resp: &mut WarpResponse, resp: &async_graphql::BatchRe
     }
 }
 
+fn add_http_headers(http_resp: &mut WarpResponse, resp: &async_graphql::BatchResponse) {
+    for (name, value) in resp.http_headers() {
+        http_resp.headers_mut().append(name, value.clone());
+    }
+}
+
 impl Reply for BatchResponse {
     fn into_response(self) -> WarpResponse {
-        let mut resp = warp::reply::with_header(
-            warp::reply::json(&self.0),
-            "content-type",
-            "application/json",
-        )
-        .into_response();
+        let mut resp = encoded_reply(self.1.unwrap_or_default().encode(&self.0), self.1);
         add_cache_control(&mut resp, &self.0);
+        add_http_headers(&mut resp, &self.0);
         resp
     }
 }
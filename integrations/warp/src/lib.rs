@@ -11,5 +11,5 @@ mod subscription;
 
 pub use batch_request::{graphql_batch, graphql_batch_opts, BatchResponse};
 pub use error::BadRequest;
-pub use request::{graphql, graphql_opts, Response};
+pub use request::{accept_encoding, graphql, graphql_opts, Response};
 pub use subscription::{graphql_subscription, graphql_subscription_with_data};
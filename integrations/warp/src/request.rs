@@ -1,11 +1,11 @@
 use crate::BadRequest;
-use async_graphql::http::MultipartOptions;
+use async_graphql::http::{Encoding, EncodingError, MultipartOptions};
 use async_graphql::{ObjectType, Schema, SubscriptionType};
 use futures::TryStreamExt;
 use std::io;
 use std::io::ErrorKind;
 use std::sync::Arc;
-use warp::http::Method;
+use warp::http::{HeaderValue, Method, StatusCode};
 use warp::reply::Response as WarpResponse;
 use warp::{Buf, Filter, Rejection, Reply};
 
@@ -39,10 +39,14 @@ use warp::{Buf, Filter, Rejection, Reply};
 /// #[tokio::main]
 /// async fn main() {
 ///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
-///     let filter = async_graphql_warp::graphql(schema).
-///             and_then(|(schema, request): (MySchema, async_graphql::Request)| async move {
-///         Ok::<_, Infallible>(async_graphql_warp::Response::from(schema.execute(request).await))
-///     });
+///     let filter = async_graphql_warp::graphql(schema)
+///         .and(async_graphql_warp::accept_encoding())
+///         .and_then(|(schema, request): (MySchema, async_graphql::Request), encoding| async move {
+///             Ok::<_, Infallible>(
+///                 async_graphql_warp::Response::from(schema.execute(request).await)
+///                     .encoding(encoding),
+///             )
+///         });
 ///     warp::serve(filter).run(([0, 0, 0, 0], 8000)).await;
 /// }
 /// ```
@@ -114,15 +118,54 @@ where
         )
 }
 
+/// Filter that chooses the encoding of the response from the `Accept` header.
+///
+/// Pass it to `Response::encoding` or `BatchResponse::encoding`.
+pub fn accept_encoding() -> impl Filter<Extract = (Encoding,), Error = Rejection> + Clone {
+    warp::header::optional::<String>("accept")
+        .map(|accept: Option<String>| Encoding::negotiate(accept.as_deref()))
+}
+
 /// Reply for `async_graphql::Request`.
-pub struct Response(async_graphql::Response);
+///
+/// It is encoded as JSON unless another encoding is set with `encoding`.
+pub struct Response(async_graphql::Response, Option<Encoding>);
+
+impl Response {
+    /// Set the encoding of the response, usually from the `accept_encoding` filter.
+    #[must_use]
+    pub fn encoding(self, encoding: Encoding) -> Self {
+        Response(self.0, Some(encoding))
+    }
+}
 
 impl From<async_graphql::Response> for Response {
     fn from(resp: async_graphql::Response) -> Self {
-        Response(resp)
+        Response(resp, None)
     }
 }
 
+pub(crate) fn encoded_reply(
+    body: Result<Vec<u8>, EncodingError>,
+    encoding: Option<Encoding>,
+) -> WarpResponse {
+    let body = match body {
+        Ok(body) => body,
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+    let mut resp = warp::reply::with_header(
+        body,
+        "content-type",
+        encoding.unwrap_or_default().content_type(),
+    )
+    .into_response();
+    if encoding.is_some() {
+        resp.headers_mut()
+            .insert("vary", HeaderValue::from_static("accept"));
+    }
+    resp
+}
+
 fn add_cache_control(http_resp: &mut WarpResponse, resp: &async_graphql::Response) {
     if resp.is_ok() {
         if let Some(cache_control) = resp.cache_control.value() {
@@ -133,15 +176,17 @@ fn add_cache_control(http_resp: &mut WarpResponse, resp: &async_graphql::Respons
     }
 }
 
+fn add_http_headers(http_resp: &mut WarpResponse, resp: &async_graphql::Response) {
+    for (name, value) in &resp.http_headers {
+        http_resp.headers_mut().append(name, value.clone());
+    }
+}
+
 impl Reply for Response {
     fn into_response(self) -> WarpResponse {
-        let mut resp = warp::reply::with_header(
-            warp::reply::json(&self.0),
-            "content-type",
-            "application/json",
-        )
-        .into_response();
+        let mut resp = encoded_reply(self.1.unwrap_or_default().encode(&self.0), self.1);
         add_cache_control(&mut resp, &self.0);
+        add_http_headers(&mut resp, &self.0);
         resp
     }
 }
@@ -1,5 +1,5 @@
 use async_graphql::{resolver_utils::ObjectType, Data, FieldResult, Schema, SubscriptionType};
-use futures::{future, StreamExt};
+use futures::{future, SinkExt, StreamExt};
 use warp::filters::ws;
 use warp::{Filter, Rejection, Reply};
 
@@ -81,9 +81,9 @@ where
                                 .map(ws::Message::into_bytes),
                             initializer,
                         )
-                        .map(ws::Message::text)
-                        .map(Ok)
-                        .forward(ws_sender)
+                        .run(ws_sender.with(|message| {
+                            future::ok::<_, warp::Error>(ws::Message::text(message))
+                        }))
                         .await;
                     }
                 })
@@ -1,3 +1,4 @@
+use super::service;
 use super::*;
 
 /// Parse a GraphQL query document.
@@ -8,19 +9,30 @@ use super::*;
 pub fn parse_query<T: AsRef<str>>(input: T) -> Result<ExecutableDocument> {
     let mut pc = PositionCalculator::new(input.as_ref());
 
-    let items = parse_definition_items(
-        exactly_one(GraphQLParser::parse(
-            Rule::executable_document,
-            input.as_ref(),
-        )?),
-        &mut pc,
+    // Type system definitions are not executable, but they are reported by validation rather
+    // than as syntax errors. Only fall back to the wider rule so that syntax errors stay the same.
+    let pairs = match GraphQLParser::parse(Rule::executable_document, input.as_ref()) {
+        Ok(pairs) => pairs,
+        Err(err) => GraphQLParser::parse(Rule::document, input.as_ref()).map_err(|_| err)?,
+    };
+    let pair = exactly_one(pairs);
+
+    let mut duplicate_input_fields = Vec::new();
+    find_duplicate_input_fields(
+        pair.clone(),
+        &mut PositionCalculator::new(input.as_ref()),
+        &mut duplicate_input_fields,
     )?;
 
+    let items = parse_definition_items(pair, &mut pc)?;
+
     let mut operations = None;
     let mut fragments: HashMap<_, Positioned<FragmentDefinition>> = HashMap::new();
+    let mut type_system_definitions = Vec::new();
 
     for item in items {
         match item {
+            DefinitionItem::TypeSystem(definition) => type_system_definitions.push(definition),
             DefinitionItem::Operation(item) => {
                 if let Some(name) = item.node.name {
                     let operations = operations
@@ -89,25 +101,72 @@ pub fn parse_query<T: AsRef<str>>(input: T) -> Result<ExecutableDocument> {
     Ok(ExecutableDocument {
         operations: operations.ok_or(Error::MissingOperation)?,
         fragments,
+        type_system_definitions,
+        duplicate_input_fields,
     })
 }
 
+fn find_duplicate_input_fields(
+    pair: Pair<Rule>,
+    pc: &mut PositionCalculator,
+    duplicates: &mut Vec<DuplicateInputField>,
+) -> Result<()> {
+    match pair.as_rule() {
+        Rule::object | Rule::const_object => {
+            let mut fields = HashMap::new();
+
+            for pair in pair.into_inner() {
+                let mut pairs = pair.into_inner();
+
+                let name = parse_name(pairs.next().unwrap(), pc)?;
+                match fields.entry(name.node) {
+                    hash_map::Entry::Occupied(entry) => duplicates.push(DuplicateInputField {
+                        name: entry.key().clone(),
+                        first: *entry.get(),
+                        second: name.pos,
+                    }),
+                    hash_map::Entry::Vacant(entry) => {
+                        entry.insert(name.pos);
+                    }
+                }
+
+                find_duplicate_input_fields(pairs.next().unwrap(), pc, duplicates)?;
+            }
+        }
+        _ => {
+            for pair in pair.into_inner() {
+                find_duplicate_input_fields(pair, pc, duplicates)?;
+            }
+        }
+    }
+    Ok(())
+}
+
 fn parse_definition_items(
     pair: Pair<Rule>,
     pc: &mut PositionCalculator,
 ) -> Result<Vec<DefinitionItem>> {
-    debug_assert_eq!(pair.as_rule(), Rule::executable_document);
+    debug_assert!(matches!(
+        pair.as_rule(),
+        Rule::executable_document | Rule::document
+    ));
 
     Ok(pair
         .into_inner()
         .filter(|pair| pair.as_rule() != Rule::EOI)
-        .map(|pair| parse_definition_item(pair, pc))
+        .map(|pair| match pair.as_rule() {
+            Rule::type_system_definition => Ok(DefinitionItem::TypeSystem(
+                service::parse_type_system_definition(pair, pc)?,
+            )),
+            _ => parse_definition_item(pair, pc),
+        })
         .collect::<Result<_>>()?)
 }
 
 enum DefinitionItem {
     Operation(Positioned<OperationDefinitionItem>),
     Fragment(Positioned<FragmentDefinitionItem>),
+    TypeSystem(TypeSystemDefinition),
 }
 
 fn parse_definition_item(pair: Pair<Rule>, pc: &mut PositionCalculator) -> Result<DefinitionItem> {
@@ -424,6 +483,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_duplicate_input_fields() {
+        let doc = parse_query("{ field(arg: { a: 1, b: { c: 2, c: 3 }, a: 4 }) }").unwrap();
+        let duplicates = doc
+            .duplicate_input_fields
+            .iter()
+            .map(|field| (field.name.as_str(), field.first.column, field.second.column))
+            .collect::<Vec<_>>();
+        assert_eq!(duplicates, vec![("c", 27, 33), ("a", 16, 41)]);
+    }
+
+    #[test]
+    fn test_parse_type_system_definitions() {
+        let doc = parse_query("{ dog } type Cow { name: String } schema { query: Query }").unwrap();
+        assert!(matches!(
+            doc.type_system_definitions.as_slice(),
+            [
+                TypeSystemDefinition::Type(_),
+                TypeSystemDefinition::Schema(_)
+            ]
+        ));
+
+        let err = parse_query("{ dog } type Cow {").unwrap_err().to_string();
+        assert!(!err.contains("type_system_definition"));
+    }
+
+    #[test]
+    fn test_parse_non_ascii_positions() {
+        let doc = parse_query("{ a(x: \"é😀\") b\n# ü\nc }").unwrap();
+        let positions = doc
+            .operations
+            .iter()
+            .flat_map(|(_, operation)| operation.node.selection_set.node.items.iter())
+            .map(|selection| (selection.pos.line, selection.pos.column))
+            .collect::<Vec<_>>();
+        assert_eq!(positions, vec![(1, 3), (1, 14), (3, 1)]);
+    }
+
     #[test]
     fn test_parse_overflowing_int() {
         let query_ok = format!("mutation {{ add(big: {}) }} ", std::i32::MAX);
@@ -31,7 +31,7 @@ fn parse_service_document(
     })
 }
 
-fn parse_type_system_definition(
+pub(super) fn parse_type_system_definition(
     pair: Pair<Rule>,
     pc: &mut PositionCalculator,
 ) -> Result<TypeSystemDefinition> {
@@ -135,17 +135,19 @@ impl<'a> PositionCalculator<'a> {
     pub(crate) fn step<R: RuleType>(&mut self, pair: &Pair<R>) -> Pos {
         let pos = pair.as_span().start();
         debug_assert!(pos >= self.pos);
-        for _ in 0..pos - self.pos {
+        // `pos` is a byte offset, and columns count characters.
+        while self.pos < pos {
             match self.input.next() {
-                Some('\r') => {
-                    self.column = 1;
-                }
-                Some('\n') => {
-                    self.line += 1;
-                    self.column = 1;
-                }
-                Some(_) => {
-                    self.column += 1;
+                Some(c) => {
+                    self.pos += c.len_utf8();
+                    match c {
+                        '\r' => self.column = 1,
+                        '\n' => {
+                            self.line += 1;
+                            self.column = 1;
+                        }
+                        _ => self.column += 1,
+                    }
                 }
                 None => break,
             }
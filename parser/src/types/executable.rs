@@ -11,6 +11,23 @@ pub struct ExecutableDocument {
     pub operations: DocumentOperations,
     /// The fragments of the document.
     pub fragments: HashMap<Name, Positioned<FragmentDefinition>>,
+    /// The type system definitions of the document. These are not executable, and validation
+    /// rejects documents that contain them.
+    pub type_system_definitions: Vec<TypeSystemDefinition>,
+    /// The input object fields that are specified more than once. Only the last value of such a
+    /// field is kept in its object.
+    pub duplicate_input_fields: Vec<DuplicateInputField>,
+}
+
+/// An input object field that is specified more than once in the same object.
+#[derive(Debug, Clone)]
+pub struct DuplicateInputField {
+    /// The name of the field.
+    pub name: Name,
+    /// The position of the first occurrence of the field.
+    pub first: Pos,
+    /// The position of the repeated occurrence of the field.
+    pub second: Pos,
 }
 
 /// The operations of a GraphQL document.
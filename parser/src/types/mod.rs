@@ -6,16 +6,19 @@
 //!
 //! This follows the [June 2018 edition of the GraphQL spec](https://spec.graphql.org/June2018/).
 
-use crate::pos::Positioned;
+use crate::pos::{Pos, Positioned};
+use indexmap::IndexMap;
 use serde::de::{Deserializer, Error as _, Unexpected};
 use serde::ser::{Error as _, Serializer};
 use serde::{Deserialize, Serialize};
+use std::any::Any;
 use std::borrow::Borrow;
-use std::collections::{hash_map, BTreeMap, HashMap};
+use std::collections::{hash_map, HashMap};
 use std::convert::{TryFrom, TryInto};
 use std::fmt::{self, Display, Formatter, Write};
 use std::fs::File;
 use std::ops::Deref;
+use std::sync::Arc;
 
 pub use executable::*;
 pub use serde_json::Number;
@@ -111,7 +114,8 @@ impl Display for BaseType {
 /// A resolved GraphQL value, for example `1` or `"Hello World!"`.
 ///
 /// It can be serialized and deserialized. Enums will be converted to strings. Attempting to
-/// serialize `Upload` will fail, and `Enum` and `Upload` cannot be deserialized.
+/// serialize `Upload` will fail, and `Enum` and `Upload` cannot be deserialized. Objects keep the
+/// order of their keys.
 ///
 /// [Reference](https://spec.graphql.org/June2018/#Value).
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -131,7 +135,7 @@ pub enum ConstValue {
     /// A list of values.
     List(Vec<ConstValue>),
     /// An object. This is a map of keys to values.
-    Object(BTreeMap<Name, ConstValue>),
+    Object(IndexMap<Name, ConstValue>),
     /// An uploaded file.
     #[serde(serialize_with = "fail_serialize_upload", skip_deserializing)]
     Upload(UploadValue),
@@ -212,6 +216,27 @@ impl TryFrom<ConstValue> for serde_json::Value {
     }
 }
 
+/// Compares a value with its JSON representation, where enums are strings. Uploads are never
+/// equal to JSON.
+impl PartialEq<serde_json::Value> for ConstValue {
+    fn eq(&self, other: &serde_json::Value) -> bool {
+        match (self, other) {
+            (Self::Null, serde_json::Value::Null) => true,
+            (Self::Number(a), serde_json::Value::Number(b)) => a == b,
+            (Self::String(a), serde_json::Value::String(b)) => a == b,
+            (Self::Enum(a), serde_json::Value::String(b)) => a.as_str() == b,
+            (Self::Boolean(a), serde_json::Value::Bool(b)) => a == b,
+            (Self::List(a), serde_json::Value::Array(b)) => a == b,
+            (Self::Object(a), serde_json::Value::Object(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .all(|(key, value)| matches!(b.get(key.as_str()), Some(b) if value == b))
+            }
+            _ => false,
+        }
+    }
+}
+
 /// A GraphQL value, for example `1`, `$name` or `"Hello World!"`. This is
 /// [`ConstValue`](enum.ConstValue.html) with variables.
 ///
@@ -240,7 +265,7 @@ pub enum Value {
     /// A list of values.
     List(Vec<Value>),
     /// An object. This is a map of keys to values.
-    Object(BTreeMap<Name, Value>),
+    Object(IndexMap<Name, Value>),
     /// An uploaded file.
     #[serde(serialize_with = "fail_serialize_upload", skip_deserializing)]
     Upload(UploadValue),
@@ -397,7 +422,7 @@ pub struct UploadValue {
     /// The content type of the file.
     pub content_type: Option<String>,
     /// The file data.
-    pub content: File,
+    pub content: UploadContent,
 }
 
 impl UploadValue {
@@ -416,6 +441,59 @@ impl UploadValue {
     }
 }
 
+/// The data of a file upload, as stored when the request was received.
+pub enum UploadContent {
+    /// The data is stored in a file, such as a temporary file.
+    File(File),
+    /// The data is stored in memory.
+    Memory(Arc<[u8]>),
+    /// The data was sent somewhere else, such as an object storage service, which is described by
+    /// `value`.
+    Custom {
+        /// The size of the data in bytes.
+        size: u64,
+        /// A value describing where the data was sent.
+        value: Arc<dyn Any + Send + Sync>,
+    },
+}
+
+impl UploadContent {
+    /// Attempt to clone the upload content.
+    ///
+    /// # Errors
+    ///
+    /// Fails if cloning the inner `File` fails.
+    pub fn try_clone(&self) -> std::io::Result<Self> {
+        Ok(match self {
+            UploadContent::File(file) => UploadContent::File(file.try_clone()?),
+            UploadContent::Memory(data) => UploadContent::Memory(data.clone()),
+            UploadContent::Custom { size, value } => UploadContent::Custom {
+                size: *size,
+                value: value.clone(),
+            },
+        })
+    }
+
+    /// Returns the size of the data in bytes.
+    ///
+    /// # Errors
+    ///
+    /// Fails if reading the metadata of the inner `File` fails.
+    pub fn size(&self) -> std::io::Result<u64> {
+        match self {
+            UploadContent::File(file) => file.metadata().map(|meta| meta.len()),
+            UploadContent::Memory(data) => Ok(data.len() as u64),
+            UploadContent::Custom { size, .. } => Ok(*size),
+        }
+    }
+}
+
+impl From<File> for UploadContent {
+    fn from(file: File) -> Self {
+        UploadContent::File(file)
+    }
+}
+
 impl Clone for UploadValue {
     fn clone(&self) -> Self {
         self.try_clone().unwrap()
@@ -30,6 +30,24 @@ pub trait Type {
 
 /// Represents a GraphQL input value
 pub trait InputValueType: Type + Sized {
+    /// Type name when used as an input value.
+    ///
+    /// It is the same as `Type::type_name`, unless the type is also an output type and uses another
+    /// name for its input side, e.g. `#[graphql(input_name = "...")]`.
+    fn input_type_name() -> Cow<'static, str> {
+        Self::type_name()
+    }
+
+    /// Qualified typename when used as an input value.
+    fn qualified_input_type_name() -> String {
+        Self::qualified_type_name()
+    }
+
+    /// Create the input type information in the registry and return the qualified input typename.
+    fn create_input_type_info(registry: &mut registry::Registry) -> String {
+        Self::create_type_info(registry)
+    }
+
     /// Parse from `Value`. None represents undefined.
     fn parse(value: Option<Value>) -> InputValueResult<Self>;
 
@@ -40,12 +58,12 @@ pub trait InputValueType: Type + Sized {
 /// Represents a GraphQL output value
 #[async_trait::async_trait]
 pub trait OutputValueType: Type {
-    /// Resolve an output value to `serde_json::Value`.
+    /// Resolve an output value to `async_graphql::Value`.
     async fn resolve(
         &self,
         ctx: &ContextSelectionSet<'_>,
         field: &Positioned<Field>,
-    ) -> Result<serde_json::Value>;
+    ) -> Result<Value>;
 }
 
 impl<T: Type + Send + Sync> Type for &T {
@@ -65,7 +83,7 @@ impl<T: OutputValueType + Send + Sync> OutputValueType for &T {
         &self,
         ctx: &ContextSelectionSet<'_>,
         field: &Positioned<Field>,
-    ) -> Result<serde_json::Value> {
+    ) -> Result<Value> {
         T::resolve(*self, ctx, field).await
     }
 }
@@ -90,7 +108,7 @@ impl<T: OutputValueType + Sync> OutputValueType for FieldResult<T> {
         &self,
         ctx: &ContextSelectionSet<'_>,
         field: &Positioned<Field>,
-    ) -> crate::Result<serde_json::Value> {
+    ) -> crate::Result<Value> {
         match self {
             Ok(value) => Ok(OutputValueType::resolve(value, ctx, field).await?),
             Err(err) => Err(err
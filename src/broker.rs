@@ -0,0 +1,289 @@
+//! Publish/subscribe brokers for subscriptions
+//!
+//! A broker delivers the messages published by mutations, or by any other part of an
+//! application, to the subscription resolvers that subscribed to the same topic.
+//!
+//! The [`Broker`](trait.Broker.html) trait carries serialized messages, so that a backend can be
+//! stored as an `Arc<dyn Broker>` and replaced by one backed by an external service. The typed
+//! `publish`, `subscribe` and `subscribe_filter` methods are provided by
+//! [`BrokerExt`](trait.BrokerExt.html), which is implemented for all brokers.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_graphql::*;
+//! use async_graphql::broker::{Broker, BrokerExt, MemoryBroker};
+//! use futures::{Stream, StreamExt};
+//! use serde::{Serialize, Deserialize};
+//!
+//! #[derive(SimpleObject, Clone, Serialize, Deserialize)]
+//! struct BookChanged {
+//!     id: i32,
+//!     owner: String,
+//! }
+//!
+//! struct QueryRoot;
+//!
+//! #[Object]
+//! impl QueryRoot {
+//!     async fn value(&self) -> i32 { 10 }
+//! }
+//!
+//! struct MutationRoot;
+//!
+//! #[Object]
+//! impl MutationRoot {
+//!     async fn change_book(&self, ctx: &Context<'_>, id: i32, owner: String) -> FieldResult<bool> {
+//!         ctx.data::<MemoryBroker>()?.publish("books", BookChanged { id, owner }).await?;
+//!         Ok(true)
+//!     }
+//! }
+//!
+//! struct SubscriptionRoot;
+//!
+//! #[Subscription]
+//! impl SubscriptionRoot {
+//!     async fn my_books<'a>(&self, ctx: &'a Context<'a>) -> impl Stream<Item = FieldResult<BookChanged>> + 'a {
+//!         // Only receive the changes of the books owned by the current user.
+//!         ctx.data_unchecked::<MemoryBroker>().subscribe_filter(ctx, "books", |ctx, event: &BookChanged| {
+//!             event.owner == *ctx.data_unchecked::<String>()
+//!         })
+//!     }
+//! }
+//!
+//! #[async_std::main]
+//! async fn main() {
+//!     let broker = MemoryBroker::default();
+//!     let schema = Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
+//!         .data(broker.clone())
+//!         .finish();
+//!
+//!     let mut stream = schema
+//!         .execute_stream(Request::new("subscription { myBooks { id } }").data("alice".to_string()))
+//!         .boxed();
+//!     // Start the subscription before publishing.
+//!     assert!(futures::poll!(stream.next()).is_pending());
+//!     assert_eq!(broker.subscriber_count("books"), 1);
+//!
+//!     schema.execute(r#"mutation { changeBook(id: 1, owner: "bob") }"#).await;
+//!     schema.execute(r#"mutation { changeBook(id: 2, owner: "alice") }"#).await;
+//!     assert_eq!(stream.next().await.unwrap().data, serde_json::json!({ "myBooks": { "id": 2 } }));
+//! }
+//! ```
+
+use crate::{Context, FieldResult};
+use futures::channel::mpsc;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// A message that can be published through a broker.
+///
+/// Messages are serialized to JSON, so that brokers backed by an external service can be used
+/// interchangeably with [`MemoryBroker`](struct.MemoryBroker.html).
+pub trait BrokerMessage: Serialize + DeserializeOwned + Send + Sync + 'static {}
+
+impl<T: Serialize + DeserializeOwned + Send + Sync + 'static> BrokerMessage for T {}
+
+/// Broker error
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum BrokerError {
+    /// The subscriber did not keep up with the publishers, and this number of messages was
+    /// dropped.
+    #[error("The subscriber lagged behind and missed {0} messages")]
+    Lagged(u64),
+
+    /// A message cannot be serialized, or cannot be deserialized to the type of the subscriber.
+    #[error("Invalid broker message: {0}")]
+    InvalidMessage(String),
+
+    /// An error of the underlying service.
+    #[error("Broker error: {0}")]
+    Other(String),
+}
+
+/// A stream of the messages of a topic.
+pub type BrokerStream<T> = BoxStream<'static, Result<T, BrokerError>>;
+
+/// A publish/subscribe broker
+///
+/// Messages are published to a topic as bytes, and are delivered to all the subscribers of the
+/// topic. This trait is object safe, use the typed methods of [`BrokerExt`](trait.BrokerExt.html)
+/// to publish and subscribe.
+#[async_trait::async_trait]
+pub trait Broker: Send + Sync + 'static {
+    /// Publish a serialized message to all the subscribers of a topic.
+    async fn publish_bytes(&self, topic: &str, message: Vec<u8>) -> Result<(), BrokerError>;
+
+    /// Subscribe to the serialized messages published to a topic.
+    fn subscribe_bytes(&self, topic: &str) -> BrokerStream<Vec<u8>>;
+}
+
+/// Typed methods of a [`Broker`](trait.Broker.html)
+///
+/// The messages of a topic are expected to have the same type. A message that cannot be
+/// deserialized to the type of a subscriber is received as a `BrokerError::InvalidMessage` error.
+#[async_trait::async_trait]
+pub trait BrokerExt: Broker {
+    /// Publish a message to all the subscribers of a topic.
+    async fn publish<T: BrokerMessage>(&self, topic: &str, message: T) -> Result<(), BrokerError> {
+        let message = serde_json::to_vec(&message)
+            .map_err(|err| BrokerError::InvalidMessage(err.to_string()))?;
+        self.publish_bytes(topic, message).await
+    }
+
+    /// Subscribe to the messages of type `T` published to a topic.
+    fn subscribe<T: BrokerMessage>(&self, topic: &str) -> BrokerStream<T> {
+        self.subscribe_bytes(topic)
+            .map(|res| {
+                res.and_then(|message| {
+                    serde_json::from_slice(&message)
+                        .map_err(|err| BrokerError::InvalidMessage(err.to_string()))
+                })
+            })
+            .boxed()
+    }
+
+    /// Subscribe to the messages of type `T` published to a topic, keeping only the messages
+    /// accepted by `filter`.
+    ///
+    /// This is intended to be returned from a subscription resolver. The filter has access to the
+    /// `Context` of the resolver, for example to check the messages against the current user.
+    /// Broker errors, such as a lagging subscriber, are turned into field errors, which end the
+    /// subscription.
+    fn subscribe_filter<'a, T, F>(
+        &self,
+        ctx: &'a Context<'a>,
+        topic: &str,
+        mut filter: F,
+    ) -> BoxStream<'a, FieldResult<T>>
+    where
+        T: BrokerMessage,
+        F: FnMut(&Context<'_>, &T) -> bool + Send + 'a,
+    {
+        self.subscribe::<T>(topic)
+            .filter_map(move |res| {
+                futures::future::ready(match res {
+                    Ok(message) if filter(ctx, &message) => Some(Ok(message)),
+                    Ok(_) => None,
+                    Err(err) => Some(Err(err.into())),
+                })
+            })
+            .boxed()
+    }
+}
+
+impl<B: Broker + ?Sized> BrokerExt for B {}
+
+struct Subscriber {
+    sender: mpsc::Sender<Vec<u8>>,
+    lagged: Arc<AtomicU64>,
+}
+
+/// An in-process broker
+///
+/// Each subscriber has a bounded buffer of messages. When it is full, new messages are dropped
+/// for this subscriber, which receives a `BrokerError::Lagged` error before its next message.
+///
+/// Cloning a `MemoryBroker` returns a handle to the same broker.
+#[derive(Clone)]
+pub struct MemoryBroker {
+    capacity: usize,
+    subscribers: Arc<spin::Mutex<HashMap<String, Vec<Subscriber>>>>,
+}
+
+impl Default for MemoryBroker {
+    fn default() -> Self {
+        Self::new(64)
+    }
+}
+
+impl MemoryBroker {
+    /// Create an in-process broker, buffering at most `capacity` messages per subscriber.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            subscribers: Default::default(),
+        }
+    }
+
+    /// Returns the number of subscribers to a topic.
+    pub fn subscriber_count(&self, topic: &str) -> usize {
+        self.subscribers
+            .lock()
+            .get(topic)
+            .map(|subscribers| {
+                subscribers
+                    .iter()
+                    .filter(|subscriber| !subscriber.sender.is_closed())
+                    .count()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait::async_trait]
+impl Broker for MemoryBroker {
+    async fn publish_bytes(&self, topic: &str, message: Vec<u8>) -> Result<(), BrokerError> {
+        let mut subscribers = self.subscribers.lock();
+        let is_empty = match subscribers.get_mut(topic) {
+            Some(topic_subscribers) => {
+                for subscriber in topic_subscribers.iter_mut() {
+                    if let Err(err) = subscriber.sender.try_send(message.clone()) {
+                        if err.is_full() {
+                            subscriber.lagged.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                }
+                topic_subscribers.retain(|subscriber| !subscriber.sender.is_closed());
+                topic_subscribers.is_empty()
+            }
+            None => false,
+        };
+        if is_empty {
+            subscribers.remove(topic);
+        }
+        Ok(())
+    }
+
+    fn subscribe_bytes(&self, topic: &str) -> BrokerStream<Vec<u8>> {
+        // The channel has one more slot for its sender.
+        let (sender, mut receiver) = mpsc::channel(self.capacity.saturating_sub(1));
+        let lagged = Arc::new(AtomicU64::new(0));
+        self.subscribers
+            .lock()
+            .entry(topic.to_string())
+            .or_default()
+            .push(Subscriber {
+                sender,
+                lagged: lagged.clone(),
+            });
+
+        let stream = async_stream::stream! {
+            while let Some(message) = receiver.next().await {
+                let missed = lagged.swap(0, Ordering::Relaxed);
+                if missed > 0 {
+                    yield Err(BrokerError::Lagged(missed));
+                }
+                yield Ok(message);
+            }
+        };
+        stream.boxed()
+    }
+}
+
+#[async_trait::async_trait]
+impl<B: Broker + ?Sized> Broker for Arc<B> {
+    async fn publish_bytes(&self, topic: &str, message: Vec<u8>) -> Result<(), BrokerError> {
+        B::publish_bytes(self, topic, message).await
+    }
+
+    fn subscribe_bytes(&self, topic: &str) -> BrokerStream<Vec<u8>> {
+        B::subscribe_bytes(self, topic)
+    }
+}
@@ -1,26 +1,33 @@
 use crate::base::Type;
 use crate::extensions::Extensions;
+use crate::http::{HeaderError, HeaderMap, HeaderName, HeaderValue};
+use crate::node::GlobalId;
 use crate::parser::types::{
     Directive, Field, FragmentDefinition, Name, OperationDefinition, SelectionSet,
     Value as InputValue,
 };
+use crate::registry::MetaInputValue;
 use crate::schema::SchemaEnv;
-use crate::{FieldResult, InputValueType, Lookahead, Pos, Positioned, QueryError, Result, Value};
+use crate::{
+    FieldError, FieldResult, InputValueType, Lookahead, Pos, Positioned, QueryError, Result, Value,
+};
 use fnv::FnvHashMap;
+use indexmap::IndexMap;
 use serde::ser::{SerializeSeq, Serializer};
 use serde::{Deserialize, Serialize};
 use std::any::{Any, TypeId};
-use std::collections::{BTreeMap, HashMap};
-use std::convert::TryFrom;
+use std::collections::HashMap;
+use std::convert::{TryFrom, TryInto};
 use std::fmt::{self, Debug, Display, Formatter};
 use std::ops::Deref;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Variables of a query.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(transparent)]
-pub struct Variables(pub BTreeMap<Name, Value>);
+pub struct Variables(pub IndexMap<Name, Value>);
 
 impl Display for Variables {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -240,6 +247,13 @@ pub struct ContextBase<'a, T> {
     pub path_node: Option<QueryPathNode<'a>>,
     pub(crate) resolve_id: ResolveId,
     pub(crate) inc_resolve_id: &'a AtomicUsize,
+    pub(crate) deadline: Option<Instant>,
+    /// Whether the value of the current field, or of one of its parents, is marked as secret.
+    #[doc(hidden)]
+    pub secret: bool,
+    /// The name in the schema of the type whose field is resolved.
+    #[doc(hidden)]
+    pub parent_type: Option<&'a str>,
     #[doc(hidden)]
     pub item: T,
     #[doc(hidden)]
@@ -255,6 +269,73 @@ pub struct QueryEnvInner {
     pub operation: Positioned<OperationDefinition>,
     pub fragments: HashMap<Name, Positioned<FragmentDefinition>>,
     pub ctx_data: Arc<Data>,
+    pub shared_data: Option<Arc<Data>>,
+    pub http_headers: spin::Mutex<HeaderMap>,
+}
+
+impl QueryEnvInner {
+    pub(crate) fn var_value(&self, name: &str, pos: Pos) -> Result<Value> {
+        self.operation
+            .node
+            .variable_definitions
+            .iter()
+            .find(|def| def.node.name.node == name)
+            .and_then(|def| {
+                self.variables
+                    .0
+                    .get(&def.node.name.node)
+                    .or_else(|| def.node.default_value())
+            })
+            .cloned()
+            .ok_or_else(|| {
+                QueryError::VarNotDefined {
+                    var_name: name.to_owned(),
+                }
+                .into_error(pos)
+            })
+    }
+
+    pub(crate) fn resolve_input_value(&self, value: Positioned<InputValue>) -> Result<Value> {
+        let pos = value.pos;
+        value
+            .node
+            .into_const_with(|name| self.var_value(&name, pos))
+    }
+
+    pub(crate) fn is_skip(&self, directives: &[Positioned<Directive>]) -> Result<bool> {
+        for directive in directives {
+            let include = match &*directive.node.name.node {
+                "skip" => false,
+                "include" => true,
+                _ => continue,
+            };
+
+            let condition_input = directive
+                .node
+                .get_argument("if")
+                .ok_or_else(|| {
+                    QueryError::RequiredDirectiveArgs {
+                        directive: if include { "@skip" } else { "@include" },
+                        arg_name: "if",
+                        arg_type: "Boolean!",
+                    }
+                    .into_error(directive.pos)
+                })?
+                .clone();
+
+            let pos = condition_input.pos;
+            let condition_input = self.resolve_input_value(condition_input)?;
+
+            if include
+                != <bool as InputValueType>::parse(Some(condition_input))
+                    .map_err(|e| e.into_error(pos, bool::qualified_type_name()))?
+            {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
 }
 
 #[doc(hidden)]
@@ -277,6 +358,7 @@ impl QueryEnv {
         operation: Positioned<OperationDefinition>,
         fragments: HashMap<Name, Positioned<FragmentDefinition>>,
         ctx_data: Arc<Data>,
+        shared_data: Option<Arc<Data>>,
     ) -> QueryEnv {
         QueryEnv(Arc::new(QueryEnvInner {
             extensions,
@@ -284,6 +366,8 @@ impl QueryEnv {
             operation,
             fragments,
             ctx_data,
+            shared_data,
+            http_headers: Default::default(),
         }))
     }
 
@@ -300,6 +384,9 @@ impl QueryEnv {
             path_node,
             resolve_id,
             inc_resolve_id,
+            deadline: None,
+            secret: false,
+            parent_type: None,
             item,
             schema_env,
             query_env: self,
@@ -333,6 +420,9 @@ impl<'a, T> ContextBase<'a, T> {
             item: field,
             resolve_id: self.get_child_resolve_id(),
             inc_resolve_id: self.inc_resolve_id,
+            deadline: self.deadline,
+            secret: self.secret,
+            parent_type: None,
             schema_env: self.schema_env,
             query_env: self.query_env,
         }
@@ -348,6 +438,9 @@ impl<'a, T> ContextBase<'a, T> {
             item: selection_set,
             resolve_id: self.resolve_id,
             inc_resolve_id: &self.inc_resolve_id,
+            deadline: self.deadline,
+            secret: self.secret,
+            parent_type: self.parent_type,
             schema_env: self.schema_env,
             query_env: self.query_env,
         }
@@ -381,38 +474,102 @@ impl<'a, T> ContextBase<'a, T> {
             .ctx_data
             .0
             .get(&TypeId::of::<D>())
+            .or_else(|| {
+                self.query_env
+                    .shared_data
+                    .as_ref()
+                    .and_then(|data| data.0.get(&TypeId::of::<D>()))
+            })
             .or_else(|| self.schema_env.data.0.get(&TypeId::of::<D>()))
             .and_then(|d| d.downcast_ref::<D>())
     }
 
-    fn var_value(&self, name: &str, pos: Pos) -> Result<Value> {
-        self.query_env
-            .operation
-            .node
-            .variable_definitions
-            .iter()
-            .find(|def| def.node.name.node == name)
-            .and_then(|def| {
-                self.query_env
-                    .variables
-                    .0
-                    .get(&def.node.name.node)
-                    .or_else(|| def.node.default_value())
-            })
-            .cloned()
-            .ok_or_else(|| {
-                QueryError::VarNotDefined {
-                    var_name: name.to_owned(),
-                }
-                .into_error(pos)
-            })
+    /// Decodes a global id with the `GlobalIdCodec` of the schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `FieldError` if the id is not a valid global id.
+    pub fn decode_global_id(&self, id: &str) -> FieldResult<GlobalId> {
+        self.schema_env
+            .global_id_codec
+            .decode(id)
+            .ok_or_else(|| FieldError::new(format!("Invalid global id \"{}\".", id)).user_facing())
+    }
+
+    /// Gets the instant at which this resolver will be cancelled.
+    ///
+    /// Returns `None` if neither the request, the schema nor any field on the path has a timeout.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Gets the time remaining before this resolver is cancelled, or `None` if there is no
+    /// deadline.
+    ///
+    /// Pass this on to downstream calls so that they give up when the resolver does.
+    pub fn remaining_time(&self) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Sets a header of the HTTP response, replacing any values it already has.
+    ///
+    /// Returns the previous value of the header, or `None` if it had no value. If it had several
+    /// values, the first one is returned. Fails if the name or the value is not valid, in which
+    /// case the header is left unchanged.
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    ///
+    /// struct MutationRoot;
+    ///
+    /// #[Object]
+    /// impl MutationRoot {
+    ///     async fn login(&self, ctx: &Context<'_>, username: String) -> FieldResult<bool> {
+    ///         ctx.insert_http_header("Set-Cookie", format!("session={}; HttpOnly", username))?;
+    ///         Ok(true)
+    ///     }
+    /// }
+    /// ```
+    pub fn insert_http_header<N, V>(
+        &self,
+        name: N,
+        value: V,
+    ) -> std::result::Result<Option<HeaderValue>, HeaderError>
+    where
+        N: TryInto<HeaderName>,
+        N::Error: Into<HeaderError>,
+        V: TryInto<HeaderValue>,
+        V::Error: Into<HeaderError>,
+    {
+        let name = name.try_into().map_err(Into::into)?;
+        let value = value.try_into().map_err(Into::into)?;
+        Ok(self.query_env.http_headers.lock().insert(name, value))
+    }
+
+    /// Adds a value to a header of the HTTP response, keeping the values it already has.
+    ///
+    /// Use this for headers that can appear multiple times, such as `Set-Cookie`. Fails if the
+    /// name or the value is not valid, in which case the header is left unchanged.
+    pub fn append_http_header<N, V>(
+        &self,
+        name: N,
+        value: V,
+    ) -> std::result::Result<(), HeaderError>
+    where
+        N: TryInto<HeaderName>,
+        N::Error: Into<HeaderError>,
+        V: TryInto<HeaderValue>,
+        V::Error: Into<HeaderError>,
+    {
+        let name = name.try_into().map_err(Into::into)?;
+        let value = value.try_into().map_err(Into::into)?;
+        self.query_env.http_headers.lock().append(name, value);
+        Ok(())
     }
 
     fn resolve_input_value(&self, value: Positioned<InputValue>) -> Result<Value> {
-        let pos = value.pos;
-        value
-            .node
-            .into_const_with(|name| self.var_value(&name, pos))
+        self.query_env.resolve_input_value(value)
     }
 
     #[doc(hidden)]
@@ -424,38 +581,7 @@ impl<'a, T> ContextBase<'a, T> {
 
     #[doc(hidden)]
     pub fn is_skip(&self, directives: &[Positioned<Directive>]) -> Result<bool> {
-        for directive in directives {
-            let include = match &*directive.node.name.node {
-                "skip" => false,
-                "include" => true,
-                _ => continue,
-            };
-
-            let condition_input = directive
-                .node
-                .get_argument("if")
-                .ok_or_else(|| {
-                    QueryError::RequiredDirectiveArgs {
-                        directive: if include { "@skip" } else { "@include" },
-                        arg_name: "if",
-                        arg_type: "Boolean!",
-                    }
-                    .into_error(directive.pos)
-                })?
-                .clone();
-
-            let pos = condition_input.pos;
-            let condition_input = self.resolve_input_value(condition_input)?;
-
-            if include
-                != <bool as InputValueType>::parse(Some(condition_input))
-                    .map_err(|e| e.into_error(pos, bool::qualified_type_name()))?
-            {
-                return Ok(true);
-            }
-        }
-
-        Ok(false)
+        self.query_env.is_skip(directives)
     }
 }
 
@@ -470,6 +596,9 @@ impl<'a> ContextBase<'a, &'a Positioned<SelectionSet>> {
             item: self.item,
             resolve_id: self.get_child_resolve_id(),
             inc_resolve_id: self.inc_resolve_id,
+            deadline: self.deadline,
+            secret: self.secret,
+            parent_type: self.parent_type,
             schema_env: self.schema_env,
             query_env: self.query_env,
         }
@@ -477,12 +606,42 @@ impl<'a> ContextBase<'a, &'a Positioned<SelectionSet>> {
 }
 
 impl<'a> ContextBase<'a, &'a Positioned<Field>> {
+    /// Returns the name of the field in the code, which the resolvers generated by the derive
+    /// macros match. It differs from the name in the query when the schema renames the field.
+    #[doc(hidden)]
+    pub fn field_code_name(&self) -> &'a str {
+        let name = self.item.node.name.node.as_str();
+        match self.parent_type {
+            Some(parent_type) => self.schema_env.registry.code_name(parent_type, name),
+            None => name,
+        }
+    }
+
+    /// Returns the definition of an argument of the field from its name in the code, if the
+    /// schema renames some names. Its values must then be converted to the names in the code.
+    fn renamed_arg(&self, name: &str) -> Option<&'a MetaInputValue> {
+        let registry = &self.schema_env.registry;
+        if registry.renames.is_empty() {
+            return None;
+        }
+        let parent_type = self.parent_type?;
+        let field = registry
+            .types
+            .get(parent_type)?
+            .field_by_name(&self.item.node.name.node)?;
+        field
+            .args
+            .get(registry.schema_arg_name(parent_type, &field.name, name))
+    }
+
     #[doc(hidden)]
     pub fn param_value<T: InputValueType>(
         &self,
         name: &str,
         default: Option<fn() -> T>,
     ) -> Result<T> {
+        let meta_arg = self.renamed_arg(name);
+        let name = meta_arg.map_or(name, |arg| arg.name);
         let value = self.item.node.get_argument(name).cloned();
         if value.is_none() {
             if let Some(default) = default {
@@ -490,10 +649,17 @@ impl<'a> ContextBase<'a, &'a Positioned<Field>> {
             }
         }
         let (pos, value) = match value {
-            Some(value) => (value.pos, Some(self.resolve_input_value(value)?)),
+            Some(value) => {
+                let pos = value.pos;
+                let mut value = self.resolve_input_value(value)?;
+                if let Some(meta_arg) = meta_arg {
+                    value = self.schema_env.registry.value_to_code(&meta_arg.ty, value);
+                }
+                (pos, Some(value))
+            }
             None => (Pos::default(), None),
         };
-        InputValueType::parse(value).map_err(|e| e.into_error(pos, T::qualified_type_name()))
+        InputValueType::parse(value).map_err(|e| e.into_error(pos, T::qualified_input_type_name()))
     }
 
     /// Creates a uniform interface to inspect the forthcoming selections.
@@ -528,11 +694,19 @@ impl<'a> ContextBase<'a, &'a Positioned<Field>> {
     ///         } else {
     ///             // This query doesn't have `a`
     ///         }
+    ///
+    ///         // Every selected field, e.g. to build the columns of a SQL query
+    ///         let columns: Vec<&str> = ctx
+    ///             .look_ahead()
+    ///             .selection_fields()
+    ///             .iter()
+    ///             .map(|field| field.name())
+    ///             .collect();
     ///         unimplemented!()
     ///     }
     /// }
     /// ```
     pub fn look_ahead(&self) -> Lookahead {
-        Lookahead::new(&self.query_env.fragments, &self.item.node)
+        Lookahead::new(&self.schema_env.registry, self.query_env, &self.item.node)
     }
 }
@@ -1,6 +1,9 @@
 use crate::{Pos, QueryPathNode, Value};
+use std::any::{Any, TypeId};
+use std::borrow::Cow;
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 use std::ops::Deref;
+use std::sync::Arc;
 use thiserror::Error;
 
 /// An error in the format of an input value.
@@ -43,20 +46,135 @@ impl InputValueError {
 /// An alias for `Result<T, InputValueError>`.
 pub type InputValueResult<T> = std::result::Result<T, InputValueError>;
 
+/// The original value that a [`FieldError`](struct.FieldError.html) was converted from.
+///
+/// It is never sent to clients, but extensions can inspect it, for example with
+/// [`downcast_ref`](#method.downcast_ref). A source created with
+/// [`from_error`](#method.from_error) also gives access to the `source()` chain of the error. Two
+/// sources are considered equal if they were created from values of the same type with equal
+/// messages.
+#[derive(Clone)]
+pub struct ErrorSource {
+    value: Arc<dyn Any + Send + Sync>,
+    error: Option<Arc<dyn std::error::Error + Send + Sync>>,
+    message: String,
+}
+
+impl ErrorSource {
+    /// Create an error source from a value, such as an error.
+    pub fn new<T: Display + Send + Sync + 'static>(value: T) -> Self {
+        Self {
+            message: value.to_string(),
+            value: Arc::new(value),
+            error: None,
+        }
+    }
+
+    /// Create an error source from an error, keeping its `source()` chain.
+    pub fn from_error<E: std::error::Error + Send + Sync + 'static>(err: E) -> Self {
+        let err = Arc::new(err);
+        Self {
+            message: err.to_string(),
+            value: err.clone(),
+            error: Some(err),
+        }
+    }
+
+    /// Returns a reference to the original value if it is of type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.value.downcast_ref()
+    }
+
+    /// Returns the original error if the source was created with
+    /// [`from_error`](#method.from_error).
+    pub fn as_error(&self) -> Option<&(dyn std::error::Error + Send + Sync + 'static)> {
+        self.error.as_deref()
+    }
+}
+
+impl Debug for ErrorSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.error {
+            Some(err) => Debug::fmt(err, f),
+            None => f.debug_tuple("ErrorSource").field(&self.message).finish(),
+        }
+    }
+}
+
+impl Display for ErrorSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ErrorSource {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.as_ref().and_then(|err| err.source())
+    }
+}
+
+impl PartialEq for ErrorSource {
+    fn eq(&self, other: &Self) -> bool {
+        (*self.value).type_id() == (*other.value).type_id() && self.message == other.message
+    }
+}
+
 /// An error in a field resolver.
-#[derive(Clone, Debug)]
-pub struct FieldError(pub String, pub Option<serde_json::Value>);
+///
+/// When the schema masks errors (see `SchemaBuilder::mask_errors`), only errors that are
+/// explicitly marked with [`user_facing`](#method.user_facing) are shown to clients as they are.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldError {
+    /// The error message.
+    pub message: String,
+
+    /// Extensions to the error provided through the [`ErrorExtensions`](trait.ErrorExtensions)
+    /// or [`ResultExt`](trait.ResultExt) traits.
+    pub extensions: Option<serde_json::Value>,
+
+    /// Whether the message and extensions of the error are meant to be shown to clients.
+    pub user_facing: bool,
+
+    /// The original value, if the error was converted from one.
+    pub source: Option<ErrorSource>,
+}
 
 impl FieldError {
+    /// Create an error with a message.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            extensions: None,
+            user_facing: false,
+            source: None,
+        }
+    }
+
+    /// Create an error from an error, keeping it as the source so that extensions can walk its
+    /// `source()` chain.
+    pub fn from_error<E: std::error::Error + Send + Sync + 'static>(err: E) -> Self {
+        let source = ErrorSource::from_error(err);
+        Self {
+            source: Some(source.clone()),
+            ..FieldError::new(source.to_string())
+        }
+    }
+
+    /// Mark the error as meant to be shown to clients, so that it is not masked.
+    #[must_use]
+    pub fn user_facing(self) -> Self {
+        Self {
+            user_facing: true,
+            ..self
+        }
+    }
+
     #[doc(hidden)]
     pub fn into_error(self, pos: Pos) -> Error {
         Error::Query {
             pos,
             path: None,
-            err: QueryError::FieldError {
-                err: self.0,
-                extended_error: self.1,
-            },
+            err: self.into(),
         }
     }
 
@@ -65,10 +183,7 @@ impl FieldError {
         Error::Query {
             pos,
             path: path.and_then(|path| serde_json::to_value(path).ok()),
-            err: QueryError::FieldError {
-                err: self.0,
-                extended_error: self.1,
-            },
+            err: self.into(),
         }
     }
 }
@@ -76,9 +191,24 @@ impl FieldError {
 /// An alias for `Result<T, FieldError>`.
 pub type FieldResult<T> = std::result::Result<T, FieldError>;
 
-impl<E: Display> From<E> for FieldError {
-    fn from(err: E) -> Self {
-        FieldError(format!("{}", err), None)
+/// Plain messages are not errors of their own, so they are not kept as a source.
+fn is_message<T: 'static>() -> bool {
+    let type_id = TypeId::of::<T>();
+    type_id == TypeId::of::<String>()
+        || type_id == TypeId::of::<&'static str>()
+        || type_id == TypeId::of::<Cow<'static, str>>()
+}
+
+impl<T: Display + Send + Sync + 'static> From<T> for FieldError {
+    fn from(value: T) -> Self {
+        if is_message::<T>() {
+            return FieldError::new(value.to_string());
+        }
+        let source = ErrorSource::new(value);
+        Self {
+            source: Some(source.clone()),
+            ..FieldError::new(source.to_string())
+        }
     }
 }
 
@@ -92,21 +222,23 @@ pub trait ErrorExtensions: Sized {
     where
         C: FnOnce(&Self) -> serde_json::Value,
     {
-        let name = self.extend().0;
+        let mut err = self.extend();
+        let mut cb_res = cb(&self);
 
-        if let Some(mut base) = self.extend().1 {
-            let mut cb_res = cb(&self);
+        if let Some(mut base) = err.extensions.take() {
             if let Some(base_map) = base.as_object_mut() {
                 if let Some(cb_res_map) = cb_res.as_object_mut() {
                     base_map.append(cb_res_map);
                 }
-                return FieldError(name, Some(serde_json::json!(base_map)));
+                err.extensions = Some(serde_json::json!(base_map));
             } else {
-                return FieldError(name, Some(cb_res));
+                err.extensions = Some(cb_res);
             }
+            return err;
         }
 
-        FieldError(name, Some(cb(&self)))
+        err.extensions = Some(cb_res);
+        err
     }
 }
 
@@ -120,7 +252,7 @@ impl ErrorExtensions for FieldError {
 // not conflict with this implementation acting as a fallback.
 impl<E: std::fmt::Display> ErrorExtensions for &E {
     fn extend(&self) -> FieldError {
-        FieldError(format!("{}", self), None)
+        FieldError::new(format!("{}", self))
     }
 }
 
@@ -294,6 +426,24 @@ pub enum QueryError {
         extended_error: Option<serde_json::Value>,
     },
 
+    /// A field handler errored, and the error is marked as user facing or keeps the value it was
+    /// converted from.
+    ///
+    /// Other field errors are reported as [`FieldError`](#variant.FieldError). Converting a
+    /// [`FieldError`](struct.FieldError.html) into a `QueryError` picks the variant.
+    #[error("Failed to resolve field: {err}")]
+    DetailedFieldError {
+        /// The error description.
+        err: String,
+        /// Extensions to the error provided through the [`ErrorExtensions`](trait.ErrorExtensions)
+        /// or [`ResultExt`](trait.ResultExt) traits.
+        extended_error: Option<serde_json::Value>,
+        /// Whether the error is meant to be shown to clients.
+        user_facing: bool,
+        /// The original value, if the error was converted from one.
+        source: Option<ErrorSource>,
+    },
+
     /// Entity not found.
     #[error("Entity not found")]
     EntityNotFound,
@@ -301,6 +451,10 @@ pub enum QueryError {
     /// "__typename" must be an existing string.
     #[error("\"__typename\" must be an existing string")]
     TypeNameNotExists,
+
+    /// The deadline of the request or field expired before the field was resolved.
+    #[error("Timeout")]
+    Timeout,
 }
 
 impl QueryError {
@@ -312,6 +466,52 @@ impl QueryError {
             err: self,
         }
     }
+
+    /// Returns the extensions of a field error.
+    pub fn extended_error(&self) -> Option<&serde_json::Value> {
+        match self {
+            QueryError::FieldError { extended_error, .. }
+            | QueryError::DetailedFieldError { extended_error, .. } => extended_error.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Whether the error is a field error marked as user facing.
+    pub fn is_user_facing(&self) -> bool {
+        matches!(
+            self,
+            QueryError::DetailedFieldError {
+                user_facing: true,
+                ..
+            }
+        )
+    }
+
+    /// Returns the original value of a field error, if it was converted from one.
+    pub fn error_source(&self) -> Option<&ErrorSource> {
+        match self {
+            QueryError::DetailedFieldError { source, .. } => source.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl From<FieldError> for QueryError {
+    fn from(err: FieldError) -> Self {
+        if !err.user_facing && err.source.is_none() {
+            QueryError::FieldError {
+                err: err.message,
+                extended_error: err.extensions,
+            }
+        } else {
+            QueryError::DetailedFieldError {
+                err: err.message,
+                extended_error: err.extensions,
+                user_facing: err.user_facing,
+                source: err.source,
+            }
+        }
+    }
 }
 
 /// An error parsing the request.
@@ -326,6 +526,10 @@ pub enum ParseRequestError {
     #[error("Invalid request: {0}")]
     InvalidRequest(serde_json::Error),
 
+    /// The request's syntax was invalid in a binary encoding.
+    #[error("Invalid request: {0}")]
+    InvalidEncodedRequest(crate::http::EncodingError),
+
     /// The request's files map was invalid.
     #[error("Invalid files map: {0}")]
     InvalidFilesMap(serde_json::Error),
@@ -359,6 +563,10 @@ pub enum ParseRequestError {
     /// The request is a batch request, but the server does not support batch requests.
     #[error("Batch requests are not supported")]
     UnsupportedBatch,
+
+    /// The batch request contains more requests than the limit of the server.
+    #[error("Too many requests in the batch, the limit is {0}")]
+    BatchTooLarge(usize),
 }
 
 #[cfg(feature = "multipart")]
@@ -421,6 +629,7 @@ pub enum Error {
         path: Option<serde_json::Value>,
 
         /// The query error.
+        #[source]
         err: QueryError,
     },
 
@@ -467,3 +676,247 @@ impl Display for RuleErrors {
         Ok(())
     }
 }
+
+/// An error in the type system of a schema, returned by
+/// [`SchemaBuilder::try_finish`](crate::SchemaBuilder::try_finish).
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum SchemaError {
+    /// Two different Rust types are registered with the same GraphQL name.
+    #[error("Type \"{name}\" is defined by both `{first}` and `{second}`")]
+    DuplicateTypeName {
+        /// The GraphQL name of the type.
+        name: String,
+        /// The Rust type that registered the name first, and which is kept in the schema.
+        first: String,
+        /// The Rust type that registered the name later, and which is ignored.
+        second: String,
+    },
+
+    /// A type references a type that is not registered in the schema.
+    #[error("\"{location}\" references unknown type \"{ty}\"")]
+    UnknownType {
+        /// The field, argument or type that references the type.
+        location: String,
+        /// The name of the unknown type.
+        ty: String,
+    },
+
+    /// An object, interface or input object doesn't define any fields.
+    #[error("Type \"{name}\" must define one or more fields")]
+    NoFields {
+        /// The name of the type.
+        name: String,
+    },
+
+    /// A union doesn't include any member types.
+    #[error("Union \"{name}\" must include one or more member types")]
+    NoUnionMembers {
+        /// The name of the union.
+        name: String,
+    },
+
+    /// An enum doesn't define any values.
+    #[error("Enum \"{name}\" must define one or more values")]
+    NoEnumValues {
+        /// The name of the enum.
+        name: String,
+    },
+
+    /// A field returns a type that is not an output type.
+    #[error("\"{location}\" must be an output type, but \"{ty}\" is an input object")]
+    NotOutputType {
+        /// The field.
+        location: String,
+        /// The type of the field.
+        ty: String,
+    },
+
+    /// An argument or input field has a type that is not an input type.
+    #[error("\"{location}\" must be an input type, but \"{ty}\" is not")]
+    NotInputType {
+        /// The argument or input field.
+        location: String,
+        /// The type of the argument or input field.
+        ty: String,
+    },
+
+    /// A root operation type is not an object type.
+    #[error("Root operation type \"{name}\" must be an object type")]
+    InvalidRootType {
+        /// The name of the root type.
+        name: String,
+    },
+
+    /// A union includes a type that is not an object type.
+    #[error("Union \"{union}\" can only include object types, but \"{member}\" is not")]
+    InvalidUnionMember {
+        /// The name of the union.
+        union: String,
+        /// The name of the member type.
+        member: String,
+    },
+
+    /// A possible type of an interface is not an object type.
+    #[error(
+        "Possible types of interface \"{interface}\" must be object types, but \"{ty}\" is not"
+    )]
+    InvalidPossibleType {
+        /// The name of the interface.
+        interface: String,
+        /// The name of the possible type.
+        ty: String,
+    },
+
+    /// A type declares that it implements a type which is not an interface.
+    #[error(
+        "Type \"{ty}\" can only implement interfaces, but \"{interface}\" is not an interface"
+    )]
+    ImplementsNonInterface {
+        /// The name of the implementing type.
+        ty: String,
+        /// The name of the implemented type.
+        interface: String,
+    },
+
+    /// An interface declares that it implements itself.
+    #[error("Interface \"{name}\" must not implement itself")]
+    InterfaceImplementsItself {
+        /// The name of the interface.
+        name: String,
+    },
+
+    /// A type doesn't implement an interface that is implemented by one of its interfaces.
+    #[error("Type \"{ty}\" must implement \"{transitive_interface}\" because it is implemented by \"{interface}\"")]
+    MissingTransitiveInterface {
+        /// The name of the implementing type.
+        ty: String,
+        /// The name of the interface implemented by the type.
+        interface: String,
+        /// The name of the interface implemented by `interface`.
+        transitive_interface: String,
+    },
+
+    /// An implementation of an interface doesn't define a field of the interface.
+    #[error("Interface field \"{interface}.{field}\" is not provided by \"{ty}\"")]
+    MissingInterfaceField {
+        /// The name of the interface.
+        interface: String,
+        /// The name of the implementing type.
+        ty: String,
+        /// The name of the field.
+        field: String,
+    },
+
+    /// A field of an implementation doesn't return a subtype of the interface field's type.
+    #[error("Interface field \"{interface}.{field}\" expects type \"{expected}\" but \"{ty}.{field}\" is type \"{found}\"")]
+    InterfaceFieldTypeMismatch {
+        /// The name of the interface.
+        interface: String,
+        /// The name of the implementing type.
+        ty: String,
+        /// The name of the field.
+        field: String,
+        /// The type of the interface field.
+        expected: String,
+        /// The type of the implementation's field.
+        found: String,
+    },
+
+    /// A field of an implementation doesn't accept an argument of the interface field.
+    #[error("Interface field argument \"{interface}.{field}({argument}:)\" is not provided by \"{ty}.{field}\"")]
+    MissingInterfaceFieldArgument {
+        /// The name of the interface.
+        interface: String,
+        /// The name of the implementing type.
+        ty: String,
+        /// The name of the field.
+        field: String,
+        /// The name of the argument.
+        argument: String,
+    },
+
+    /// An argument of an implementation's field has a different type than in the interface.
+    #[error("Interface field argument \"{interface}.{field}({argument}:)\" expects type \"{expected}\" but \"{ty}.{field}({argument}:)\" is type \"{found}\"")]
+    InterfaceFieldArgumentTypeMismatch {
+        /// The name of the interface.
+        interface: String,
+        /// The name of the implementing type.
+        ty: String,
+        /// The name of the field.
+        field: String,
+        /// The name of the argument.
+        argument: String,
+        /// The type of the interface field's argument.
+        expected: String,
+        /// The type of the implementation's argument.
+        found: String,
+    },
+
+    /// A required argument or input field is deprecated.
+    #[error("Required argument or input field \"{location}\" can't be deprecated")]
+    RequiredInputValueDeprecated {
+        /// The argument or input field.
+        location: String,
+    },
+
+    /// A field of an implementation has a required argument that the interface field doesn't have.
+    #[error("Argument \"{ty}.{field}({argument}:)\" must not be required because it is not defined by interface field \"{interface}.{field}\"")]
+    RequiredExtraArgument {
+        /// The name of the interface.
+        interface: String,
+        /// The name of the implementing type.
+        ty: String,
+        /// The name of the field.
+        field: String,
+        /// The name of the argument.
+        argument: String,
+    },
+
+    /// The Relay `Node` interface or its query fields conflict with a type or field of the schema.
+    #[error(
+        "\"{name}\" is needed by the Relay `Node` interface, but is already defined by the schema"
+    )]
+    NodeConflict {
+        /// The conflicting type, or query field such as `Query.node`.
+        name: String,
+    },
+}
+
+/// A collection of SchemaError.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaErrors(Vec<SchemaError>);
+
+impl From<Vec<SchemaError>> for SchemaErrors {
+    fn from(errors: Vec<SchemaError>) -> Self {
+        Self(errors)
+    }
+}
+
+impl Deref for SchemaErrors {
+    type Target = Vec<SchemaError>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl IntoIterator for SchemaErrors {
+    type Item = SchemaError;
+    type IntoIter = std::vec::IntoIter<SchemaError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl Display for SchemaErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "Schema error:")?;
+        for err in &self.0 {
+            writeln!(f, "  {}", err)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SchemaErrors {}
@@ -83,6 +83,17 @@ impl Extension for ApolloTracing {
         self.end_time = Utc::now();
     }
 
+    fn subscription_event_start(&mut self, _field_name: &str) {
+        // Each event of a subscription is reported with its own timings.
+        self.start_time = Utc::now();
+        self.pending_resolves.clear();
+        self.resolves.clear();
+    }
+
+    fn subscription_event_end(&mut self, _field_name: &str) {
+        self.end_time = Utc::now();
+    }
+
     fn resolve_start(&mut self, info: &ResolveInfo<'_>) {
         self.pending_resolves.insert(
             info.resolve_id.current,
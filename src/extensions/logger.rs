@@ -1,4 +1,4 @@
-use crate::extensions::{Extension, ResolveInfo};
+use crate::extensions::{Extension, RedactedRequest, ResolveInfo};
 use crate::parser::types::{ExecutableDocument, OperationType, Selection};
 use crate::{Error, Variables};
 use itertools::Itertools;
@@ -6,6 +6,10 @@ use log::{error, info, trace};
 use std::borrow::Cow;
 
 /// Logger extension
+///
+/// The values of secret arguments and input fields are masked in the logged query and
+/// variables. Since they cannot be located in a query that fails to parse, the query and
+/// variables are not logged for parse errors.
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "log")))]
 pub struct Logger {
     enabled: bool,
@@ -24,11 +28,6 @@ impl Default for Logger {
 }
 
 impl Extension for Logger {
-    fn parse_start(&mut self, query_source: &str, variables: &Variables) {
-        self.query = query_source.replace(char::is_whitespace, "");
-        self.variables = variables.clone();
-    }
-
     fn parse_end(&mut self, document: &ExecutableDocument) {
         let is_schema = document
             .operations
@@ -38,9 +37,16 @@ impl Extension for Logger {
 
         if is_schema {
             self.enabled = false;
-            return;
         }
+    }
+
+    fn redacted_request(&mut self, request: &RedactedRequest<'_>) {
+        self.query = request.query().replace(char::is_whitespace, "");
+        self.variables = request.variables();
 
+        if !self.enabled {
+            return;
+        }
         info!(target: "async-graphql", "[Query] query: \"{}\", variables: {}", &self.query, self.variables);
     }
 
@@ -58,23 +64,47 @@ impl Extension for Logger {
         trace!(target: "async-graphql", "[ResolveEnd] path: \"{}\"", info.path_node);
     }
 
+    fn subscription_event_start(&mut self, field_name: &str) {
+        if !self.enabled {
+            return;
+        }
+        trace!(target: "async-graphql", "[SubscriptionEventStart] field: \"{}\"", field_name);
+    }
+
+    fn subscription_event_end(&mut self, field_name: &str) {
+        if !self.enabled {
+            return;
+        }
+        trace!(target: "async-graphql", "[SubscriptionEventEnd] field: \"{}\"", field_name);
+    }
+
     fn error(&mut self, err: &Error) {
         match err {
             Error::Parse(err) => {
                 error!(
-                    target: "async-graphql", "[ParseError] {}query: \"{}\", variables: {}, {}",
+                    target: "async-graphql", "[ParseError] {}{}",
                     if let Some(pos) = err.positions().next() {
                         // TODO: Make this more efficient
                         format!("pos: [{}:{}], ", pos.line, pos.column)
                     } else {
                         String::new()
                     },
-                    self.query,
-                    self.variables,
                     err
                 )
             }
             Error::Query { pos, path, err } => {
+                let mut extensions = match err.extended_error() {
+                    Some(extended_error) => format!(", extensions: {}", extended_error),
+                    None => String::new(),
+                };
+                let mut cause = err
+                    .error_source()
+                    .and_then(|source| source.as_error())
+                    .and_then(|source| source.source());
+                while let Some(err) = cause {
+                    extensions.push_str(&format!(", caused by: {}", err));
+                    cause = err.source();
+                }
                 if let Some(path) = path {
                     let path = if let serde_json::Value::Array(values) = path {
                         values
@@ -88,9 +118,9 @@ impl Extension for Logger {
                     } else {
                         String::new()
                     };
-                    error!(target: "async-graphql", "[QueryError] path: \"{}\", pos: [{}:{}], query: \"{}\", variables: {}, {}", path, pos.line, pos.column, self.query, self.variables, err)
+                    error!(target: "async-graphql", "[QueryError] path: \"{}\", pos: [{}:{}], query: \"{}\", variables: {}, {}{}", path, pos.line, pos.column, self.query, self.variables, err, extensions)
                 } else {
-                    error!(target: "async-graphql", "[QueryError] pos: [{}:{}], query: \"{}\", variables: {}, {}", pos.line, pos.column, self.query, self.variables, err)
+                    error!(target: "async-graphql", "[QueryError] pos: [{}:{}], query: \"{}\", variables: {}, {}{}", pos.line, pos.column, self.query, self.variables, err, extensions)
                 }
             }
             Error::Rule { errors } => {
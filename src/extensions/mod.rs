@@ -4,6 +4,7 @@
 mod apollo_tracing;
 #[cfg(feature = "log")]
 mod logger;
+mod redacted;
 #[cfg(feature = "tracing")]
 mod tracing;
 
@@ -14,6 +15,8 @@ use crate::{FieldResult, QueryEnv, Result, SchemaEnv, Variables};
 pub use self::apollo_tracing::ApolloTracing;
 #[cfg(feature = "log")]
 pub use self::logger::Logger;
+pub(crate) use self::redacted::redact_unparsed;
+pub use self::redacted::RedactedRequest;
 #[cfg(feature = "tracing")]
 pub use self::tracing::Tracing;
 use crate::parser::types::ExecutableDocument;
@@ -41,6 +44,11 @@ pub struct ResolveInfo<'a> {
     /// Current return type, is qualified name.
     pub return_type: &'a str,
 
+    /// Whether the field, or one of its parents, is marked with `#[field(secret)]`.
+    ///
+    /// Extensions that record the values of fields should mask the values of secret fields.
+    pub is_secret: bool,
+
     #[doc(hidden)]
     pub schema_env: &'a SchemaEnv,
 
@@ -76,6 +84,12 @@ impl<'a> ResolveInfo<'a> {
         self.query_env
             .ctx_data
             .get(&TypeId::of::<D>())
+            .or_else(|| {
+                self.query_env
+                    .shared_data
+                    .as_ref()
+                    .and_then(|data| data.get(&TypeId::of::<D>()))
+            })
             .or_else(|| self.schema_env.data.get(&TypeId::of::<D>()))
             .and_then(|d| d.downcast_ref::<D>())
     }
@@ -90,11 +104,22 @@ pub trait Extension: Sync + Send + 'static {
     }
 
     /// Called at the begin of the parse.
+    ///
+    /// The values of secret arguments and input fields are masked in `query_source` and
+    /// `variables`. Since they can only be located in a parsed query, the query is parsed before
+    /// this is called, and if it cannot be parsed the whole query and all the variable values are
+    /// masked.
     fn parse_start(&mut self, query_source: &str, variables: &Variables) {}
 
     /// Called at the end of the parse.
     fn parse_end(&mut self, document: &ExecutableDocument) {}
 
+    /// Called after the parse with a view of the request in which secret values are masked.
+    ///
+    /// The view gives the same query and variables as `parse_start`. It is not called if the
+    /// query cannot be parsed.
+    fn redacted_request(&mut self, request: &RedactedRequest<'_>) {}
+
     /// Called at the begin of the validation.
     fn validation_start(&mut self) {}
 
@@ -110,6 +135,14 @@ pub trait Extension: Sync + Send + 'static {
     /// Called at the begin of the resolve field.
     fn resolve_start(&mut self, info: &ResolveInfo<'_>) {}
 
+    /// Called when a subscription field receives an event, before it is resolved.
+    ///
+    /// `field_name` is the response key of the subscription field.
+    fn subscription_event_start(&mut self, field_name: &str) {}
+
+    /// Called when an event of a subscription field has been resolved.
+    fn subscription_event_end(&mut self, field_name: &str) {}
+
     /// Called at the end of the resolve field.
     fn resolve_end(&mut self, info: &ResolveInfo<'_>) {}
 
@@ -146,6 +179,10 @@ impl Extension for Extensions {
         self.0.iter_mut().for_each(|e| e.parse_end(document));
     }
 
+    fn redacted_request(&mut self, request: &RedactedRequest<'_>) {
+        self.0.iter_mut().for_each(|e| e.redacted_request(request));
+    }
+
     fn validation_start(&mut self) {
         self.0.iter_mut().for_each(|e| e.validation_start());
     }
@@ -166,6 +203,18 @@ impl Extension for Extensions {
         self.0.iter_mut().for_each(|e| e.resolve_start(info));
     }
 
+    fn subscription_event_start(&mut self, field_name: &str) {
+        self.0
+            .iter_mut()
+            .for_each(|e| e.subscription_event_start(field_name));
+    }
+
+    fn subscription_event_end(&mut self, field_name: &str) {
+        self.0
+            .iter_mut()
+            .for_each(|e| e.subscription_event_end(field_name));
+    }
+
     fn resolve_end(&mut self, resolve_id: &ResolveInfo<'_>) {
         self.0.iter_mut().for_each(|e| e.resolve_end(resolve_id));
     }
@@ -0,0 +1,382 @@
+use crate::parser::types::{
+    ExecutableDocument, OperationType, Selection, SelectionSet, Value as InputValue,
+};
+use crate::registry::{MetaInputValue, MetaType, MetaTypeName, Registry};
+use crate::{Pos, Positioned, Value, Variables};
+use std::collections::HashSet;
+
+/// The text that replaces secret values.
+const REDACTED: &str = "<redacted>";
+
+/// A view of a request in which the values of secret arguments and input fields are masked.
+///
+/// Arguments are marked as secret with `#[arg(secret)]`, and input object fields with
+/// `#[field(secret)]`. Secret values written inline in the query are replaced by
+/// `"<redacted>"`, and so are the variables that are passed to them. Use this instead of the
+/// raw query source and variables in extensions that log or record the request.
+pub struct RedactedRequest<'a> {
+    registry: &'a Registry,
+    query_source: &'a str,
+    document: &'a ExecutableDocument,
+    variables: &'a Variables,
+}
+
+impl<'a> RedactedRequest<'a> {
+    pub(crate) fn new(
+        registry: &'a Registry,
+        query_source: &'a str,
+        document: &'a ExecutableDocument,
+        variables: &'a Variables,
+    ) -> Self {
+        Self {
+            registry,
+            query_source,
+            document,
+            variables,
+        }
+    }
+
+    /// Returns the query source with the secret values written inline masked.
+    ///
+    /// If the position of a secret value cannot be found in the source, the whole query is
+    /// masked.
+    pub fn query(&self) -> String {
+        let mut redactor = self.redactor();
+        redactor.redact_default_values(self.document);
+        redactor
+            .literals
+            .sort_by_key(|(pos, _)| (pos.line, pos.column));
+
+        let mut query = String::new();
+        let mut offset = 0;
+        for (pos, value) in &redactor.literals {
+            let start = match byte_offset(self.query_source, *pos) {
+                Some(start) if start >= offset => start,
+                _ => return REDACTED.to_string(),
+            };
+            let end = match value_end(self.query_source, start) {
+                Some(end) => end,
+                None => return REDACTED.to_string(),
+            };
+            query.push_str(&self.query_source[offset..start]);
+            query.push_str(&value.to_string());
+            offset = end;
+        }
+        query.push_str(&self.query_source[offset..]);
+        query
+    }
+
+    /// Returns the variables with the values of the secret variables masked.
+    pub fn variables(&self) -> Variables {
+        let mut redactor = self.redactor();
+        let mut variables = self.variables.clone();
+        for (name, value) in &mut variables.0 {
+            if redactor.secret_variables.contains(name.as_str()) {
+                *value = Value::String(REDACTED.to_string());
+            }
+        }
+        for (name, ty) in std::mem::take(&mut redactor.typed_variables) {
+            if let Some(value) = variables.0.get_mut(name.as_str()) {
+                let mut input = value.clone().into_value();
+                if redactor.redact_value(&ty, &mut input) {
+                    if let Some(redacted) = input.into_const() {
+                        *value = redacted;
+                    }
+                }
+            }
+        }
+        variables
+    }
+
+    fn redactor(&self) -> Redactor<'a> {
+        let mut redactor = Redactor {
+            registry: self.registry,
+            literals: Vec::new(),
+            secret_variables: HashSet::new(),
+            typed_variables: Vec::new(),
+        };
+
+        for (_, operation) in self.document.operations.iter() {
+            let root = match operation.node.ty {
+                OperationType::Query => Some(self.registry.query_type.as_str()),
+                OperationType::Mutation => self.registry.mutation_type.as_deref(),
+                OperationType::Subscription => self.registry.subscription_type.as_deref(),
+            };
+            if let Some(root) = root {
+                redactor.redact_selection_set(root, &operation.node.selection_set.node);
+            }
+        }
+        for fragment in self.document.fragments.values() {
+            redactor.redact_selection_set(
+                &fragment.node.type_condition.node.on.node,
+                &fragment.node.selection_set.node,
+            );
+        }
+
+        redactor
+    }
+}
+
+/// Returns the query and variables of a request whose query cannot be parsed.
+///
+/// Secret values cannot be located in such a query, so the query and all the variable values are
+/// masked.
+pub(crate) fn redact_unparsed(variables: &Variables) -> (&'static str, Variables) {
+    let mut variables = variables.clone();
+    for value in variables.0.values_mut() {
+        *value = Value::String(REDACTED.to_string());
+    }
+    (REDACTED, variables)
+}
+
+struct Redactor<'a> {
+    registry: &'a Registry,
+    /// The positions of inline values that must be replaced, with their replacements.
+    literals: Vec<(Pos, InputValue)>,
+    /// The variables passed to secret arguments or fields.
+    secret_variables: HashSet<String>,
+    /// The variables passed to other arguments or fields, with their types.
+    typed_variables: Vec<(String, String)>,
+}
+
+impl<'a> Redactor<'a> {
+    fn redact_selection_set(&mut self, parent_type: &str, selection_set: &SelectionSet) {
+        for selection in &selection_set.items {
+            match &selection.node {
+                Selection::Field(field) => {
+                    let meta_field = match self
+                        .registry
+                        .types
+                        .get(parent_type)
+                        .and_then(|ty| ty.field_by_name(&field.node.name.node))
+                    {
+                        Some(meta_field) => meta_field,
+                        None => continue,
+                    };
+                    for (name, value) in &field.node.arguments {
+                        if let Some(meta_arg) = meta_field.args.get(name.node.as_str()) {
+                            self.redact_argument(meta_arg, value);
+                        }
+                    }
+                    self.redact_selection_set(
+                        MetaTypeName::concrete_typename(&meta_field.ty),
+                        &field.node.selection_set.node,
+                    );
+                }
+                Selection::InlineFragment(fragment) => {
+                    let ty = fragment
+                        .node
+                        .type_condition
+                        .as_ref()
+                        .map(|condition| condition.node.on.node.as_str())
+                        .unwrap_or(parent_type);
+                    self.redact_selection_set(ty, &fragment.node.selection_set.node);
+                }
+                Selection::FragmentSpread(_) => {}
+            }
+        }
+    }
+
+    fn redact_argument(&mut self, meta: &MetaInputValue, value: &Positioned<InputValue>) {
+        if meta.is_secret {
+            match &value.node {
+                InputValue::Variable(name) => {
+                    self.secret_variables.insert(name.to_string());
+                }
+                InputValue::Null => {}
+                _ => self
+                    .literals
+                    .push((value.pos, InputValue::String(REDACTED.to_string()))),
+            }
+        } else {
+            let mut redacted = value.node.clone();
+            if self.redact_value(&meta.ty, &mut redacted) {
+                self.literals.push((value.pos, redacted));
+            }
+        }
+    }
+
+    /// Masks the secret input fields of a value of type `ty`, returning whether anything was
+    /// masked.
+    fn redact_value(&mut self, ty: &str, value: &mut InputValue) -> bool {
+        if let InputValue::Variable(name) = value {
+            self.typed_variables
+                .push((name.to_string(), ty.to_string()));
+            return false;
+        }
+
+        match MetaTypeName::create(ty) {
+            MetaTypeName::NonNull(ty) => self.redact_value(ty, value),
+            MetaTypeName::List(ty) => match value {
+                InputValue::List(items) => {
+                    let mut redacted = false;
+                    for item in items {
+                        redacted |= self.redact_value(ty, item);
+                    }
+                    redacted
+                }
+                _ => self.redact_value(ty, value),
+            },
+            MetaTypeName::Named(ty) => {
+                let (fields, input_fields) = match (value, self.registry.types.get(ty)) {
+                    (
+                        InputValue::Object(fields),
+                        Some(MetaType::InputObject { input_fields, .. }),
+                    ) => (fields, input_fields),
+                    _ => return false,
+                };
+                let mut redacted = false;
+                for (name, value) in fields {
+                    let meta = match input_fields.get(name.as_str()) {
+                        Some(meta) => meta,
+                        None => continue,
+                    };
+                    if !meta.is_secret {
+                        redacted |= self.redact_value(&meta.ty, value);
+                    } else if let InputValue::Variable(name) = value {
+                        self.secret_variables.insert(name.to_string());
+                    } else if *value != InputValue::Null {
+                        *value = InputValue::String(REDACTED.to_string());
+                        redacted = true;
+                    }
+                }
+                redacted
+            }
+        }
+    }
+
+    /// Masks the default values of the variables passed to secret arguments or fields.
+    fn redact_default_values(&mut self, document: &ExecutableDocument) {
+        for (_, operation) in document.operations.iter() {
+            for definition in &operation.node.variable_definitions {
+                let default_value = match &definition.node.default_value {
+                    Some(default_value) => default_value,
+                    None => continue,
+                };
+                let name = definition.node.name.node.as_str();
+                if self.secret_variables.contains(name) {
+                    self.literals
+                        .push((default_value.pos, InputValue::String(REDACTED.to_string())));
+                    continue;
+                }
+
+                let types: Vec<String> = self
+                    .typed_variables
+                    .iter()
+                    .filter(|(variable, _)| variable == name)
+                    .map(|(_, ty)| ty.clone())
+                    .collect();
+                let mut value = default_value.node.clone().into_value();
+                let mut redacted = false;
+                for ty in &types {
+                    redacted |= self.redact_value(ty, &mut value);
+                }
+                if redacted {
+                    self.literals.push((default_value.pos, value));
+                }
+            }
+        }
+    }
+}
+
+/// Finds the byte offset of a position reported by the parser.
+///
+/// The parser counts columns in characters, so the source is walked character by character to
+/// find the position, and the byte offset of that character is returned.
+fn byte_offset(source: &str, pos: Pos) -> Option<usize> {
+    let mut line = 1;
+    let mut column = 1;
+    for (offset, c) in source.char_indices() {
+        if line == pos.line && column == pos.column {
+            return Some(offset);
+        }
+        match c {
+            '\r' => column = 1,
+            '\n' => {
+                line += 1;
+                column = 1;
+            }
+            _ => column += 1,
+        }
+    }
+    None
+}
+
+/// Finds the end of the value that starts at `start`, or `None` if there is no value there.
+fn value_end(source: &str, start: usize) -> Option<usize> {
+    let rest = &source[start..];
+    if rest.starts_with("\"\"\"") {
+        let mut offset = 3;
+        loop {
+            let end = offset + rest[offset..].find("\"\"\"")?;
+            if !rest[..end].ends_with('\\') {
+                return Some(start + end + 3);
+            }
+            offset = end + 3;
+        }
+    }
+
+    let mut chars = rest.char_indices();
+    match chars.next()? {
+        (_, '"') => {
+            while let Some((offset, c)) = chars.next() {
+                match c {
+                    '\\' => {
+                        chars.next();
+                    }
+                    '"' => return Some(start + offset + 1),
+                    '\n' | '\r' => return None,
+                    _ => {}
+                }
+            }
+            None
+        }
+        (_, open @ '[') | (_, open @ '{') => {
+            let close = if open == '[' { ']' } else { '}' };
+            let mut offset = 1;
+            loop {
+                let c = rest[offset..].chars().next()?;
+                if c == close {
+                    return Some(start + offset + 1);
+                } else if c == '"' || c == '[' || c == '{' {
+                    offset = value_end(source, start + offset)? - start;
+                } else if c == '#' {
+                    offset += rest[offset..].find('\n')?;
+                } else {
+                    offset += c.len_utf8();
+                }
+            }
+        }
+        (_, c) if c == '-' || c == '$' || c == '_' || c.is_ascii_alphanumeric() => Some(
+            start
+                + rest
+                    .find(|c: char| {
+                        !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '+' | '.' | '$'))
+                    })
+                    .unwrap_or(rest.len()),
+        ),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_end() {
+        let source = r#"(a: "x\"y", b: [1, {c: "]"}], d: -1.5e3, e: """a\"""b""")"#;
+        for (value, end) in &[
+            (r#""x\"y""#, ","),
+            (r#"[1, {c: "]"}]"#, ","),
+            ("-1.5e3", ","),
+            (r#""""a\"""b""""#, ")"),
+        ] {
+            let start = source.find(value).unwrap();
+            let value_end = value_end(source, start).unwrap();
+            assert_eq!(&source[start..value_end], *value);
+            assert!(source[value_end..].starts_with(end));
+        }
+        assert_eq!(value_end("(a: )", 4), None);
+    }
+}
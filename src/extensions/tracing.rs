@@ -1,7 +1,8 @@
-use crate::extensions::{Extension, ResolveInfo};
+use crate::extensions::{Extension, RedactedRequest, ResolveInfo};
 use crate::{Error, Variables};
 use async_graphql_parser::types::ExecutableDocument;
 use std::collections::BTreeMap;
+use tracing::field::{display, Empty};
 use tracing::{span, Level, Span};
 
 /// Tracing extension
@@ -16,17 +17,18 @@ pub struct Tracing {
     parse: Option<Span>,
     validation: Option<Span>,
     execute: Option<Span>,
+    subscription_event: Option<Span>,
     fields: BTreeMap<usize, Span>,
 }
 
 impl Extension for Tracing {
-    fn parse_start(&mut self, query_source: &str, _variables: &Variables) {
+    fn parse_start(&mut self, _query_source: &str, _variables: &Variables) {
         let root_span = span!(
             target: "async_graphql::graphql",
             parent: None,
             Level::INFO,
             "query",
-            source = %query_source
+            source = Empty
         );
 
         let parse_span = span!(
@@ -49,6 +51,12 @@ impl Extension for Tracing {
             .and_then(|span| span.with_subscriber(|(id, d)| d.exit(id)));
     }
 
+    fn redacted_request(&mut self, request: &RedactedRequest<'_>) {
+        if let Some(root) = &self.root {
+            root.record("source", &display(request.query()));
+        }
+    }
+
     fn validation_start(&mut self) {
         if let Some(parent) = &self.root {
             let validation_span = span!(
@@ -69,7 +77,12 @@ impl Extension for Tracing {
     }
 
     fn execution_start(&mut self) {
-        let execute_span = if let Some(parent) = &self.root {
+        // The events of a subscription stream are executed in their own span.
+        let parent = self
+            .root
+            .as_ref()
+            .or_else(|| self.subscription_event.as_ref());
+        let execute_span = if let Some(parent) = parent {
             span!(
                 target: "async_graphql::graphql",
                 parent: parent,
@@ -77,7 +90,6 @@ impl Extension for Tracing {
                 "execute"
             )
         } else {
-            // For every step of the subscription stream.
             span!(
                 target: "async_graphql::graphql",
                 parent: None,
@@ -100,6 +112,24 @@ impl Extension for Tracing {
             .and_then(|span| span.with_subscriber(|(id, d)| d.exit(id)));
     }
 
+    fn subscription_event_start(&mut self, field_name: &str) {
+        let event_span = span!(
+            target: "async_graphql::graphql",
+            parent: None,
+            Level::INFO,
+            "subscription_event",
+            field = %field_name,
+        );
+        event_span.with_subscriber(|(id, d)| d.enter(id));
+        self.subscription_event.replace(event_span);
+    }
+
+    fn subscription_event_end(&mut self, _field_name: &str) {
+        self.subscription_event
+            .take()
+            .and_then(|span| span.with_subscriber(|(id, d)| d.exit(id)));
+    }
+
     fn resolve_start(&mut self, info: &ResolveInfo<'_>) {
         let parent_span = match info.resolve_id.parent {
             Some(parent_id) if parent_id > 0 => self.fields.get(&parent_id),
@@ -142,6 +172,9 @@ impl Extension for Tracing {
         self.validation
             .take()
             .and_then(|span| span.with_subscriber(|(id, d)| d.exit(id)));
+        self.subscription_event
+            .take()
+            .and_then(|span| span.with_subscriber(|(id, d)| d.exit(id)));
         self.parse
             .take()
             .and_then(|span| span.with_subscriber(|(id, d)| d.exit(id)));
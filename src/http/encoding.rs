@@ -0,0 +1,256 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use thiserror::Error;
+
+/// An encoding of the requests and responses sent over HTTP.
+///
+/// JSON is always supported, CBOR requires the `cbor` feature and MessagePack requires the
+/// `msgpack` feature. 64-bit integers are preserved by all the encodings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Encoding {
+    /// JSON, `application/json`
+    Json,
+
+    /// CBOR, `application/cbor`
+    #[cfg(feature = "cbor")]
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "cbor")))]
+    Cbor,
+
+    /// MessagePack, `application/msgpack`
+    #[cfg(feature = "msgpack")]
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "msgpack")))]
+    MessagePack,
+}
+
+impl Default for Encoding {
+    fn default() -> Self {
+        Encoding::Json
+    }
+}
+
+/// An error encoding or decoding a value.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum EncodingError {
+    /// A JSON error.
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+
+    /// A CBOR error.
+    #[cfg(feature = "cbor")]
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "cbor")))]
+    #[error("{0}")]
+    Cbor(#[from] serde_cbor::Error),
+
+    /// A MessagePack encoding error.
+    #[cfg(feature = "msgpack")]
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "msgpack")))]
+    #[error("{0}")]
+    MessagePackEncode(#[from] rmp_serde::encode::Error),
+
+    /// A MessagePack decoding error.
+    #[cfg(feature = "msgpack")]
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "msgpack")))]
+    #[error("{0}")]
+    MessagePackDecode(#[from] rmp_serde::decode::Error),
+}
+
+impl Encoding {
+    /// All the supported encodings, in order of preference.
+    const ALL: &'static [Encoding] = &[
+        Encoding::Json,
+        #[cfg(feature = "cbor")]
+        Encoding::Cbor,
+        #[cfg(feature = "msgpack")]
+        Encoding::MessagePack,
+    ];
+
+    /// Returns the MIME type of the encoding, to use in the `Content-Type` header.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Encoding::Json => "application/json",
+            #[cfg(feature = "cbor")]
+            Encoding::Cbor => "application/cbor",
+            #[cfg(feature = "msgpack")]
+            Encoding::MessagePack => "application/msgpack",
+        }
+    }
+
+    fn matches(self, mime: &str) -> bool {
+        match self {
+            Encoding::Json => mime == "application/json",
+            #[cfg(feature = "cbor")]
+            Encoding::Cbor => mime == "application/cbor",
+            #[cfg(feature = "msgpack")]
+            Encoding::MessagePack => {
+                mime == "application/msgpack" || mime == "application/x-msgpack"
+            }
+        }
+    }
+
+    /// Returns the encoding of a `Content-Type` header, or `None` if it is not supported.
+    pub fn from_content_type(content_type: &str) -> Option<Encoding> {
+        let mime = essence(content_type);
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|encoding| encoding.matches(&mime))
+    }
+
+    /// Chooses the encoding of a response from the `Accept` header of the request.
+    ///
+    /// The supported media range with the highest quality value is chosen, and JSON is used if
+    /// there is no header or if none of its media ranges are supported.
+    pub fn negotiate(accept: Option<&str>) -> Encoding {
+        let accept = match accept {
+            Some(accept) => accept,
+            None => return Encoding::Json,
+        };
+
+        let mut best: Option<(Encoding, f32)> = None;
+        for range in accept.split(',') {
+            let mut params = range.split(';');
+            let mime = essence(params.next().unwrap_or_default());
+            let quality = params
+                .filter_map(|param| {
+                    let param = param.trim();
+                    param
+                        .strip_prefix("q=")
+                        .or_else(|| param.strip_prefix("Q="))
+                })
+                .find_map(|quality| quality.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if quality <= 0.0 {
+                continue;
+            }
+
+            let encoding = if mime == "*/*" || mime == "application/*" {
+                Some(Encoding::Json)
+            } else {
+                Self::ALL
+                    .iter()
+                    .copied()
+                    .find(|encoding| encoding.matches(&mime))
+            };
+            if let Some(encoding) = encoding {
+                if best.map_or(true, |(_, best_quality)| quality > best_quality) {
+                    best = Some((encoding, quality));
+                }
+            }
+        }
+        best.map(|(encoding, _)| encoding).unwrap_or_default()
+    }
+
+    /// Encode a value, such as a `BatchResponse`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the value cannot be represented in the encoding.
+    pub fn encode<T: Serialize>(self, value: &T) -> Result<Vec<u8>, EncodingError> {
+        Ok(match self {
+            Encoding::Json => serde_json::to_vec(value)?,
+            #[cfg(feature = "cbor")]
+            Encoding::Cbor => serde_cbor::to_vec(value)?,
+            #[cfg(feature = "msgpack")]
+            Encoding::MessagePack => rmp_serde::to_vec_named(value)?,
+        })
+    }
+
+    /// Decode a value, such as a `BatchRequest`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the data is not a valid encoding of the value.
+    pub fn decode<T: DeserializeOwned>(self, data: &[u8]) -> Result<T, EncodingError> {
+        Ok(match self {
+            Encoding::Json => serde_json::from_slice(data)?,
+            #[cfg(feature = "cbor")]
+            Encoding::Cbor => serde_cbor::from_slice(data)?,
+            #[cfg(feature = "msgpack")]
+            Encoding::MessagePack => rmp_serde::from_slice(data)?,
+        })
+    }
+}
+
+/// Returns the media type of a header value without its parameters, in lowercase.
+fn essence(mime: &str) -> String {
+    mime.split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Request, Response, Value};
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(Encoding::negotiate(None), Encoding::Json);
+        assert_eq!(Encoding::negotiate(Some("text/html")), Encoding::Json);
+        assert_eq!(Encoding::negotiate(Some("*/*")), Encoding::Json);
+        assert_eq!(
+            Encoding::from_content_type("application/json; charset=utf-8"),
+            Some(Encoding::Json)
+        );
+        assert_eq!(Encoding::from_content_type("text/plain"), None);
+
+        #[cfg(feature = "cbor")]
+        {
+            assert_eq!(
+                Encoding::negotiate(Some("application/json;q=0.5, application/cbor")),
+                Encoding::Cbor
+            );
+            assert_eq!(
+                Encoding::negotiate(Some("application/json, application/cbor;q=0.9")),
+                Encoding::Json
+            );
+            assert_eq!(
+                Encoding::from_content_type("Application/CBOR"),
+                Some(Encoding::Cbor)
+            );
+        }
+
+        #[cfg(feature = "msgpack")]
+        assert_eq!(
+            Encoding::negotiate(Some("application/x-msgpack")),
+            Encoding::MessagePack
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let data = Value::from_json(serde_json::json!({
+            "max": u64::MAX,
+            "min": i64::MIN,
+            "float": 1.5,
+            "list": [true, null, "a"],
+        }))
+        .unwrap();
+
+        for encoding in Encoding::ALL {
+            let request: Request = encoding
+                .decode(
+                    &encoding
+                        .encode(&serde_json::json!({
+                            "query": "{ a }",
+                            "variables": { "max": u64::MAX, "min": i64::MIN },
+                        }))
+                        .unwrap(),
+                )
+                .unwrap();
+            assert_eq!(request.query, "{ a }");
+            assert_eq!(
+                request.variables.into_value(),
+                serde_json::json!({ "max": u64::MAX, "min": i64::MIN })
+            );
+
+            let bytes = encoding.encode(&Response::new(data.clone())).unwrap();
+            let response: serde_json::Value = encoding.decode(&bytes).unwrap();
+            assert_eq!(data, response["data"], "{:?}", encoding);
+        }
+    }
+}
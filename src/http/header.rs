@@ -0,0 +1,21 @@
+use ::http::header::{InvalidHeaderName, InvalidHeaderValue};
+use std::convert::Infallible;
+use thiserror::Error;
+
+/// An invalid HTTP header name or value.
+#[derive(Debug, Error)]
+pub enum HeaderError {
+    /// The header name is not valid.
+    #[error("{0}")]
+    InvalidName(#[from] InvalidHeaderName),
+
+    /// The header value is not valid.
+    #[error("{0}")]
+    InvalidValue(#[from] InvalidHeaderValue),
+}
+
+impl From<Infallible> for HeaderError {
+    fn from(err: Infallible) -> Self {
+        match err {}
+    }
+}
@@ -1,16 +1,25 @@
 //! A helper module that supports HTTP
 
+mod encoding;
 mod graphiql_source;
+mod header;
 #[cfg(feature = "multipart")]
 mod multipart;
 mod playground_source;
+#[cfg(feature = "multipart")]
+mod upload_storage;
 mod websocket;
 
+pub use ::http::header::{HeaderMap, HeaderName, HeaderValue};
+pub use encoding::{Encoding, EncodingError};
 pub use graphiql_source::graphiql_source;
+pub use header::HeaderError;
 #[cfg(feature = "multipart")]
 pub use multipart::MultipartOptions;
 pub use playground_source::{playground_source, GraphQLPlaygroundConfig};
-pub use websocket::WebSocket;
+#[cfg(feature = "multipart")]
+pub use upload_storage::{MemoryStorage, SinkStorage, TempFileStorage, UploadInfo, UploadStorage};
+pub use websocket::{Backpressure, WebSocket};
 
 use crate::{BatchRequest, ParseRequestError, Request};
 use futures::io::AsyncRead;
@@ -32,16 +41,31 @@ pub async fn receive_body(
 #[cfg(feature = "multipart")]
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
 /// Receive a GraphQL request from a content type and body.
+///
+/// The body is decoded according to its content type, which can be `multipart/form-data` or
+/// any of the supported [`Encoding`](enum.Encoding.html)s. JSON is used by default.
+///
+/// A batch request with more requests than `MultipartOptions::max_batch_size` is rejected with
+/// `ParseRequestError::BatchTooLarge`, whatever its content type.
 pub async fn receive_batch_body(
     content_type: Option<impl AsRef<str>>,
     body: impl AsyncRead + Send + 'static,
     opts: MultipartOptions,
 ) -> Result<BatchRequest, ParseRequestError> {
-    if let Some(Ok(boundary)) = content_type.map(multer::parse_boundary) {
-        multipart::receive_batch_multipart(body, boundary, opts).await
+    let max_batch_size = opts.max_batch_size;
+    let content_type = content_type.as_ref().map(AsRef::as_ref);
+    let batch_request = if let Some(Ok(boundary)) = content_type.map(multer::parse_boundary) {
+        multipart::receive_batch_multipart(body, boundary, opts).await?
     } else {
-        receive_batch_json(body).await
+        let encoding = content_type
+            .and_then(Encoding::from_content_type)
+            .unwrap_or_default();
+        receive_batch_encoded(body, encoding).await?
+    };
+    if let Some(max_batch_size) = max_batch_size {
+        batch_request.check_batch_size(max_batch_size)?;
     }
+    Ok(batch_request)
 }
 
 /// Receive a GraphQL request from a body as JSON.
@@ -52,13 +76,33 @@ pub async fn receive_json(
 }
 
 /// Receive a GraphQL batch request from a body as JSON.
+///
+/// The size of the batch is not limited, call `BatchRequest::check_batch_size` on the result to
+/// limit it.
 pub async fn receive_batch_json(
     body: impl AsyncRead + Send + 'static,
+) -> Result<BatchRequest, ParseRequestError> {
+    receive_batch_encoded(body, Encoding::Json).await
+}
+
+/// Receive a GraphQL batch request from a body in an encoding.
+///
+/// The size of the batch is not limited, call `BatchRequest::check_batch_size` on the result to
+/// limit it.
+pub async fn receive_batch_encoded(
+    body: impl AsyncRead + Send + 'static,
+    encoding: Encoding,
 ) -> Result<BatchRequest, ParseRequestError> {
     let mut data = Vec::new();
     futures::pin_mut!(body);
     body.read_to_end(&mut data)
         .await
         .map_err(ParseRequestError::Io)?;
-    Ok(serde_json::from_slice::<BatchRequest>(&data).map_err(ParseRequestError::InvalidRequest)?)
+    if encoding == Encoding::Json {
+        serde_json::from_slice::<BatchRequest>(&data).map_err(ParseRequestError::InvalidRequest)
+    } else {
+        encoding
+            .decode::<BatchRequest>(&data)
+            .map_err(ParseRequestError::InvalidEncodedRequest)
+    }
 }
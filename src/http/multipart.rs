@@ -1,16 +1,18 @@
+use crate::http::{TempFileStorage, UploadInfo, UploadStorage};
 use crate::{BatchRequest, ParseRequestError};
 use bytes::Bytes;
 use futures::io::AsyncRead;
-use futures::stream::Stream;
+use futures::stream::{self, Stream, StreamExt};
 use multer::{Constraints, Multipart, SizeLimit};
 use pin_project_lite::pin_project;
 use std::collections::HashMap;
-use std::io::{self, Seek, SeekFrom, Write};
+use std::io;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
 /// Options for `receive_multipart`.
-#[derive(Default, Clone, Copy)]
+#[derive(Default, Clone)]
 #[non_exhaustive]
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
 pub struct MultipartOptions {
@@ -18,6 +20,10 @@ pub struct MultipartOptions {
     pub max_file_size: Option<usize>,
     /// The maximum number of files.
     pub max_num_files: Option<usize>,
+    /// The maximum number of requests in a batch request, whatever the content type of the body.
+    pub max_batch_size: Option<usize>,
+    /// Where the uploaded files are stored, temporary files by default.
+    pub storage: Option<Arc<dyn UploadStorage>>,
 }
 
 impl MultipartOptions {
@@ -36,6 +42,22 @@ impl MultipartOptions {
             ..self
         }
     }
+
+    /// Set maximum number of requests in a batch request.
+    pub fn max_batch_size(self, n: usize) -> Self {
+        MultipartOptions {
+            max_batch_size: Some(n),
+            ..self
+        }
+    }
+
+    /// Set where the uploaded files are stored.
+    pub fn storage(self, storage: impl UploadStorage) -> Self {
+        MultipartOptions {
+            storage: Some(Arc::new(storage)),
+            ..self
+        }
+    }
 }
 
 pub(super) async fn receive_batch_multipart(
@@ -49,7 +71,7 @@ pub(super) async fn receive_batch_multipart(
         Constraints::new().size_limit({
             let mut limit = SizeLimit::new();
             if let (Some(max_file_size), Some(max_num_files)) =
-                (opts.max_file_size, opts.max_file_size)
+                (opts.max_file_size, opts.max_num_files)
             {
                 limit = limit.whole_stream((max_file_size * max_num_files) as u64);
             }
@@ -60,6 +82,9 @@ pub(super) async fn receive_batch_multipart(
         }),
     );
 
+    let storage = opts
+        .storage
+        .unwrap_or_else(|| Arc::new(TempFileStorage) as Arc<dyn UploadStorage>);
     let mut request = None;
     let mut map = None;
     let mut files = Vec::new();
@@ -83,13 +108,30 @@ pub(super) async fn receive_batch_multipart(
             _ => {
                 if let Some(name) = field.name().map(ToString::to_string) {
                     if let Some(filename) = field.file_name().map(ToString::to_string) {
-                        let content_type = field.content_type().map(|mime| mime.to_string());
-                        let mut file = tempfile::tempfile().map_err(ParseRequestError::Io)?;
-                        while let Some(chunk) = field.chunk().await.unwrap() {
-                            file.write(&chunk).map_err(ParseRequestError::Io)?;
+                        if let Some(max_num_files) = opts.max_num_files {
+                            if files.len() >= max_num_files {
+                                return Err(ParseRequestError::PayloadTooLarge);
+                            }
                         }
-                        file.seek(SeekFrom::Start(0))?;
-                        files.push((name, filename, content_type, file));
+                        let info = UploadInfo {
+                            name,
+                            filename,
+                            content_type: field.content_type().map(|mime| mime.to_string()),
+                        };
+                        let chunks = stream::unfold(&mut field, |field| async move {
+                            match field.chunk().await {
+                                Ok(Some(chunk)) => Some((Ok(chunk), field)),
+                                Ok(None) => None,
+                                Err(err) => {
+                                    Some((Err(io::Error::new(io::ErrorKind::Other, err)), field))
+                                }
+                            }
+                        });
+                        let content = storage
+                            .store(&info, chunks.boxed())
+                            .await
+                            .map_err(store_error)?;
+                        files.push((info, content));
                     }
                 }
             }
@@ -99,16 +141,16 @@ pub(super) async fn receive_batch_multipart(
     let mut request: BatchRequest = request.ok_or(ParseRequestError::MissingOperatorsPart)?;
     let map = map.as_mut().ok_or(ParseRequestError::MissingMapPart)?;
 
-    for (name, filename, content_type, file) in files {
-        if let Some(var_paths) = map.remove(&name) {
+    for (info, content) in files {
+        if let Some(var_paths) = map.remove(&info.name) {
             for var_path in var_paths {
                 match &mut request {
                     BatchRequest::Single(request) => {
                         request.set_upload(
                             &var_path,
-                            filename.clone(),
-                            content_type.clone(),
-                            file.try_clone().unwrap(),
+                            info.filename.clone(),
+                            info.content_type.clone(),
+                            content.try_clone()?,
                         );
                     }
                     BatchRequest::Batch(requests) => {
@@ -120,9 +162,9 @@ pub(super) async fn receive_batch_multipart(
                             if let Some(request) = requests.get_mut(idx) {
                                 request.set_upload(
                                     path,
-                                    filename.clone(),
-                                    content_type.clone(),
-                                    file.try_clone().unwrap(),
+                                    info.filename.clone(),
+                                    info.content_type.clone(),
+                                    content.try_clone()?,
                                 );
                             }
                         }
@@ -139,6 +181,24 @@ pub(super) async fn receive_batch_multipart(
     Ok(request)
 }
 
+/// The errors of the field chunks and of the storages can wrap request errors, such as
+/// `PayloadTooLarge` when a file exceeds the maximum size.
+fn store_error(err: io::Error) -> ParseRequestError {
+    let is_request_error = err.get_ref().map_or(false, |err| {
+        err.is::<ParseRequestError>() || err.is::<multer::Error>()
+    });
+    if !is_request_error {
+        return ParseRequestError::Io(err);
+    }
+    match err.into_inner().unwrap().downcast::<ParseRequestError>() {
+        Ok(err) => *err,
+        Err(err) => match err.downcast::<multer::Error>() {
+            Ok(err) => (*err).into(),
+            Err(err) => ParseRequestError::Io(io::Error::new(io::ErrorKind::Other, err)),
+        },
+    }
+}
+
 pin_project! {
     pub(crate) struct ReaderStream<T> {
         buf: [u8; 2048],
@@ -0,0 +1,169 @@
+use crate::parser::types::UploadContent;
+use crate::ParseRequestError;
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::stream::BoxStream;
+use futures::{Future, Sink, SinkExt, StreamExt};
+use std::io::{self, Seek, SeekFrom, Write};
+use std::sync::Arc;
+
+/// Information about an uploaded file, given to an [`UploadStorage`](trait.UploadStorage.html).
+#[derive(Debug, Clone)]
+pub struct UploadInfo {
+    /// The name of the multipart field.
+    pub name: String,
+    /// The name of the file.
+    pub filename: String,
+    /// The content type of the file.
+    pub content_type: Option<String>,
+}
+
+/// Where the files uploaded with `multipart/form-data` requests are stored.
+///
+/// The default storage is [`TempFileStorage`](struct.TempFileStorage.html).
+#[async_trait::async_trait]
+pub trait UploadStorage: Send + Sync + 'static {
+    /// Store the chunks of an uploaded file, returning the content given to the
+    /// [`Upload`](../struct.Upload.html) values.
+    ///
+    /// To reject the request with a specific [`ParseRequestError`](../enum.ParseRequestError.html),
+    /// such as `PayloadTooLarge`, return an `io::Error` wrapping it.
+    async fn store(
+        &self,
+        info: &UploadInfo,
+        chunks: BoxStream<'_, io::Result<Bytes>>,
+    ) -> io::Result<UploadContent>;
+}
+
+async fn store_temp_file(
+    mut file: std::fs::File,
+    mut chunks: BoxStream<'_, io::Result<Bytes>>,
+) -> io::Result<UploadContent> {
+    while let Some(chunk) = chunks.next().await {
+        file.write_all(&chunk?)?;
+    }
+    file.seek(SeekFrom::Start(0))?;
+    Ok(UploadContent::File(file))
+}
+
+/// Stores the uploaded files in temporary files, which are deleted when they are closed.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TempFileStorage;
+
+#[async_trait::async_trait]
+impl UploadStorage for TempFileStorage {
+    async fn store(
+        &self,
+        _info: &UploadInfo,
+        chunks: BoxStream<'_, io::Result<Bytes>>,
+    ) -> io::Result<UploadContent> {
+        store_temp_file(tempfile::tempfile()?, chunks).await
+    }
+}
+
+/// Stores the uploaded files in memory.
+///
+/// Files larger than the maximum size are rejected with `ParseRequestError::PayloadTooLarge`,
+/// unless they are [moved to temporary files](#method.fallback_to_temp_file).
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryStorage {
+    max_size: usize,
+    fallback_to_temp_file: bool,
+}
+
+impl MemoryStorage {
+    /// Create a storage keeping the files of at most `max_size` bytes in memory.
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            max_size,
+            fallback_to_temp_file: false,
+        }
+    }
+
+    /// Move the files larger than the maximum size to temporary files instead of rejecting them.
+    #[must_use]
+    pub fn fallback_to_temp_file(self) -> Self {
+        Self {
+            fallback_to_temp_file: true,
+            ..self
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UploadStorage for MemoryStorage {
+    async fn store(
+        &self,
+        _info: &UploadInfo,
+        mut chunks: BoxStream<'_, io::Result<Bytes>>,
+    ) -> io::Result<UploadContent> {
+        let mut data = Vec::new();
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk?;
+            if data.len() + chunk.len() > self.max_size {
+                if !self.fallback_to_temp_file {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        ParseRequestError::PayloadTooLarge,
+                    ));
+                }
+                let mut file = tempfile::tempfile()?;
+                file.write_all(&data)?;
+                file.write_all(&chunk)?;
+                return store_temp_file(file, chunks).await;
+            }
+            data.extend_from_slice(&chunk);
+        }
+        Ok(UploadContent::Memory(data.into()))
+    }
+}
+
+type SinkFactory<S> = dyn Fn(UploadInfo) -> BoxFuture<'static, io::Result<S>> + Send + Sync;
+
+/// Streams the uploaded files to sinks, for example to upload them to an object storage service
+/// without writing them to the disk.
+///
+/// A sink is created for each file, and is closed once all the chunks were sent. It is then
+/// available from [`Upload::custom_value`](../struct.Upload.html#method.custom_value), so the
+/// resolvers can find where the file was sent.
+pub struct SinkStorage<S> {
+    factory: Box<SinkFactory<S>>,
+}
+
+impl<S> SinkStorage<S> {
+    /// Create a storage using `factory` to create the sink of each file.
+    pub fn new<F, R>(factory: F) -> Self
+    where
+        F: Fn(UploadInfo) -> R + Send + Sync + 'static,
+        R: Future<Output = io::Result<S>> + Send + 'static,
+    {
+        Self {
+            factory: Box::new(move |info| Box::pin(factory(info))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> UploadStorage for SinkStorage<S>
+where
+    S: Sink<Bytes, Error = io::Error> + Unpin + Send + Sync + 'static,
+{
+    async fn store(
+        &self,
+        info: &UploadInfo,
+        mut chunks: BoxStream<'_, io::Result<Bytes>>,
+    ) -> io::Result<UploadContent> {
+        let mut sink = (self.factory)(info.clone()).await?;
+        let mut size = 0;
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk?;
+            size += chunk.len() as u64;
+            sink.send(chunk).await?;
+        }
+        sink.close().await?;
+        Ok(UploadContent::Custom {
+            size,
+            value: Arc::new(sink),
+        })
+    }
+}
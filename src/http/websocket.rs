@@ -2,23 +2,74 @@
 
 use crate::resolver_utils::ObjectType;
 use crate::{Data, FieldResult, Request, Response, Schema, SubscriptionType};
-use futures::Stream;
+use futures::future::{self, AbortHandle};
+use futures::stream::{self, BoxStream, SelectAll};
+use futures::{pin_mut, Sink, Stream, StreamExt};
 use pin_project_lite::pin_project;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
+/// What a WebSocket connection does when its outbound buffer is full, because the client does not
+/// read the messages as fast as the subscriptions produce them.
+///
+/// Only messages that the sink has not taken yet count, so the policy applies when the connection
+/// is driven with [`WebSocket::run`](struct.WebSocket.html#method.run). A connection consumed as a
+/// `Stream` only produces messages when asked for, so it always waits.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Backpressure {
+    /// Stop polling the subscriptions until the client catches up. This is the default.
+    Wait,
+
+    /// Keep polling the subscriptions, and drop the oldest pending data messages.
+    DropOldest,
+
+    /// Close the connection with an error.
+    Close,
+}
+
+impl Default for Backpressure {
+    fn default() -> Self {
+        Backpressure::Wait
+    }
+}
+
+/// An operation event, `None` when the operation is complete.
+type OperationStream = BoxStream<'static, (String, Option<Response>)>;
+
+enum OutboundMessage {
+    Data(String),
+    Control(String),
+}
+
+impl OutboundMessage {
+    fn into_string(self) -> String {
+        match self {
+            OutboundMessage::Data(message) | OutboundMessage::Control(message) => message,
+        }
+    }
+}
+
 pin_project! {
     /// A GraphQL connection over websocket.
     ///
+    /// The operations of a connection are polled fairly: only the operations that were woken up
+    /// are polled, and an operation producing many events cannot starve the others.
+    ///
     /// [Reference](https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md).
     pub struct WebSocket<S, F, Query, Mutation, Subscription> {
         data_initializer: Option<F>,
         data: Arc<Data>,
         schema: Schema<Query, Mutation, Subscription>,
-        streams: HashMap<String, Pin<Box<dyn Stream<Item = Response> + Send>>>,
+        operations: SelectAll<OperationStream>,
+        abort_handles: HashMap<String, AbortHandle>,
+        outbound: VecDeque<OutboundMessage>,
+        max_operations: Option<usize>,
+        buffer_size: usize,
+        backpressure: Backpressure,
+        closed: bool,
         #[pin]
         stream: S,
     }
@@ -30,13 +81,7 @@ impl<S, Query, Mutation, Subscription>
     /// Create a new websocket.
     #[must_use]
     pub fn new(schema: Schema<Query, Mutation, Subscription>, stream: S) -> Self {
-        Self {
-            data_initializer: None,
-            data: Arc::default(),
-            schema,
-            streams: HashMap::new(),
-            stream,
-        }
+        Self::with_data(schema, stream, None)
     }
 }
 
@@ -56,13 +101,50 @@ impl<S, F, Query, Mutation, Subscription> WebSocket<S, F, Query, Mutation, Subsc
             data_initializer,
             data: Arc::default(),
             schema,
-            streams: HashMap::new(),
+            operations: SelectAll::new(),
+            abort_handles: HashMap::new(),
+            outbound: VecDeque::new(),
+            max_operations: None,
+            buffer_size: 16,
+            backpressure: Backpressure::default(),
+            closed: false,
             stream,
         }
     }
+
+    /// Set the maximum number of concurrent operations of the connection. By default there is no
+    /// limit.
+    ///
+    /// Operations started over this limit fail with an error message.
+    #[must_use]
+    pub fn max_operations(mut self, max_operations: usize) -> Self {
+        self.max_operations = Some(max_operations);
+        self
+    }
+
+    /// Set the number of outbound messages buffered before applying the backpressure policy,
+    /// default is `16`.
+    ///
+    /// Client messages are not read while the buffer is full.
+    #[must_use]
+    pub fn buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size.max(1);
+        self
+    }
+
+    /// Set what happens when the outbound buffer is full, default is `Backpressure::Wait`.
+    #[must_use]
+    pub fn backpressure(mut self, backpressure: Backpressure) -> Self {
+        self.backpressure = backpressure;
+        self
+    }
 }
 
-impl<S, F, Query, Mutation, Subscription> Stream for WebSocket<S, F, Query, Mutation, Subscription>
+fn control_message(message: &ServerMessage<'_>) -> OutboundMessage {
+    OutboundMessage::Control(serde_json::to_string(message).unwrap())
+}
+
+impl<S, F, Query, Mutation, Subscription> WebSocket<S, F, Query, Mutation, Subscription>
 where
     S: Stream,
     S::Item: AsRef<[u8]>,
@@ -71,107 +153,266 @@ where
     Mutation: ObjectType + Send + Sync + 'static,
     Subscription: SubscriptionType + Send + Sync + 'static,
 {
-    type Item = String;
+    /// Send the messages of the connection to a sink until the connection is closed.
+    ///
+    /// Unlike forwarding the connection as a `Stream`, this keeps polling the operations while
+    /// the sink is not ready, so the [backpressure policy](#method.backpressure) applies to
+    /// clients that do not read their messages. As a `Stream`, the connection only produces
+    /// messages when its consumer asks for them, so it always waits.
+    pub async fn run<Si>(self, sink: Si) -> Result<(), Si::Error>
+    where
+        Si: Sink<String>,
+    {
+        let websocket = self;
+        pin_mut!(websocket, sink);
 
-    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
-        let this = self.project();
+        future::poll_fn(|cx| {
+            let mut sent = 0;
+            loop {
+                match sink.as_mut().poll_ready(cx) {
+                    Poll::Ready(Ok(())) => {}
+                    Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                    Poll::Pending => {
+                        websocket.as_mut().poll_outbound(cx, true);
+                        return Poll::Pending;
+                    }
+                }
 
-        match this.stream.poll_next(cx) {
-            Poll::Ready(message) => {
-                let message = match message {
-                    Some(message) => message,
-                    None => return Poll::Ready(None),
-                };
-
-                let message: ClientMessage = match serde_json::from_slice(message.as_ref()) {
-                    Ok(message) => message,
-                    Err(e) => {
-                        return Poll::Ready(Some(
-                            serde_json::to_string(&ServerMessage::ConnectionError {
-                                payload: ConnectionError {
-                                    message: e.to_string(),
-                                    extensions: None,
-                                },
-                            })
-                            .unwrap(),
-                        ))
+                if sent == websocket.buffer_size {
+                    // Give the other tasks a chance to run.
+                    cx.waker().wake_by_ref();
+                    if let Poll::Ready(Err(err)) = sink.as_mut().poll_flush(cx) {
+                        return Poll::Ready(Err(err));
                     }
-                };
-
-                match message {
-                    ClientMessage::ConnectionInit { payload } => {
-                        if let Some(payload) = payload {
-                            if let Some(data_initializer) = this.data_initializer.take() {
-                                *this.data = Arc::new(match data_initializer(payload) {
-                                    Ok(data) => data,
-                                    Err(e) => {
-                                        return Poll::Ready(Some(
-                                            serde_json::to_string(
-                                                &ServerMessage::ConnectionError {
-                                                    payload: ConnectionError {
-                                                        message: e.0,
-                                                        extensions: e.1,
-                                                    },
-                                                },
-                                            )
-                                            .unwrap(),
-                                        ))
-                                    }
-                                });
+                    return Poll::Pending;
+                }
+
+                websocket.as_mut().poll_outbound(cx, false);
+                let this = websocket.as_mut().project();
+                match this.outbound.pop_front() {
+                    Some(message) => {
+                        sink.as_mut().start_send(message.into_string())?;
+                        sent += 1;
+                    }
+                    None if *this.closed => return sink.as_mut().poll_close(cx),
+                    None => {
+                        if let Poll::Ready(Err(err)) = sink.as_mut().poll_flush(cx) {
+                            return Poll::Ready(Err(err));
+                        }
+                        return Poll::Pending;
+                    }
+                }
+            }
+        })
+        .await
+    }
+
+    /// Read the client messages and poll the operations, queueing the messages to send.
+    ///
+    /// At most `buffer_size` messages are queued. Only when the consumer is `stalled` does the
+    /// backpressure policy decide whether to keep polling the operations.
+    fn poll_outbound(self: Pin<&mut Self>, cx: &mut Context, stalled: bool) {
+        let mut this = self.project();
+        let buffer_size = *this.buffer_size;
+
+        // Client messages are only read while there is room for a reply.
+        let mut read = 0;
+        while !*this.closed && this.outbound.len() < buffer_size {
+            if read == buffer_size {
+                cx.waker().wake_by_ref();
+                break;
+            }
+
+            let message = match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(message)) => message,
+                Poll::Ready(None) => {
+                    Self::terminate(this.abort_handles, this.outbound, this.closed);
+                    return;
+                }
+                Poll::Pending => break,
+            };
+            read += 1;
+
+            let message: ClientMessage = match serde_json::from_slice(message.as_ref()) {
+                Ok(message) => message,
+                Err(e) => {
+                    this.outbound
+                        .push_back(control_message(&ServerMessage::ConnectionError {
+                            payload: ConnectionError {
+                                message: e.to_string(),
+                                extensions: None,
+                            },
+                        }));
+                    continue;
+                }
+            };
+
+            match message {
+                ClientMessage::ConnectionInit { payload } => {
+                    if let Some(payload) = payload {
+                        if let Some(data_initializer) = this.data_initializer.take() {
+                            match data_initializer(payload) {
+                                Ok(data) => *this.data = Arc::new(data),
+                                Err(e) => {
+                                    this.outbound.push_back(control_message(
+                                        &ServerMessage::ConnectionError {
+                                            payload: ConnectionError {
+                                                message: e.message,
+                                                extensions: e.extensions,
+                                            },
+                                        },
+                                    ));
+                                    continue;
+                                }
                             }
                         }
-                        return Poll::Ready(Some(
-                            serde_json::to_string(&ServerMessage::ConnectionAck).unwrap(),
-                        ));
                     }
-                    ClientMessage::Start {
-                        id,
-                        payload: request,
-                    } => {
-                        this.streams.insert(
-                            id,
-                            Box::pin(
-                                this.schema
-                                    .execute_stream_with_ctx_data(request, Arc::clone(this.data)),
-                            ),
-                        );
+                    this.outbound
+                        .push_back(control_message(&ServerMessage::ConnectionAck));
+                }
+                ClientMessage::Start {
+                    id,
+                    payload: request,
+                } => {
+                    if let Some(handle) = this.abort_handles.remove(&id) {
+                        handle.abort();
                     }
-                    ClientMessage::Stop { id } => {
-                        if this.streams.remove(id).is_some() {
-                            return Poll::Ready(Some(
-                                serde_json::to_string(&ServerMessage::Complete { id }).unwrap(),
-                            ));
+                    if let Some(max_operations) = *this.max_operations {
+                        if this.abort_handles.len() >= max_operations {
+                            this.outbound
+                                .push_back(control_message(&ServerMessage::Error {
+                                    id: &id,
+                                    payload: ConnectionError {
+                                        message: format!(
+                                            "Too many operations, the limit is {}.",
+                                            max_operations
+                                        ),
+                                        extensions: None,
+                                    },
+                                }));
+                            continue;
                         }
                     }
-                    ClientMessage::ConnectionTerminate => return Poll::Ready(None),
+
+                    let (operation, handle) = stream::abortable(
+                        this.schema
+                            .execute_stream_with_ctx_data(request, Arc::clone(this.data))
+                            .map(Some)
+                            .chain(stream::once(future::ready(None)))
+                            .map({
+                                let id = id.clone();
+                                move |payload| (id.clone(), payload)
+                            }),
+                    );
+                    this.operations.push(operation.boxed());
+                    this.abort_handles.insert(id, handle);
+                }
+                ClientMessage::Stop { id } => {
+                    if let Some(handle) = this.abort_handles.remove(id) {
+                        handle.abort();
+                        this.outbound
+                            .push_back(control_message(&ServerMessage::Complete { id }));
+                    }
+                }
+                ClientMessage::ConnectionTerminate => {
+                    Self::terminate(this.abort_handles, this.outbound, this.closed);
+                    return;
                 }
             }
-            Poll::Pending => {}
         }
 
-        for (id, stream) in &mut *this.streams {
-            match Pin::new(stream).poll_next(cx) {
-                Poll::Ready(Some(payload)) => {
-                    return Poll::Ready(Some(
-                        serde_json::to_string(&ServerMessage::Data {
-                            id,
-                            payload: Box::new(payload),
-                        })
-                        .unwrap(),
-                    ));
+        // Only the operations that were woken up are polled, in the order they were woken up.
+        let overflow = stalled && *this.backpressure != Backpressure::Wait;
+        let mut polled = 0;
+        while !*this.closed && polled < buffer_size {
+            if this.outbound.len() >= buffer_size && !overflow {
+                break;
+            }
+
+            let (id, payload) = match this.operations.poll_next_unpin(cx) {
+                Poll::Ready(Some(item)) => item,
+                Poll::Ready(None) | Poll::Pending => break,
+            };
+            polled += 1;
+
+            match payload {
+                Some(payload) => this.outbound.push_back(OutboundMessage::Data(
+                    serde_json::to_string(&ServerMessage::Data {
+                        id: &id,
+                        payload: Box::new(payload),
+                    })
+                    .unwrap(),
+                )),
+                None => {
+                    this.abort_handles.remove(&id);
+                    this.outbound
+                        .push_back(control_message(&ServerMessage::Complete { id: &id }));
                 }
-                Poll::Ready(None) => {
-                    let id = id.clone();
-                    this.streams.remove(&id);
-                    return Poll::Ready(Some(
-                        serde_json::to_string(&ServerMessage::Complete { id: &id }).unwrap(),
-                    ));
+            }
+
+            if this.outbound.len() > buffer_size {
+                match *this.backpressure {
+                    Backpressure::Wait => {}
+                    Backpressure::DropOldest => {
+                        if let Some(idx) = this
+                            .outbound
+                            .iter()
+                            .position(|message| matches!(message, OutboundMessage::Data(_)))
+                        {
+                            this.outbound.remove(idx);
+                        }
+                    }
+                    Backpressure::Close => {
+                        Self::terminate(this.abort_handles, this.outbound, this.closed);
+                        this.outbound
+                            .push_back(control_message(&ServerMessage::ConnectionError {
+                                payload: ConnectionError {
+                                    message: "The client is too slow to receive the messages."
+                                        .to_string(),
+                                    extensions: None,
+                                },
+                            }));
+                    }
                 }
-                Poll::Pending => {}
             }
         }
+        if polled == buffer_size {
+            cx.waker().wake_by_ref();
+        }
+    }
+
+    fn terminate(
+        abort_handles: &mut HashMap<String, AbortHandle>,
+        outbound: &mut VecDeque<OutboundMessage>,
+        closed: &mut bool,
+    ) {
+        for (_, handle) in abort_handles.drain() {
+            handle.abort();
+        }
+        outbound.clear();
+        *closed = true;
+    }
+}
+
+impl<S, F, Query, Mutation, Subscription> Stream for WebSocket<S, F, Query, Mutation, Subscription>
+where
+    S: Stream,
+    S::Item: AsRef<[u8]>,
+    F: FnOnce(serde_json::Value) -> FieldResult<Data>,
+    Query: ObjectType + Send + Sync + 'static,
+    Mutation: ObjectType + Send + Sync + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+{
+    type Item = String;
 
-        Poll::Pending
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.as_mut().poll_outbound(cx, false);
+        let this = self.project();
+        match this.outbound.pop_front() {
+            Some(message) => Poll::Ready(Some(message.into_string())),
+            None if *this.closed => Poll::Ready(None),
+            None => Poll::Pending,
+        }
     }
 }
 
@@ -187,15 +428,21 @@ enum ClientMessage<'a> {
 #[derive(Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum ServerMessage<'a> {
-    ConnectionError { payload: ConnectionError },
+    ConnectionError {
+        payload: ConnectionError,
+    },
     ConnectionAck,
-    Data { id: &'a str, payload: Box<Response> },
-    // Not used by this library, as it's not necessary to send
-    // Error {
-    //     id: &'a str,
-    //     payload: serde_json::Value,
-    // },
-    Complete { id: &'a str },
+    Data {
+        id: &'a str,
+        payload: Box<Response>,
+    },
+    Error {
+        id: &'a str,
+        payload: ConnectionError,
+    },
+    Complete {
+        id: &'a str,
+    },
     // Not used by this library
     // #[serde(rename = "ka")]
     // KeepAlive
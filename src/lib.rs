@@ -64,6 +64,12 @@
 //! - `url`: Integrate with the [`url` crate](https://crates.io/crates/url).
 //! - `uuid`: Integrate with the [`uuid` crate](https://crates.io/crates/uuid).
 //!
+//! The following features are not activated by default:
+//!
+//! - `signed_cursor`: Support [HMAC-signed connection cursors](connection/struct.SignedCursor.html).
+//! - `cbor`: Support [CBOR-encoded requests and responses](http/enum.Encoding.html).
+//! - `msgpack`: Support [MessagePack-encoded requests and responses](http/enum.Encoding.html).
+//!
 //! ## Integrations
 //!
 //! * Actix-web [async-graphql-actix_web](https://crates.io/crates/async-graphql-actix-web)
@@ -124,13 +130,14 @@ mod response;
 mod schema;
 mod serialize_resp;
 mod subscription;
-mod validation;
 
+pub mod broker;
 pub mod extensions;
 pub mod guard;
 pub mod http;
 pub mod resolver_utils;
 pub mod types;
+pub mod validation;
 pub mod validators;
 
 #[doc(hidden)]
@@ -161,15 +168,17 @@ pub use context::{
     Context, ContextBase, Data, QueryEnv, QueryPathNode, QueryPathSegment, ResolveId, Variables,
 };
 pub use error::{
-    Error, ErrorExtensions, FieldError, FieldResult, InputValueError, InputValueResult,
-    ParseRequestError, QueryError, ResultExt, RuleError,
+    Error, ErrorExtensions, ErrorSource, FieldError, FieldResult, InputValueError,
+    InputValueResult, ParseRequestError, QueryError, ResultExt, RuleError, SchemaError,
+    SchemaErrors,
 };
-pub use look_ahead::Lookahead;
+pub use look_ahead::{Lookahead, SelectionField};
 pub use parser::types::{ConstValue as Value, Number};
-pub use registry::CacheControl;
+pub use registry::{CacheControl, RenameRule};
 pub use request::{BatchRequest, Request};
 pub use response::{BatchResponse, Response};
 pub use schema::{Schema, SchemaBuilder, SchemaEnv};
+pub use subscription::SubscriptionErrorPolicy;
 pub use validation::ValidationMode;
 
 #[doc(no_inline)]
@@ -197,6 +206,9 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// | desc          | Object description        | string   | Y        |
 /// | cache_control | Object cache control      | [`CacheControl`](struct.CacheControl.html) | Y        |
 /// | extends       | Add fields to an entity that's defined in another service | bool | Y |
+/// | node          | Implement the Relay `Node` interface, loading objects with `loader`, an async function taking the `Context` and the id within the type, and returning `FieldResult<Option<Self>>`. The object must have an `id` field of type [`GlobalId`](node/struct.GlobalId.html). | `loader = "path"` | Y |
+/// | rename_fields | Rename all the fields according to the given case convention. The possible values are "lowercase", "camelCase", "PascalCase", "snake_case", "SCREAMING_SNAKE_CASE". | string | Y |
+/// | rename_args   | Rename all the arguments according to the given case convention. The possible values are "lowercase", "camelCase", "PascalCase", "snake_case", "SCREAMING_SNAKE_CASE". | string | Y |
 ///
 /// # Field parameters
 ///
@@ -210,6 +222,8 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// | provides      | Annotate the expected returned fieldset from a field on a base type that is guaranteed to be selectable by the gateway. | string | Y |
 /// | requires      | Annotate the required input fieldset from a base type for a resolver. It is used to develop a query plan where the required fields may not be needed by the client, but the service may need additional information from other services. | string | Y |
 /// | guard         | Field of guard            | [`Guard`](guard/trait.Guard.html) | Y        |
+/// | timeout       | Maximum time the resolver may take, such as `"500ms"`, `"5s"`, `"1m"` or `"1h"` | string | Y |
+/// | secret        | Mark the value of the field as sensitive | bool | Y |
 ///
 /// # Field argument parameters
 ///
@@ -221,6 +235,8 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// | default      | Argument default value                   | literal     | Y        |
 /// | default_with | Expression to generate default value     | code string | Y        |
 /// | validator    | Input value validator                    | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
+/// | deprecation  | Argument deprecation reason              | string      | Y        |
+/// | secret       | Mask the value in logs and traces        | bool        | Y        |
 ///
 /// # Valid field return types
 ///
@@ -309,6 +325,10 @@ pub use async_graphql_derive::Object;
 /// | name          | Object name               | string   | Y        |
 /// | desc          | Object description        | string   | Y        |
 /// | cache_control | Object cache control      | [`CacheControl`](struct.CacheControl.html) | Y        |
+/// | node          | Implement the Relay `Node` interface, loading objects with `loader`, an async function taking the `Context` and the id within the type, and returning `FieldResult<Option<Self>>`. The object must have an `id` field of type [`GlobalId`](node/struct.GlobalId.html). | `loader = "path"` | Y |
+/// | complex       | Add the computed fields defined by a [`ComplexObject`](attr.ComplexObject.html) impl block | bool | Y |
+/// | rename_fields | Rename all the fields according to the given case convention. The possible values are "lowercase", "camelCase", "PascalCase", "snake_case", "SCREAMING_SNAKE_CASE". | string | Y |
+/// | concrete      | Register a concrete instantiation of a generic struct with the given name, e.g. `concrete(name = "IntPage", params(i32))`. Can be repeated. | `name = "...", params(...)` | Y |
 ///
 /// # Field parameters
 ///
@@ -318,11 +338,13 @@ pub use async_graphql_derive::Object;
 /// | desc          | Field description         | string   | Y        |
 /// | deprecation   | Field deprecation reason  | string   | Y        |
 /// | owned         | Field resolver return a ownedship value  | bool   | Y        |
+/// | secret        | Mark the value of the field as sensitive | bool   | Y        |
 /// | cache_control | Field cache control       | [`CacheControl`](struct.CacheControl.html) | Y        |
 /// | external      | Mark a field as owned by another service. This allows service A to use fields from service B while also knowing at runtime the types of that field. | bool | Y |
 /// | provides      | Annotate the expected returned fieldset from a field on a base type that is guaranteed to be selectable by the gateway. | string | Y |
 /// | requires      | Annotate the required input fieldset from a base type for a resolver. It is used to develop a query plan where the required fields may not be needed by the client, but the service may need additional information from other services. | string | Y |
 /// | guard         | Field of guard            | [`Guard`](guard/trait.Guard.html) | Y        |
+/// | output        | Parameters that only apply to the output side of a type that also derives `InputObject` | list | Y |
 ///
 /// # Examples
 ///
@@ -344,6 +366,51 @@ pub use async_graphql_derive::Object;
 /// ```
 pub use async_graphql_derive::SimpleObject;
 
+/// Define computed fields for a `SimpleObject`
+///
+/// The methods of the impl block are resolvers, like those of an [`Object`](attr.Object.html), and
+/// take the same field and argument parameters. They are added to the object type of a
+/// [`SimpleObject`](derive.SimpleObject.html) declared with `#[graphql(complex)]`. The macro
+/// accepts the `rename_fields` and `rename_args` parameters of an `Object`.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// #[derive(SimpleObject)]
+/// #[graphql(complex)]
+/// struct MyObj {
+///     a: i32,
+///     b: i32,
+/// }
+///
+/// #[ComplexObject]
+/// impl MyObj {
+///     async fn sum(&self, #[arg(default = 0)] c: i32) -> i32 {
+///         self.a + self.b + c
+///     }
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn obj(&self) -> MyObj {
+///         MyObj { a: 10, b: 20 }
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+///     let res = schema.execute("{ obj { a b sum sum2: sum(c: 5) } }").await.into_result().unwrap().data;
+///     assert_eq!(res, serde_json::json!({
+///         "obj": { "a": 10, "b": 20, "sum": 30, "sum2": 35 },
+///     }));
+/// });
+/// ```
+pub use async_graphql_derive::ComplexObject;
+
 /// Define a GraphQL enum
 ///
 /// *[See also the Book](https://async-graphql.github.io/async-graphql/en/define_enum.html).*
@@ -355,6 +422,7 @@ pub use async_graphql_derive::SimpleObject;
 /// | name        | Enum name                 | string   | Y        |
 /// | desc        | Enum description          | string   | Y        |
 /// | remote      | Derive a remote enum      | string   | Y        |
+/// | rename_items | Rename all the items according to the given case convention. The possible values are "lowercase", "camelCase", "PascalCase", "snake_case", "SCREAMING_SNAKE_CASE". | string | Y |
 ///
 /// # Item parameters
 ///
@@ -410,7 +478,10 @@ pub use async_graphql_derive::Enum;
 /// | Attribute   | description               | Type     | Optional |
 /// |-------------|---------------------------|----------|----------|
 /// | name        | Object name               | string   | Y        |
+/// | input_name  | Name of the input object, for a type that also derives an output object such as `SimpleObject` | string | Y |
 /// | desc        | Object description        | string   | Y        |
+/// | rename_fields | Rename all the fields according to the given case convention. The possible values are "lowercase", "camelCase", "PascalCase", "snake_case", "SCREAMING_SNAKE_CASE". | string | Y |
+/// | concrete      | Register a concrete instantiation of a generic struct with the given name, e.g. `concrete(name = "IntRange", params(i32))`. When the struct also derives `SimpleObject`, `input_name` gives the name of the input object. Can be repeated. | `name = "...", input_name = "...", params(...)` | Y |
 ///
 /// # Field parameters
 ///
@@ -418,11 +489,14 @@ pub use async_graphql_derive::Enum;
 /// |--------------|------------------------------------------|----------|----------|
 /// | name         | Field name                               | string   | Y        |
 /// | desc         | Field description                        | string   | Y        |
+/// | input        | Parameters that only apply to the input side of a type that also derives an output object | list | Y |
 /// | default      | Use `Default::default` for default value | none        | Y        |
 /// | default      | Argument default value                   | literal     | Y        |
 /// | default_with | Expression to generate default value     | code string | Y        |
 /// | validator    | Input value validator                    | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
 /// | flatten      | Similar to serde (flatten)               | boolean | Y |
+/// | deprecation  | Field deprecation reason                 | string      | Y        |
+/// | secret       | Mask the value in logs and traces        | bool     | Y        |
 ///
 /// # Examples
 ///
@@ -458,6 +532,68 @@ pub use async_graphql_derive::Enum;
 /// ```
 pub use async_graphql_derive::InputObject;
 
+/// Define a GraphQL oneof input object
+///
+/// Each variant of the enum must contain exactly one value, and becomes a nullable field of the input
+/// object. The input object is marked with the `@oneOf` directive, and exactly one of its fields
+/// must be provided and non-null.
+///
+/// # Macro parameters
+///
+/// | Attribute   | description               | Type     | Optional |
+/// |-------------|---------------------------|----------|----------|
+/// | name        | Object name               | string   | Y        |
+/// | desc        | Object description        | string   | Y        |
+/// | rename_fields | Rename all the fields according to the given case convention. The possible values are "lowercase", "camelCase", "PascalCase", "snake_case", "SCREAMING_SNAKE_CASE". | string | Y |
+///
+/// # Item parameters
+///
+/// | Attribute    | description                              | Type     | Optional |
+/// |--------------|------------------------------------------|----------|----------|
+/// | name         | Field name                               | string   | Y        |
+/// | desc         | Field description                        | string   | Y        |
+/// | validator    | Input value validator                    | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
+/// | deprecation  | Field deprecation reason                 | string   | Y        |
+/// | secret       | Mask the value in logs and traces        | bool     | Y        |
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// #[derive(OneofObject)]
+/// enum UserBy {
+///     Id(ID),
+///     Email(String),
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn user(&self, by: UserBy) -> String {
+///         match by {
+///             UserBy::Id(id) => format!("id:{}", *id),
+///             UserBy::Email(email) => format!("email:{}", email),
+///         }
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+///     let res = schema.execute(r#"
+///     {
+///         a: user(by: { id: "1" })
+///         b: user(by: { email: "a@example.com" })
+///     }"#).await.into_result().unwrap().data;
+///     assert_eq!(res, serde_json::json!({ "a": "id:1", "b": "email:a@example.com" }));
+///
+///     assert!(schema.execute(r#"{ user(by: { id: "1", email: "a@example.com" }) }"#)
+///         .await.is_err());
+/// });
+/// ```
+pub use async_graphql_derive::OneofObject;
+
 /// Define a GraphQL interface
 ///
 /// *[See also the Book](https://async-graphql.github.io/async-graphql/en/define_interface.html).*
@@ -468,6 +604,9 @@ pub use async_graphql_derive::InputObject;
 /// |-------------|---------------------------|----------|----------|
 /// | name        | Object name               | string   | Y        |
 /// | desc        | Object description        | string   | Y        |
+/// | implements  | Interface implemented by this interface, can be repeated | string | Y |
+/// | rename_fields | Rename all the fields according to the given case convention. The possible values are "lowercase", "camelCase", "PascalCase", "snake_case", "SCREAMING_SNAKE_CASE". | string | Y |
+/// | rename_args | Rename all the arguments according to the given case convention. The possible values are "lowercase", "camelCase", "PascalCase", "snake_case", "SCREAMING_SNAKE_CASE". | string | Y |
 ///
 /// # Field parameters
 ///
@@ -490,6 +629,8 @@ pub use async_graphql_derive::InputObject;
 /// | default      | Use `Default::default` for default value | none        | Y        |
 /// | default      | Argument default value                   | literal     | Y        |
 /// | default_with | Expression to generate default value     | code string | Y        |
+/// | deprecation  | Argument deprecation reason              | string      | Y        |
+/// | secret       | Mask the value in logs and traces        | bool        | Y        |
 ///
 /// # Define an interface
 ///
@@ -673,6 +814,8 @@ pub use async_graphql_derive::Union;
 /// |-------------|---------------------------|----------|----------|
 /// | name        | Object name               | string   | Y        |
 /// | desc        | Object description        | string   | Y        |
+/// | rename_fields | Rename all the fields according to the given case convention. The possible values are "lowercase", "camelCase", "PascalCase", "snake_case", "SCREAMING_SNAKE_CASE". | string | Y |
+/// | rename_args | Rename all the arguments according to the given case convention. The possible values are "lowercase", "camelCase", "PascalCase", "snake_case", "SCREAMING_SNAKE_CASE". | string | Y |
 ///
 /// # Field parameters
 ///
@@ -682,6 +825,7 @@ pub use async_graphql_derive::Union;
 /// | desc        | Field description         | string   | Y        |
 /// | deprecation | Field deprecation reason  | string   | Y        |
 /// | guard         | Field of guard            | [`Guard`](guard/trait.Guard.html) | Y        |
+/// | error_policy | What happens to the subscription when an event fails, `"terminate"`, `"skip"` or `"emit"` | string | Y |
 ///
 /// # Field argument parameters
 ///
@@ -693,6 +837,8 @@ pub use async_graphql_derive::Union;
 /// | default      | Argument default value                   | literal     | Y        |
 /// | default_with | Expression to generate default value     | code string | Y        |
 /// | validator    | Input value validator                    | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
+/// | deprecation  | Argument deprecation reason              | string      | Y        |
+/// | secret       | Mask the value in logs and traces        | bool        | Y        |
 ///
 /// # Examples
 ///
@@ -720,6 +866,7 @@ pub use async_graphql_derive::Subscription;
 /// |-------------|---------------------------|----------|----------|
 /// | name        | Scalar name               | string   | Y        |
 /// | desc        | Scalar description        | string   | Y        |
+/// | specified_by_url | URL of the specification of this scalar | string | Y |
 ///
 pub use async_graphql_derive::Scalar;
 
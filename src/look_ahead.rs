@@ -1,68 +1,204 @@
-use crate::parser::types::{Field, FragmentDefinition, Name, Selection, SelectionSet};
-use crate::Positioned;
-use std::collections::HashMap;
+use crate::context::QueryEnvInner;
+use crate::parser::types::{Field, Selection, SelectionSet};
+use crate::registry::Registry;
+use crate::{InputValueType, Pos, Result, Value};
 
 /// A selection performed by a query.
 pub struct Lookahead<'a> {
-    fragments: &'a HashMap<Name, Positioned<FragmentDefinition>>,
-    field: Option<&'a Field>,
+    registry: &'a Registry,
+    query_env: &'a QueryEnvInner,
+    fields: Vec<&'a Field>,
+    type_name: Option<String>,
 }
 
 impl<'a> Lookahead<'a> {
     pub(crate) fn new(
-        fragments: &'a HashMap<Name, Positioned<FragmentDefinition>>,
+        registry: &'a Registry,
+        query_env: &'a QueryEnvInner,
         field: &'a Field,
     ) -> Self {
         Self {
-            fragments,
-            field: Some(field),
+            registry,
+            query_env,
+            fields: vec![field],
+            type_name: None,
         }
     }
 
-    /// Get the first subfield of the selection set with the specified name. This will ignore
-    /// aliases.
+    /// Get the subfields of the selection set with the specified name. This will ignore
+    /// aliases, so if the field is selected several times the lookahead represents all of them.
     ///
     /// For example, calling `.field("a")` on `{ a { b } }` will return a lookahead that
     /// represents `{ b }`.
     pub fn field(&self, name: &str) -> Self {
         Self {
-            fragments: self.fragments,
-            field: self
-                .field
-                .and_then(|field| find(self.fragments, &field.selection_set.node, name)),
+            registry: self.registry,
+            query_env: self.query_env,
+            fields: self
+                .selection_fields()
+                .into_iter()
+                .filter(|field| field.name() == name)
+                .map(|field| field.field)
+                .collect(),
+            type_name: None,
         }
     }
 
     /// Returns true if field exists otherwise return false.
     #[inline]
     pub fn exists(&self) -> bool {
-        self.field.is_some()
+        !self.fields.is_empty()
     }
-}
 
-fn find<'a>(
-    fragments: &'a HashMap<Name, Positioned<FragmentDefinition>>,
-    selection_set: &'a SelectionSet,
-    name: &str,
-) -> Option<&'a Field> {
-    selection_set
-        .items
-        .iter()
-        .find_map(|item| match &item.node {
-            Selection::Field(field) => {
-                if field.node.name.node == name {
-                    Some(&field.node)
-                } else {
-                    None
+    /// Only consider the selections that apply to the object type with the specified name.
+    ///
+    /// Fields of fragments with a type condition that doesn't include this type are ignored. This
+    /// is useful when the field returns an interface or a union.
+    ///
+    /// For example, calling `.on_type("Human")` on
+    /// `{ name ... on Human { height } ... on Droid { primaryFunction } }` will return a
+    /// lookahead that represents `{ name height }`.
+    pub fn on_type(&self, type_name: &str) -> Self {
+        Self {
+            registry: self.registry,
+            query_env: self.query_env,
+            fields: self.fields.clone(),
+            type_name: Some(type_name.to_string()),
+        }
+    }
+
+    /// Get the fields directly selected in the selection set, in query order.
+    ///
+    /// Fragments are expanded, and fields excluded with `@skip` or `@include` are left out. A
+    /// field selected several times, for example with different aliases or arguments, is
+    /// returned once for each selection.
+    pub fn selection_fields(&self) -> Vec<SelectionField<'a>> {
+        let mut fields = Vec::new();
+        for field in &self.fields {
+            self.collect_fields(&field.selection_set.node, &mut fields);
+        }
+        fields
+    }
+
+    fn collect_fields(
+        &self,
+        selection_set: &'a SelectionSet,
+        fields: &mut Vec<SelectionField<'a>>,
+    ) {
+        for selection in &selection_set.items {
+            if self
+                .query_env
+                .is_skip(selection.node.directives())
+                .unwrap_or_default()
+            {
+                continue;
+            }
+
+            match &selection.node {
+                Selection::Field(field) => fields.push(SelectionField {
+                    registry: self.registry,
+                    query_env: self.query_env,
+                    field: &field.node,
+                }),
+                Selection::InlineFragment(fragment) => {
+                    let applies = match &fragment.node.type_condition {
+                        Some(type_condition) => {
+                            self.type_condition_applies(&type_condition.node.on.node)
+                        }
+                        None => true,
+                    };
+                    if applies {
+                        self.collect_fields(&fragment.node.selection_set.node, fields);
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    if let Some(fragment) = self
+                        .query_env
+                        .fragments
+                        .get(&spread.node.fragment_name.node)
+                    {
+                        if self.type_condition_applies(&fragment.node.type_condition.node.on.node) {
+                            self.collect_fields(&fragment.node.selection_set.node, fields);
+                        }
+                    }
                 }
             }
-            Selection::InlineFragment(fragment) => {
-                find(fragments, &fragment.node.selection_set.node, name)
+        }
+    }
+
+    fn type_condition_applies(&self, type_condition: &str) -> bool {
+        match &self.type_name {
+            Some(type_name) => {
+                type_condition == type_name
+                    || self
+                        .registry
+                        .types
+                        .get(type_condition)
+                        .map(|ty| ty.is_possible_type(type_name))
+                        .unwrap_or_default()
             }
-            Selection::FragmentSpread(spread) => fragments
-                .get(&spread.node.fragment_name.node)
-                .and_then(|fragment| find(fragments, &fragment.node.selection_set.node, name)),
-        })
+            None => true,
+        }
+    }
+}
+
+/// A field selected by a query, returned by
+/// [`Lookahead::selection_fields`](struct.Lookahead.html#method.selection_fields).
+pub struct SelectionField<'a> {
+    registry: &'a Registry,
+    query_env: &'a QueryEnvInner,
+    field: &'a Field,
+}
+
+impl<'a> SelectionField<'a> {
+    /// The name of the field.
+    pub fn name(&self) -> &'a str {
+        &self.field.name.node
+    }
+
+    /// The alias of the field, if it has one.
+    pub fn alias(&self) -> Option<&'a str> {
+        self.field.alias.as_ref().map(|alias| alias.node.as_str())
+    }
+
+    /// The key of the field in the response. This is the alias if present and the name otherwise.
+    pub fn response_key(&self) -> &'a str {
+        &self.field.response_key().node
+    }
+
+    /// The arguments given to the field, in query order, with the values of variables
+    /// substituted. Default values of arguments that are not given are not included.
+    pub fn arguments(&self) -> Result<Vec<(&'a str, Value)>> {
+        self.field
+            .arguments
+            .iter()
+            .map(|(name, value)| {
+                Ok((
+                    name.node.as_str(),
+                    self.query_env.resolve_input_value(value.clone())?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Parse the argument with the specified name, with the values of variables substituted.
+    ///
+    /// A missing argument is parsed from `null`, so use an `Option` to tell it apart.
+    pub fn argument<T: InputValueType>(&self, name: &str) -> Result<T> {
+        let (pos, value) = match self.field.get_argument(name) {
+            Some(value) => (
+                value.pos,
+                Some(self.query_env.resolve_input_value(value.clone())?),
+            ),
+            None => (Pos::default(), None),
+        };
+        T::parse(value).map_err(|e| e.into_error(pos, T::qualified_type_name()))
+    }
+
+    /// Get a lookahead that represents the selection set of this field.
+    pub fn look_ahead(&self) -> Lookahead<'a> {
+        Lookahead::new(self.registry, self.query_env, self.field)
+    }
 }
 
 #[cfg(test)]
@@ -217,4 +353,164 @@ mod tests {
             .await
             .is_err());
     }
+
+    #[async_std::test]
+    async fn test_look_ahead_selection_fields() {
+        struct MyObj;
+
+        #[Object(internal)]
+        impl MyObj {
+            async fn value(&self, n: i32) -> i32 {
+                n
+            }
+        }
+
+        struct Query;
+
+        #[Object(internal)]
+        impl Query {
+            async fn obj(&self, ctx: &Context<'_>) -> FieldResult<MyObj> {
+                let mut fields = Vec::new();
+                for field in ctx.look_ahead().selection_fields() {
+                    fields.push(format!(
+                        "{}:{}({})",
+                        field.response_key(),
+                        field.name(),
+                        field.argument::<i32>("n")?
+                    ));
+                }
+                assert_eq!(fields, vec!["x:value(1)", "y:value(2)", "value:value(3)"]);
+                Ok(MyObj)
+            }
+        }
+
+        let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+        let query = r#"
+            query($n: Int!, $skip: Boolean!) {
+                obj {
+                    x: value(n: 1)
+                    y: value(n: $n)
+                    z: value(n: 4) @skip(if: $skip)
+                    ... on MyObj {
+                        value(n: 3)
+                    }
+                }
+            }
+        "#;
+        let resp = schema
+            .execute(
+                Request::new(query).variables(Variables::from_json(serde_json::json!({
+                    "n": 2,
+                    "skip": true,
+                }))),
+            )
+            .await;
+        assert!(resp.is_ok(), "{:?}", resp.error);
+    }
+
+    #[async_std::test]
+    async fn test_look_ahead_arguments() {
+        struct MyObj;
+
+        struct Query;
+
+        #[Object(internal)]
+        impl Query {
+            async fn obj(&self, ctx: &Context<'_>) -> FieldResult<MyObj> {
+                let fields = ctx.look_ahead().selection_fields();
+                assert_eq!(fields.len(), 1);
+                assert_eq!(fields[0].alias(), Some("b"));
+                assert_eq!(
+                    fields[0].arguments()?,
+                    vec![("a", Value::Number(10.into())), ("b", Value::Null)]
+                );
+                Ok(MyObj)
+            }
+        }
+
+        #[Object(internal)]
+        impl MyObj {
+            async fn list(&self, a: i32, b: Option<i32>) -> i32 {
+                a + b.unwrap_or_default()
+            }
+        }
+
+        let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+        let resp = schema
+            .execute(
+                Request::new("query($a: Int!) { obj { b: list(a: $a, b: null) } }").variables(
+                    Variables::from_json(serde_json::json!({
+                        "a": 10,
+                    })),
+                ),
+            )
+            .await;
+        assert!(resp.is_ok(), "{:?}", resp.error);
+    }
+
+    #[async_std::test]
+    async fn test_look_ahead_on_type() {
+        #[derive(SimpleObject)]
+        #[graphql(internal)]
+        struct Human {
+            name: String,
+            height: i32,
+        }
+
+        #[derive(SimpleObject)]
+        #[graphql(internal)]
+        struct Droid {
+            name: String,
+            primary_function: String,
+        }
+
+        #[derive(Interface)]
+        #[graphql(internal, field(name = "name", type = "&String"))]
+        enum Character {
+            Human(Human),
+            Droid(Droid),
+        }
+
+        struct Query;
+
+        #[Object(internal)]
+        impl Query {
+            async fn character(&self, ctx: &Context<'_>) -> Character {
+                let names = |type_name: &str| {
+                    ctx.look_ahead()
+                        .on_type(type_name)
+                        .selection_fields()
+                        .iter()
+                        .map(|field| field.name())
+                        .collect::<Vec<_>>()
+                };
+                assert_eq!(names("Human"), vec!["name", "height", "name"]);
+                assert_eq!(names("Droid"), vec!["name", "primaryFunction", "name"]);
+                Human {
+                    name: "Luke".to_string(),
+                    height: 172,
+                }
+                .into()
+            }
+        }
+
+        let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+        let resp = schema
+            .execute(
+                r#"{
+                    character {
+                        name
+                        ... on Human { height }
+                        ... on Droid { primaryFunction }
+                        ... CharacterFields
+                    }
+                }
+
+                fragment CharacterFields on Character {
+                    name
+                }"#,
+            )
+            .await;
+        assert!(resp.is_ok(), "{:?}", resp.error);
+    }
 }
@@ -25,4 +25,12 @@ impl<'a> __InputValue<'a> {
     async fn default_value(&self) -> Option<String> {
         self.input_value.default_value.clone()
     }
+
+    async fn is_deprecated(&self) -> bool {
+        self.input_value.deprecation.is_some()
+    }
+
+    async fn deprecation_reason(&self) -> Option<String> {
+        self.input_value.deprecation.map(|s| s.to_string())
+    }
 }
@@ -114,19 +114,20 @@ impl<'a> __Type<'a> {
     }
 
     async fn interfaces(&self) -> Option<Vec<__Type<'a>>> {
-        if let TypeDetail::Named(registry::MetaType::Object { name, .. }) = &self.detail {
-            Some(
-                self.registry
-                    .implements
-                    .get(name)
-                    .unwrap_or(&Default::default())
-                    .iter()
-                    .map(|ty| __Type::new(self.registry, ty))
-                    .collect(),
-            )
-        } else {
-            None
-        }
+        let name = match &self.detail {
+            TypeDetail::Named(registry::MetaType::Object { name, .. }) => name,
+            TypeDetail::Named(registry::MetaType::Interface { name, .. }) => name,
+            _ => return None,
+        };
+        Some(
+            self.registry
+                .implements
+                .get(name)
+                .unwrap_or(&Default::default())
+                .iter()
+                .map(|ty| __Type::new(self.registry, ty))
+                .collect(),
+        )
     }
 
     async fn possible_types(&self) -> Option<Vec<__Type<'a>>> {
@@ -173,13 +174,17 @@ impl<'a> __Type<'a> {
         }
     }
 
-    async fn input_fields(&self) -> Option<Vec<__InputValue<'a>>> {
+    async fn input_fields(
+        &self,
+        #[arg(default = false)] include_deprecated: bool,
+    ) -> Option<Vec<__InputValue<'a>>> {
         if let TypeDetail::Named(registry::MetaType::InputObject { input_fields, .. }) =
             &self.detail
         {
             Some(
                 input_fields
                     .values()
+                    .filter(|input_value| include_deprecated || input_value.deprecation.is_none())
                     .map(|input_value| __InputValue {
                         registry: self.registry,
                         input_value,
@@ -200,4 +205,24 @@ impl<'a> __Type<'a> {
             None
         }
     }
+
+    async fn is_one_of(&self) -> Option<bool> {
+        if let TypeDetail::Named(registry::MetaType::InputObject { oneof, .. }) = &self.detail {
+            Some(*oneof)
+        } else {
+            None
+        }
+    }
+
+    #[field(name = "specifiedByURL")]
+    async fn specified_by_url(&self) -> Option<String> {
+        if let TypeDetail::Named(registry::MetaType::Scalar {
+            specified_by_url, ..
+        }) = &self.detail
+        {
+            specified_by_url.map(|url| url.to_string())
+        } else {
+            None
+        }
+    }
 }
@@ -0,0 +1,335 @@
+use crate::registry::{MetaField, MetaInputValue, MetaType, MetaTypeName, Registry};
+use crate::SchemaError;
+use indexmap::IndexMap;
+use itertools::Itertools;
+use std::collections::HashSet;
+
+impl Registry {
+    /// Validates the type system against the rules of the GraphQL specification.
+    pub(crate) fn check(&self) -> Vec<SchemaError> {
+        let mut errors = Vec::new();
+
+        for (name, first, second) in &self.type_conflicts {
+            errors.push(SchemaError::DuplicateTypeName {
+                name: name.clone(),
+                first: first.to_string(),
+                second: second.to_string(),
+            });
+        }
+
+        for name in &self.node_conflicts {
+            errors.push(SchemaError::NodeConflict { name: name.clone() });
+        }
+
+        for root_type in std::iter::once(&self.query_type)
+            .chain(self.mutation_type.as_ref())
+            .chain(self.subscription_type.as_ref())
+        {
+            match self.types.get(root_type) {
+                Some(MetaType::Object { .. }) => {}
+                Some(_) => errors.push(SchemaError::InvalidRootType {
+                    name: root_type.clone(),
+                }),
+                None => errors.push(SchemaError::UnknownType {
+                    location: "schema".to_string(),
+                    ty: root_type.clone(),
+                }),
+            }
+        }
+
+        for ty in self.types.values() {
+            match ty {
+                MetaType::Scalar { .. } => {}
+                MetaType::Object { name, fields, .. } => {
+                    self.check_fields(name, fields, &mut errors);
+                }
+                MetaType::Interface {
+                    name,
+                    fields,
+                    possible_types,
+                    ..
+                } => {
+                    self.check_fields(name, fields, &mut errors);
+                    for possible_type in possible_types {
+                        match self.types.get(possible_type) {
+                            Some(MetaType::Object { .. }) => {}
+                            Some(_) => errors.push(SchemaError::InvalidPossibleType {
+                                interface: name.clone(),
+                                ty: possible_type.clone(),
+                            }),
+                            None => errors.push(SchemaError::UnknownType {
+                                location: name.clone(),
+                                ty: possible_type.clone(),
+                            }),
+                        }
+                    }
+                }
+                MetaType::Union {
+                    name,
+                    possible_types,
+                    ..
+                } => {
+                    if possible_types.is_empty() {
+                        errors.push(SchemaError::NoUnionMembers { name: name.clone() });
+                    }
+                    for member in possible_types {
+                        match self.types.get(member) {
+                            Some(MetaType::Object { .. }) => {}
+                            Some(_) => errors.push(SchemaError::InvalidUnionMember {
+                                union: name.clone(),
+                                member: member.clone(),
+                            }),
+                            None => errors.push(SchemaError::UnknownType {
+                                location: name.clone(),
+                                ty: member.clone(),
+                            }),
+                        }
+                    }
+                }
+                MetaType::Enum {
+                    name, enum_values, ..
+                } => {
+                    if enum_values.is_empty() {
+                        errors.push(SchemaError::NoEnumValues { name: name.clone() });
+                    }
+                }
+                MetaType::InputObject {
+                    name, input_fields, ..
+                } => {
+                    if input_fields.is_empty() {
+                        errors.push(SchemaError::NoFields { name: name.clone() });
+                    }
+                    for field in input_fields.values() {
+                        self.check_input_value(
+                            format!("{}.{}", name, field.name),
+                            field,
+                            &mut errors,
+                        );
+                    }
+                }
+            }
+        }
+
+        for ty in self.types.values() {
+            if let (Some(fields), Some(interfaces)) = (ty.fields(), self.implements.get(ty.name()))
+            {
+                for interface in interfaces.iter().sorted() {
+                    self.check_implementation(
+                        ty.name(),
+                        fields,
+                        interface,
+                        interfaces,
+                        &mut errors,
+                    );
+                }
+            }
+        }
+
+        for directive in self.directives.values() {
+            for arg in directive.args.values() {
+                self.check_input_value(
+                    format!("@{}({}:)", directive.name, arg.name),
+                    arg,
+                    &mut errors,
+                );
+            }
+        }
+
+        errors
+    }
+
+    fn check_fields(
+        &self,
+        type_name: &str,
+        fields: &IndexMap<String, MetaField>,
+        errors: &mut Vec<SchemaError>,
+    ) {
+        if fields.is_empty() {
+            errors.push(SchemaError::NoFields {
+                name: type_name.to_string(),
+            });
+        }
+
+        for field in fields.values() {
+            let location = format!("{}.{}", type_name, field.name);
+            match self.concrete_type_by_name(&field.ty) {
+                Some(MetaType::InputObject { .. }) => errors.push(SchemaError::NotOutputType {
+                    location,
+                    ty: field.ty.clone(),
+                }),
+                Some(_) => {}
+                None => errors.push(SchemaError::UnknownType {
+                    location,
+                    ty: field.ty.clone(),
+                }),
+            }
+
+            for arg in field.args.values() {
+                self.check_input_value(
+                    format!("{}.{}({}:)", type_name, field.name, arg.name),
+                    arg,
+                    errors,
+                );
+            }
+        }
+    }
+
+    fn check_input_value(
+        &self,
+        location: String,
+        input_value: &MetaInputValue,
+        errors: &mut Vec<SchemaError>,
+    ) {
+        if input_value.deprecation.is_some()
+            && MetaTypeName::create(&input_value.ty).is_non_null()
+            && input_value.default_value.is_none()
+        {
+            errors.push(SchemaError::RequiredInputValueDeprecated {
+                location: location.clone(),
+            });
+        }
+
+        match self.concrete_type_by_name(&input_value.ty) {
+            Some(ty) if ty.is_input() => {}
+            Some(_) => errors.push(SchemaError::NotInputType {
+                location,
+                ty: input_value.ty.clone(),
+            }),
+            None => errors.push(SchemaError::UnknownType {
+                location,
+                ty: input_value.ty.clone(),
+            }),
+        }
+    }
+
+    fn check_implementation(
+        &self,
+        type_name: &str,
+        fields: &IndexMap<String, MetaField>,
+        interface: &str,
+        interfaces: &HashSet<String>,
+        errors: &mut Vec<SchemaError>,
+    ) {
+        if interface == type_name {
+            errors.push(SchemaError::InterfaceImplementsItself {
+                name: type_name.to_string(),
+            });
+            return;
+        }
+
+        let interface_fields = match self.types.get(interface) {
+            Some(MetaType::Interface { fields, .. }) => fields,
+            Some(_) => {
+                errors.push(SchemaError::ImplementsNonInterface {
+                    ty: type_name.to_string(),
+                    interface: interface.to_string(),
+                });
+                return;
+            }
+            None => {
+                errors.push(SchemaError::UnknownType {
+                    location: type_name.to_string(),
+                    ty: interface.to_string(),
+                });
+                return;
+            }
+        };
+
+        if let Some(transitive_interfaces) = self.implements.get(interface) {
+            for transitive_interface in transitive_interfaces.iter().sorted() {
+                if !interfaces.contains(transitive_interface) {
+                    errors.push(SchemaError::MissingTransitiveInterface {
+                        ty: type_name.to_string(),
+                        interface: interface.to_string(),
+                        transitive_interface: transitive_interface.clone(),
+                    });
+                }
+            }
+        }
+
+        for interface_field in interface_fields.values() {
+            let field = match fields.get(&interface_field.name) {
+                Some(field) => field,
+                None => {
+                    errors.push(SchemaError::MissingInterfaceField {
+                        interface: interface.to_string(),
+                        ty: type_name.to_string(),
+                        field: interface_field.name.clone(),
+                    });
+                    continue;
+                }
+            };
+
+            if !self.is_valid_implementation_type(&field.ty, &interface_field.ty) {
+                errors.push(SchemaError::InterfaceFieldTypeMismatch {
+                    interface: interface.to_string(),
+                    ty: type_name.to_string(),
+                    field: field.name.clone(),
+                    expected: interface_field.ty.clone(),
+                    found: field.ty.clone(),
+                });
+            }
+
+            for interface_arg in interface_field.args.values() {
+                match field.args.get(interface_arg.name) {
+                    Some(arg) if arg.ty != interface_arg.ty => {
+                        errors.push(SchemaError::InterfaceFieldArgumentTypeMismatch {
+                            interface: interface.to_string(),
+                            ty: type_name.to_string(),
+                            field: field.name.clone(),
+                            argument: arg.name.to_string(),
+                            expected: interface_arg.ty.clone(),
+                            found: arg.ty.clone(),
+                        })
+                    }
+                    Some(_) => {}
+                    None => errors.push(SchemaError::MissingInterfaceFieldArgument {
+                        interface: interface.to_string(),
+                        ty: type_name.to_string(),
+                        field: field.name.clone(),
+                        argument: interface_arg.name.to_string(),
+                    }),
+                }
+            }
+
+            for arg in field.args.values() {
+                if !interface_field.args.contains_key(arg.name)
+                    && MetaTypeName::create(&arg.ty).is_non_null()
+                    && arg.default_value.is_none()
+                {
+                    errors.push(SchemaError::RequiredExtraArgument {
+                        interface: interface.to_string(),
+                        ty: type_name.to_string(),
+                        field: field.name.clone(),
+                        argument: arg.name.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Whether a field of type `ty` can implement an interface field of type `interface_ty`.
+    fn is_valid_implementation_type(&self, ty: &str, interface_ty: &str) -> bool {
+        match (MetaTypeName::create(ty), MetaTypeName::create(interface_ty)) {
+            (MetaTypeName::NonNull(ty), MetaTypeName::NonNull(interface_ty)) => {
+                self.is_valid_implementation_type(ty, interface_ty)
+            }
+            (_, MetaTypeName::NonNull(_)) => false,
+            (MetaTypeName::NonNull(ty), _) => self.is_valid_implementation_type(ty, interface_ty),
+            (MetaTypeName::List(ty), MetaTypeName::List(interface_ty)) => {
+                self.is_valid_implementation_type(ty, interface_ty)
+            }
+            (MetaTypeName::Named(ty), MetaTypeName::Named(interface_ty)) => {
+                ty == interface_ty
+                    || self
+                        .types
+                        .get(interface_ty)
+                        .map(|interface_ty| {
+                            interface_ty.is_abstract() && interface_ty.is_possible_type(ty)
+                        })
+                        .unwrap_or_default()
+            }
+            _ => false,
+        }
+    }
+}
@@ -72,6 +72,7 @@ impl Registry {
             } else {
                 write!(sdl, "\t{}: {}", field.name, field.ty).ok();
             }
+            write!(sdl, "{}", export_deprecation(field.deprecation)).ok();
 
             if federation {
                 if field.external {
@@ -92,7 +93,10 @@ impl Registry {
     fn export_type(&self, ty: &MetaType, sdl: &mut String, federation: bool) {
         match ty {
             MetaType::Scalar {
-                name, description, ..
+                name,
+                description,
+                specified_by_url,
+                ..
             } => {
                 const SYSTEM_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
                 const FEDERATION_SCALARS: &[&str] = &["Any"];
@@ -104,7 +108,11 @@ impl Registry {
                     if description.is_some() && !federation {
                         writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.unwrap()).ok();
                     }
-                    writeln!(sdl, "scalar {}", name).ok();
+                    write!(sdl, "scalar {}", name).ok();
+                    if let Some(url) = specified_by_url {
+                        write!(sdl, " @specifiedBy(url: \"{}\")", url).ok();
+                    }
+                    writeln!(sdl).ok();
                 }
             }
             MetaType::Object {
@@ -135,7 +143,12 @@ impl Registry {
                 write!(sdl, "type {} ", name).ok();
                 if let Some(implements) = self.implements.get(name) {
                     if !implements.is_empty() {
-                        write!(sdl, "implements {} ", implements.iter().join(" & ")).ok();
+                        write!(
+                            sdl,
+                            "implements {} ",
+                            implements.iter().sorted().join(" & ")
+                        )
+                        .ok();
                     }
                 }
 
@@ -166,6 +179,16 @@ impl Registry {
                     write!(sdl, "extend ").ok();
                 }
                 write!(sdl, "interface {} ", name).ok();
+                if let Some(implements) = self.implements.get(name) {
+                    if !implements.is_empty() {
+                        write!(
+                            sdl,
+                            "implements {} ",
+                            implements.iter().sorted().join(" & ")
+                        )
+                        .ok();
+                    }
+                }
                 if federation {
                     if let Some(keys) = keys {
                         for key in keys {
@@ -189,7 +212,13 @@ impl Registry {
                 write!(sdl, "enum {} ", name).ok();
                 writeln!(sdl, "{{").ok();
                 for value in enum_values.values() {
-                    writeln!(sdl, "\t{}", value.name).ok();
+                    writeln!(
+                        sdl,
+                        "\t{}{}",
+                        value.name,
+                        export_deprecation(value.deprecation)
+                    )
+                    .ok();
                 }
                 writeln!(sdl, "}}").ok();
             }
@@ -197,12 +226,15 @@ impl Registry {
                 name,
                 input_fields,
                 description,
-                ..
+                oneof,
             } => {
                 if description.is_some() && !federation {
                     writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.unwrap()).ok();
                 }
                 write!(sdl, "input {} ", name).ok();
+                if *oneof {
+                    write!(sdl, "@oneOf ").ok();
+                }
                 writeln!(sdl, "{{").ok();
                 for field in input_fields.values() {
                     if let Some(description) = field.description {
@@ -234,12 +266,20 @@ impl Registry {
 }
 
 fn export_input_value(input_value: &MetaInputValue) -> String {
+    let deprecation = export_deprecation(input_value.deprecation);
     if let Some(default_value) = &input_value.default_value {
         format!(
-            "{}: {} = {}",
-            input_value.name, input_value.ty, default_value
+            "{}: {} = {}{}",
+            input_value.name, input_value.ty, default_value, deprecation
         )
     } else {
-        format!("{}: {}", input_value.name, input_value.ty)
+        format!("{}: {}{}", input_value.name, input_value.ty, deprecation)
+    }
+}
+
+fn export_deprecation(deprecation: Option<&str>) -> String {
+    match deprecation {
+        Some(reason) => format!(" @deprecated(reason: \"{}\")", reason.replace('"', "\\\"")),
+        None => String::new(),
     }
 }
@@ -1,15 +1,23 @@
 mod cache_control;
+mod check;
 mod export_sdl;
+mod rename;
 
 use crate::parser::types::{BaseType as ParsedBaseType, Type as ParsedType};
 use crate::validators::InputValueValidator;
-use crate::{model, Any, Type, Value};
+use crate::{model, Any, Context, ContextSelectionSet, Result, Type, Value, ID};
+use futures::future::BoxFuture;
 use indexmap::map::IndexMap;
 use indexmap::set::IndexSet;
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 
 pub use cache_control::CacheControl;
+pub use rename::RenameRule;
+
+use rename::Renames;
 
 fn strip_brackets(type_name: &str) -> Option<&str> {
     if let Some(rest) = type_name.strip_prefix('[') {
@@ -19,6 +27,29 @@ fn strip_brackets(type_name: &str) -> Option<&str> {
     }
 }
 
+/// Removes the generic parameters from a Rust type name, so that every instantiation of a generic
+/// type has the same name.
+fn strip_generics(name: &str) -> &str {
+    match name.find('<') {
+        Some(idx) => &name[..idx],
+        None => name,
+    }
+}
+
+/// Returns true if two Rust types may be registered under the same GraphQL name.
+///
+/// `builtin` is true if both Rust types are scalars provided by this crate.
+fn is_same_source(ty: &MetaType, first: &str, second: &str, builtin: bool) -> bool {
+    match ty {
+        // Several scalars of this crate share a name, e.g. `i32` and `i64` are both `Int`, and
+        // `HashMap<String, T>` and `BTreeMap<String, T>` are both `JSONObject`.
+        MetaType::Scalar { .. } if builtin => true,
+        // A generic scalar such as `Json<T>` has one name for all its instantiations.
+        MetaType::Scalar { .. } => strip_generics(first) == strip_generics(second),
+        _ => first == second,
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum MetaTypeName<'a> {
     List(&'a str),
@@ -89,7 +120,13 @@ pub struct MetaInputValue {
     pub description: Option<&'static str>,
     pub ty: String,
     pub default_value: Option<String>,
+    pub deprecation: Option<&'static str>,
     pub validator: Option<Arc<dyn InputValueValidator>>,
+    pub is_secret: bool,
+    /// The names of the item under each `RenameRule`, in the order of its variants. `None` if the
+    /// name is set explicitly or by a rename attribute of the type, so the rename rules of the
+    /// schema don't apply to it.
+    pub default_names: Option<&'static [&'static str; 5]>,
 }
 
 #[derive(Clone)]
@@ -103,6 +140,12 @@ pub struct MetaField {
     pub external: bool,
     pub requires: Option<&'static str>,
     pub provides: Option<&'static str>,
+    pub timeout: Option<Duration>,
+    pub is_secret: bool,
+    /// The names of the item under each `RenameRule`, in the order of its variants. `None` if the
+    /// name is set explicitly or by a rename attribute of the type, so the rename rules of the
+    /// schema don't apply to it.
+    pub default_names: Option<&'static [&'static str; 5]>,
 }
 
 #[derive(Clone)]
@@ -110,6 +153,10 @@ pub struct MetaEnumValue {
     pub name: &'static str,
     pub description: Option<&'static str>,
     pub deprecation: Option<&'static str>,
+    /// The names of the item under each `RenameRule`, in the order of its variants. `None` if the
+    /// name is set explicitly or by a rename attribute of the type, so the rename rules of the
+    /// schema don't apply to it.
+    pub default_names: Option<&'static [&'static str; 5]>,
 }
 
 pub enum MetaType {
@@ -117,6 +164,7 @@ pub enum MetaType {
         name: String,
         description: Option<&'static str>,
         is_valid: fn(value: &Value) -> bool,
+        specified_by_url: Option<&'static str>,
     },
     Object {
         name: String,
@@ -148,6 +196,7 @@ pub enum MetaType {
         name: String,
         description: Option<&'static str>,
         input_fields: IndexMap<String, MetaInputValue>,
+        oneof: bool,
     },
 }
 
@@ -250,23 +299,83 @@ pub struct MetaDirective {
     pub description: Option<&'static str>,
     pub locations: Vec<model::__DirectiveLocation>,
     pub args: IndexMap<&'static str, MetaInputValue>,
+    pub is_repeatable: bool,
 }
 
+pub type NodeLoader = for<'a> fn(
+    &'a Context<'a>,
+    &'a ContextSelectionSet<'a>,
+    &'a str,
+) -> BoxFuture<'a, Result<Option<Value>>>;
+
 pub struct Registry {
     pub types: IndexMap<String, MetaType>,
     pub directives: HashMap<String, MetaDirective>,
     pub implements: HashMap<String, HashSet<String>>,
+    pub nodes: IndexMap<String, NodeLoader>,
     pub query_type: String,
     pub mutation_type: Option<String>,
     pub subscription_type: Option<String>,
+    pub type_sources: HashMap<String, &'static str>,
+    pub builtin_types: HashSet<String>,
+    pub type_conflicts: IndexSet<(String, &'static str, &'static str)>,
+    pub node_conflicts: Vec<String>,
+    pub(crate) renames: HashMap<String, Renames>,
 }
 
 impl Registry {
     pub fn create_type<T: crate::Type, F: FnMut(&mut Registry) -> MetaType>(
         &mut self,
-        mut f: F,
+        f: F,
+    ) -> String {
+        self.create_type_with_name(T::type_name(), std::any::type_name::<T>(), false, f);
+        T::qualified_type_name()
+    }
+
+    /// Like `create_type`, but registers a scalar provided by this crate. Scalars of this crate
+    /// may share a name, such as `i32` and `i64`, which are both `Int`.
+    #[doc(hidden)]
+    pub fn create_builtin_type<T: crate::Type, F: FnMut(&mut Registry) -> MetaType>(
+        &mut self,
+        f: F,
+    ) -> String {
+        self.create_type_with_name(T::type_name(), std::any::type_name::<T>(), true, f);
+        T::qualified_type_name()
+    }
+
+    /// Like `create_type`, but registers the input side of a type that is also an output type.
+    pub fn create_input_type<T: crate::InputValueType, F: FnMut(&mut Registry) -> MetaType>(
+        &mut self,
+        f: F,
     ) -> String {
-        let name = T::type_name();
+        self.create_type_with_name(T::input_type_name(), std::any::type_name::<T>(), false, f);
+        T::qualified_input_type_name()
+    }
+
+    fn create_type_with_name<F: FnMut(&mut Registry) -> MetaType>(
+        &mut self,
+        name: Cow<'static, str>,
+        source: &'static str,
+        builtin: bool,
+        mut f: F,
+    ) {
+        match self.types.get(name.as_ref()) {
+            Some(ty) => {
+                if let Some(first) = self.type_sources.get(name.as_ref()) {
+                    let builtin = builtin && self.builtin_types.contains(name.as_ref());
+                    if !is_same_source(ty, first, source, builtin) {
+                        self.type_conflicts
+                            .insert((name.clone().into_owned(), *first, source));
+                    }
+                }
+            }
+            None => {
+                self.type_sources.insert(name.clone().into_owned(), source);
+                if builtin {
+                    self.builtin_types.insert(name.clone().into_owned());
+                }
+            }
+        }
         if !self.types.contains_key(name.as_ref()) {
             // Inserting a fake type before calling the function allows recursive types to exist.
             self.types.insert(
@@ -283,7 +392,6 @@ impl Registry {
             let ty = f(self);
             *self.types.get_mut(&*name).unwrap() = ty;
         }
-        T::qualified_type_name()
     }
 
     pub fn add_directive(&mut self, directive: MetaDirective) {
@@ -304,6 +412,11 @@ impl Registry {
             });
     }
 
+    pub fn add_node(&mut self, ty: &str, loader: NodeLoader) {
+        self.nodes.insert(ty.to_string(), loader);
+        self.add_implements(ty, "Node");
+    }
+
     pub fn add_keys(&mut self, ty: &str, keys: &str) {
         let all_keys = match self.types.get_mut(ty) {
             Some(MetaType::Object { keys: all_keys, .. }) => all_keys,
@@ -391,6 +504,9 @@ impl Registry {
                             external: false,
                             requires: None,
                             provides: None,
+                            timeout: None,
+                            is_secret: false,
+                            default_names: None,
                         },
                     );
                     fields
@@ -417,6 +533,9 @@ impl Registry {
                     external: false,
                     requires: None,
                     provides: None,
+                    timeout: None,
+                    is_secret: false,
+                    default_names: None,
                 },
             );
 
@@ -434,7 +553,10 @@ impl Registry {
                                 description: None,
                                 ty: "[_Any!]!".to_string(),
                                 default_value: None,
+                                deprecation: None,
                                 validator: None,
+                                is_secret: false,
+                                default_names: None,
                             },
                         );
                         args
@@ -445,6 +567,149 @@ impl Registry {
                     external: false,
                     requires: None,
                     provides: None,
+                    timeout: None,
+                    is_secret: false,
+                    default_names: None,
+                },
+            );
+        }
+    }
+
+    pub(crate) fn has_nodes(&self) -> bool {
+        !self.nodes.is_empty()
+    }
+
+    /// Returns true if the `node` or `nodes` query root field is the one added for the Relay `Node`
+    /// interface, rather than a field of the same name defined by the schema.
+    pub(crate) fn is_node_field(&self, name: &str) -> bool {
+        self.has_nodes()
+            && !self
+                .node_conflicts
+                .contains(&format!("{}.{}", self.query_type, name))
+    }
+
+    /// Adds the Relay `Node` interface and the `node` and `nodes` query fields.
+    ///
+    /// Existing types and fields with these names are kept, and reported by `check`.
+    pub(crate) fn create_node_types(&mut self) {
+        let id_type = ID::create_type_info(self);
+
+        if self.types.contains_key("Node") {
+            self.node_conflicts.push("Node".to_string());
+        } else {
+            self.types.insert(
+                "Node".to_string(),
+                MetaType::Interface {
+                    name: "Node".to_string(),
+                    description: Some("An object with a globally unique ID."),
+                    fields: {
+                        let mut fields = IndexMap::new();
+                        fields.insert(
+                            "id".to_string(),
+                            MetaField {
+                                name: "id".to_string(),
+                                description: Some("The globally unique ID of the object."),
+                                args: Default::default(),
+                                ty: id_type.clone(),
+                                deprecation: None,
+                                cache_control: Default::default(),
+                                external: false,
+                                requires: None,
+                                provides: None,
+                                timeout: None,
+                                is_secret: false,
+                                default_names: None,
+                            },
+                        );
+                        fields
+                    },
+                    possible_types: self.nodes.keys().cloned().collect(),
+                    extends: false,
+                    keys: None,
+                },
+            );
+        }
+
+        let fields = match self.types.get_mut(&self.query_type) {
+            Some(MetaType::Object { fields, .. }) => fields,
+            // An invalid query root type is reported by `check`.
+            _ => return,
+        };
+
+        if fields.contains_key("node") {
+            self.node_conflicts
+                .push(format!("{}.node", self.query_type));
+        } else {
+            fields.insert(
+                "node".to_string(),
+                MetaField {
+                    name: "node".to_string(),
+                    description: Some("Fetches an object given its ID."),
+                    args: {
+                        let mut args = IndexMap::new();
+                        args.insert(
+                            "id",
+                            MetaInputValue {
+                                name: "id",
+                                description: Some("The ID of an object."),
+                                ty: id_type.clone(),
+                                default_value: None,
+                                deprecation: None,
+                                validator: None,
+                                is_secret: false,
+                                default_names: None,
+                            },
+                        );
+                        args
+                    },
+                    ty: "Node".to_string(),
+                    deprecation: None,
+                    cache_control: Default::default(),
+                    external: false,
+                    requires: None,
+                    provides: None,
+                    timeout: None,
+                    is_secret: false,
+                    default_names: None,
+                },
+            );
+        }
+
+        if fields.contains_key("nodes") {
+            self.node_conflicts
+                .push(format!("{}.nodes", self.query_type));
+        } else {
+            fields.insert(
+                "nodes".to_string(),
+                MetaField {
+                    name: "nodes".to_string(),
+                    description: Some("Fetches objects given their IDs."),
+                    args: {
+                        let mut args = IndexMap::new();
+                        args.insert(
+                            "ids",
+                            MetaInputValue {
+                                name: "ids",
+                                description: Some("The IDs of objects."),
+                                ty: format!("[{}]!", id_type),
+                                default_value: None,
+                                deprecation: None,
+                                validator: None,
+                                is_secret: false,
+                                default_names: None,
+                            },
+                        );
+                        args
+                    },
+                    ty: "[Node]!".to_string(),
+                    deprecation: None,
+                    cache_control: Default::default(),
+                    external: false,
+                    requires: None,
+                    provides: None,
+                    timeout: None,
+                    is_secret: false,
+                    default_names: None,
                 },
             );
         }
@@ -0,0 +1,352 @@
+use crate::parser::parse_query;
+use crate::parser::types::{DocumentOperations, Name};
+use crate::registry::{MetaType, MetaTypeName, Registry};
+use crate::Value;
+use indexmap::map::IndexMap;
+use std::collections::HashMap;
+
+/// A case convention for the names of fields, arguments and enum items.
+///
+/// The derive macros name them after the Rust items, in `camelCase` for fields and arguments and
+/// in `SCREAMING_SNAKE_CASE` for enum items. Another convention can be chosen for a type with its
+/// `rename_fields`, `rename_args` or `rename_items` attribute, or for the whole schema with
+/// `SchemaBuilder::rename_fields`, `SchemaBuilder::rename_args` and
+/// `SchemaBuilder::rename_items`.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn add_one(&self, the_value: i32) -> i32 {
+///         the_value + 1
+///     }
+/// }
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+///         .rename_fields(RenameRule::SnakeCase)
+///         .rename_args(RenameRule::SnakeCase)
+///         .finish();
+///     let res = schema.execute("{ add_one(the_value: 1) }").await.into_result().unwrap().data;
+///     assert_eq!(res, serde_json::json!({ "add_one": 2 }));
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RenameRule {
+    /// `camelCase`
+    CamelCase,
+    /// `snake_case`
+    SnakeCase,
+    /// `PascalCase`
+    PascalCase,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnakeCase,
+    /// `lowercase`
+    Lowercase,
+}
+
+impl RenameRule {
+    /// Picks the name under this rule from the names generated by the derive macros, which are in
+    /// the order of the variants.
+    fn select(self, names: &[&'static str; 5]) -> &'static str {
+        names[self as usize]
+    }
+}
+
+/// The names of a type that were changed by the rename rules of the schema.
+#[derive(Default)]
+pub(crate) struct Renames {
+    /// The names in the schema of fields, input fields and enum items, by their names in the code.
+    to_schema: HashMap<String, String>,
+    /// The names in the code of fields, input fields and enum items, by their names in the schema.
+    to_code: HashMap<String, String>,
+    /// For each field, by its name in the schema, the names in the schema of its arguments by
+    /// their names in the code.
+    args: HashMap<String, HashMap<String, String>>,
+}
+
+impl Registry {
+    /// Applies the rename rules of the schema to the fields, arguments and enum items that are not
+    /// named explicitly, and records the names that changed.
+    pub(crate) fn apply_rename_rules(
+        &mut self,
+        rename_fields: Option<RenameRule>,
+        rename_args: Option<RenameRule>,
+        rename_items: Option<RenameRule>,
+    ) {
+        for (type_name, ty) in self.types.iter_mut() {
+            let mut renames = Renames::default();
+            match ty {
+                MetaType::Object { fields, .. } | MetaType::Interface { fields, .. } => {
+                    *fields = std::mem::take(fields)
+                        .into_iter()
+                        .map(|(_, mut field)| {
+                            if let (Some(rule), Some(names)) = (rename_fields, field.default_names)
+                            {
+                                let name = rule.select(names);
+                                if field.name != name {
+                                    renames.insert(&field.name, name);
+                                    field.name = name.to_string();
+                                }
+                            }
+                            if let Some(rule) = rename_args {
+                                let mut arg_renames = HashMap::new();
+                                field.args = std::mem::take(&mut field.args)
+                                    .into_iter()
+                                    .map(|(_, mut arg)| {
+                                        if let Some(names) = arg.default_names {
+                                            let name = rule.select(names);
+                                            if arg.name != name {
+                                                arg_renames
+                                                    .insert(arg.name.to_string(), name.to_string());
+                                                arg.name = name;
+                                            }
+                                        }
+                                        (arg.name, arg)
+                                    })
+                                    .collect();
+                                if !arg_renames.is_empty() {
+                                    renames.args.insert(field.name.clone(), arg_renames);
+                                }
+                            }
+                            (field.name.clone(), field)
+                        })
+                        .collect();
+                }
+                MetaType::InputObject { input_fields, .. } => {
+                    *input_fields = std::mem::take(input_fields)
+                        .into_iter()
+                        .map(|(_, mut field)| {
+                            if let (Some(rule), Some(names)) = (rename_fields, field.default_names)
+                            {
+                                let name = rule.select(names);
+                                if field.name != name {
+                                    renames.insert(field.name, name);
+                                    field.name = name;
+                                }
+                            }
+                            (field.name.to_string(), field)
+                        })
+                        .collect();
+                }
+                MetaType::Enum { enum_values, .. } => {
+                    *enum_values = std::mem::take(enum_values)
+                        .into_iter()
+                        .map(|(_, mut item)| {
+                            if let (Some(rule), Some(names)) = (rename_items, item.default_names) {
+                                let name = rule.select(names);
+                                if item.name != name {
+                                    renames.insert(item.name, name);
+                                    item.name = name;
+                                }
+                            }
+                            (item.name, item)
+                        })
+                        .collect();
+                }
+                _ => {}
+            }
+            if !renames.is_empty() {
+                self.renames.insert(type_name.clone(), renames);
+            }
+        }
+
+        self.rename_default_values();
+    }
+
+    /// The default values of arguments and input fields are generated from Rust values, so they
+    /// use the names in the code.
+    fn rename_default_values(&mut self) {
+        let mut default_values = Vec::new();
+        for (type_name, ty) in &self.types {
+            let input_values = match ty {
+                MetaType::Object { fields, .. } | MetaType::Interface { fields, .. } => fields
+                    .values()
+                    .flat_map(|field| {
+                        field
+                            .args
+                            .values()
+                            .map(move |arg| (Some(field.name.as_str()), arg))
+                    })
+                    .collect::<Vec<_>>(),
+                MetaType::InputObject { input_fields, .. } => {
+                    input_fields.values().map(|field| (None, field)).collect()
+                }
+                _ => continue,
+            };
+            for (field_name, input_value) in input_values {
+                let default_value = match input_value
+                    .default_value
+                    .as_deref()
+                    .and_then(parse_default_value)
+                {
+                    Some(default_value) => default_value,
+                    None => continue,
+                };
+                let renamed = self
+                    .convert_value(&input_value.ty, default_value, true)
+                    .to_string();
+                if input_value.default_value.as_ref() != Some(&renamed) {
+                    default_values.push((
+                        type_name.clone(),
+                        field_name.map(ToString::to_string),
+                        input_value.name,
+                        renamed,
+                    ));
+                }
+            }
+        }
+
+        for (type_name, field_name, name, default_value) in default_values {
+            let input_value = match (self.types.get_mut(&type_name), field_name) {
+                (Some(MetaType::Object { fields, .. }), Some(field_name))
+                | (Some(MetaType::Interface { fields, .. }), Some(field_name)) => fields
+                    .get_mut(&field_name)
+                    .and_then(|field| field.args.get_mut(name)),
+                (Some(MetaType::InputObject { input_fields, .. }), None) => {
+                    input_fields.get_mut(name)
+                }
+                _ => None,
+            };
+            if let Some(input_value) = input_value {
+                input_value.default_value = Some(default_value);
+            }
+        }
+    }
+
+    /// Returns the name in the code of a field, input field or enum item of a type.
+    #[doc(hidden)]
+    pub fn code_name<'a>(&'a self, type_name: &str, name: &'a str) -> &'a str {
+        self.renames
+            .get(type_name)
+            .and_then(|renames| renames.to_code.get(name))
+            .map_or(name, String::as_str)
+    }
+
+    /// Returns the name in the schema of an argument of a field, from its name in the code.
+    pub(crate) fn schema_arg_name<'a>(
+        &'a self,
+        type_name: &str,
+        field_name: &str,
+        name: &'a str,
+    ) -> &'a str {
+        self.renames
+            .get(type_name)
+            .and_then(|renames| renames.args.get(field_name))
+            .and_then(|args| args.get(name))
+            .map_or(name, String::as_str)
+    }
+
+    /// Converts the enum items and input object fields of a value of the given type from their
+    /// names in the code to their names in the schema.
+    #[doc(hidden)]
+    pub fn value_to_schema(&self, ty: &str, value: Value) -> Value {
+        if self.renames.is_empty() {
+            return value;
+        }
+        self.convert_value(ty, value, true)
+    }
+
+    /// Converts the enum items and input object fields of a value of the given type from their
+    /// names in the schema to their names in the code.
+    pub(crate) fn value_to_code(&self, ty: &str, value: Value) -> Value {
+        if self.renames.is_empty() {
+            return value;
+        }
+        self.convert_value(ty, value, false)
+    }
+
+    fn convert_value(&self, ty: &str, value: Value, to_schema: bool) -> Value {
+        let ty = match MetaTypeName::create(ty) {
+            MetaTypeName::NonNull(ty) => return self.convert_value(ty, value, to_schema),
+            MetaTypeName::List(ty) => {
+                return match value {
+                    Value::List(items) => Value::List(
+                        items
+                            .into_iter()
+                            .map(|item| self.convert_value(ty, item, to_schema))
+                            .collect(),
+                    ),
+                    // A single value is accepted for a list.
+                    value => self.convert_value(ty, value, to_schema),
+                };
+            }
+            MetaTypeName::Named(ty) => ty,
+        };
+
+        let renames = self.renames.get(ty);
+        let rename = |name: &str| -> Option<String> {
+            let renames = renames?;
+            let names = if to_schema {
+                &renames.to_schema
+            } else {
+                &renames.to_code
+            };
+            names.get(name).cloned()
+        };
+
+        match (self.types.get(ty), value) {
+            (Some(MetaType::Enum { .. }), Value::Enum(name)) => match rename(&name) {
+                Some(name) => Value::Enum(Name::new_unchecked(name)),
+                None => Value::Enum(name),
+            },
+            (Some(MetaType::Enum { .. }), Value::String(name)) => {
+                Value::String(rename(&name).unwrap_or(name))
+            }
+            (Some(MetaType::InputObject { input_fields, .. }), Value::Object(map)) => {
+                Value::Object(
+                    map.into_iter()
+                        .map(|(name, value)| {
+                            let renamed = rename(&name);
+                            let schema_name = match (&renamed, to_schema) {
+                                (Some(renamed), true) => renamed.as_str(),
+                                _ => name.as_str(),
+                            };
+                            let value = match input_fields.get(schema_name) {
+                                Some(field) => self.convert_value(&field.ty, value, to_schema),
+                                None => value,
+                            };
+                            (renamed.map_or(name, Name::new_unchecked), value)
+                        })
+                        .collect::<IndexMap<_, _>>(),
+                )
+            }
+            (_, value) => value,
+        }
+    }
+}
+
+impl Renames {
+    fn insert(&mut self, code_name: &str, schema_name: &str) {
+        self.to_schema
+            .insert(code_name.to_string(), schema_name.to_string());
+        self.to_code
+            .insert(schema_name.to_string(), code_name.to_string());
+    }
+
+    fn is_empty(&self) -> bool {
+        self.to_schema.is_empty() && self.args.is_empty()
+    }
+}
+
+/// Parses a default value of the registry, which is printed in the GraphQL syntax.
+fn parse_default_value(value: &str) -> Option<Value> {
+    let document = parse_query(format!("query($value: Int = {}) {{ __typename }}", value)).ok()?;
+    match document.operations {
+        DocumentOperations::Single(operation) => operation
+            .node
+            .variable_definitions
+            .into_iter()
+            .next()?
+            .node
+            .default_value
+            .map(|value| value.node),
+        DocumentOperations::Multiple(_) => None,
+    }
+}
@@ -1,10 +1,10 @@
 use crate::extensions::{BoxExtension, Extension};
-use crate::parser::types::UploadValue;
+use crate::parser::types::{UploadContent, UploadValue};
 use crate::{Data, ParseRequestError, Value, Variables};
 use serde::{Deserialize, Deserializer};
 use std::any::Any;
 use std::fmt::{self, Debug, Formatter};
-use std::fs::File;
+use std::time::Duration;
 
 /// GraphQL request.
 ///
@@ -33,6 +33,11 @@ pub struct Request {
     /// Extensions for this request.
     #[serde(skip)]
     pub extensions: Vec<Box<dyn Fn() -> BoxExtension + Send + Sync>>,
+
+    /// The maximum time the execution of this request may take, overriding the timeout of the
+    /// schema.
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
 }
 
 fn deserialize_variables<'de, D: Deserializer<'de>>(
@@ -50,6 +55,7 @@ impl Request {
             variables: Variables::default(),
             data: Data::default(),
             extensions: Vec::default(),
+            timeout: None,
         }
     }
 
@@ -72,6 +78,17 @@ impl Request {
         self
     }
 
+    /// Set the maximum time the execution of this request may take.
+    ///
+    /// When it expires, the resolvers still running are dropped and the request fails with a
+    /// timeout error at the path of the field that was being resolved.
+    pub fn timeout(self, timeout: Duration) -> Self {
+        Self {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+
     /// Set a variable to an upload value.
     ///
     /// `var_path` is a dot-separated path to the item that begins with `variables`, for example
@@ -83,7 +100,7 @@ impl Request {
         var_path: &str,
         filename: String,
         content_type: Option<String>,
-        content: File,
+        content: impl Into<UploadContent>,
     ) {
         let variable = match self.variables.variable_path(var_path) {
             Some(variable) => variable,
@@ -92,7 +109,7 @@ impl Request {
         *variable = Value::Upload(UploadValue {
             filename,
             content_type,
-            content,
+            content: content.into(),
         });
     }
 
@@ -119,6 +136,7 @@ impl Debug for Request {
             .field("query", &self.query)
             .field("operation_name", &self.operation_name)
             .field("variables", &self.variables)
+            .field("timeout", &self.timeout)
             .finish()
     }
 }
@@ -150,6 +168,21 @@ impl BatchRequest {
             Self::Batch(_) => Err(ParseRequestError::UnsupportedBatch),
         }
     }
+
+    /// Check that the batch request contains at most `max` requests.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `ParseRequestError::BatchTooLarge` if the batch request is a list of more than
+    /// `max` requests.
+    pub fn check_batch_size(&self, max: usize) -> Result<(), ParseRequestError> {
+        match self {
+            Self::Batch(requests) if requests.len() > max => {
+                Err(ParseRequestError::BatchTooLarge(max))
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 fn deserialize_non_empty_vec<'de, D, T>(deserializer: D) -> std::result::Result<Vec<T>, D::Error>
@@ -1,13 +1,13 @@
-use crate::extensions::{ErrorLogger, Extension, ResolveInfo};
+use crate::extensions::{Extension, ResolveInfo};
 use crate::parser::types::Field;
-use crate::{ContextSelectionSet, OutputValueType, Positioned, Result, Type};
+use crate::{ContextSelectionSet, OutputValueType, Positioned, Result, Type, Value};
 
 /// Resolve an list by executing each of the items concurrently.
 pub async fn resolve_list<'a, T: OutputValueType + Send + Sync + 'a>(
     ctx: &ContextSelectionSet<'a>,
     field: &Positioned<Field>,
     iter: impl IntoIterator<Item = T>,
-) -> Result<serde_json::Value> {
+) -> Result<Value> {
     let mut futures = Vec::new();
 
     for (idx, item) in iter.into_iter().enumerate() {
@@ -18,6 +18,7 @@ pub async fn resolve_list<'a, T: OutputValueType + Send + Sync + 'a>(
                 path_node: ctx_idx.path_node.as_ref().unwrap(),
                 parent_type: &Vec::<T>::type_name(),
                 return_type: &T::qualified_type_name(),
+                is_secret: ctx_idx.secret,
                 schema_env: ctx.schema_env,
                 query_env: ctx.query_env,
             };
@@ -28,9 +29,10 @@ pub async fn resolve_list<'a, T: OutputValueType + Send + Sync + 'a>(
                 .lock()
                 .resolve_start(&resolve_info);
 
-            let res = OutputValueType::resolve(&item, &ctx_idx, field)
-                .await
-                .log_error(&ctx_idx.query_env.extensions)?;
+            let res = ctx_idx.schema_env.log_resolver_error(
+                OutputValueType::resolve(&item, &ctx_idx, field).await,
+                &ctx_idx.query_env.extensions,
+            )?;
 
             ctx_idx
                 .query_env
@@ -42,5 +44,5 @@ pub async fn resolve_list<'a, T: OutputValueType + Send + Sync + 'a>(
         });
     }
 
-    Ok(futures::future::try_join_all(futures).await?.into())
+    Ok(Value::List(futures::future::try_join_all(futures).await?))
 }
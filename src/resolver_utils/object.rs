@@ -1,10 +1,14 @@
-use crate::extensions::{ErrorLogger, Extension, ResolveInfo};
-use crate::parser::types::Selection;
-use crate::registry::MetaType;
+use crate::extensions::{Extension, ResolveInfo};
+use crate::parser::types::{Name, Selection};
+use crate::registry::{MetaField, MetaType, Registry};
 use crate::{Context, ContextSelectionSet, Error, OutputValueType, QueryError, Result, Value};
+use futures::future::{self, Either};
 use futures::TryFutureExt;
+use futures_timer::Delay;
+use indexmap::IndexMap;
 use std::future::Future;
 use std::pin::Pin;
+use std::time::Instant;
 
 /// A GraphQL object.
 ///
@@ -18,8 +22,8 @@ pub trait ObjectType: OutputValueType {
         false
     }
 
-    /// Resolves a field value and outputs it as a json value `serde_json::Value`.
-    async fn resolve_field(&self, ctx: &Context<'_>) -> Result<serde_json::Value>;
+    /// Resolves a field value and outputs it as a `Value`.
+    async fn resolve_field(&self, ctx: &Context<'_>) -> Result<Value>;
 
     /// Collect all the fields of the object that are queried in the selection set.
     ///
@@ -39,73 +43,122 @@ pub trait ObjectType: OutputValueType {
     /// Find the GraphQL entity with the given name from the parameter.
     ///
     /// Objects should override this in case they are the query root.
-    async fn find_entity(&self, ctx: &Context<'_>, _params: &Value) -> Result<serde_json::Value> {
+    async fn find_entity(&self, ctx: &Context<'_>, _params: &Value) -> Result<Value> {
         Err(QueryError::EntityNotFound.into_error(ctx.item.pos))
     }
 }
 
 #[async_trait::async_trait]
 impl<T: ObjectType + Send + Sync> ObjectType for &T {
-    async fn resolve_field(&self, ctx: &Context<'_>) -> Result<serde_json::Value> {
+    async fn resolve_field(&self, ctx: &Context<'_>) -> Result<Value> {
         T::resolve_field(*self, ctx).await
     }
 }
 
+/// The computed fields of a `SimpleObject` declared with `#[graphql(complex)]`.
+///
+/// It is implemented by the `#[ComplexObject]` macro, and its fields are merged into the object
+/// type of the `SimpleObject`.
+#[async_trait::async_trait]
+pub trait ComplexObject {
+    /// Create the type information of the computed fields in the registry.
+    fn fields(registry: &mut Registry) -> IndexMap<String, MetaField>;
+
+    /// Resolves a computed field, or returns `None` if it is not one of the computed fields.
+    async fn resolve_field(&self, ctx: &Context<'_>) -> Result<Option<Value>>;
+}
+
 // TODO: reduce code duplication between the two below functions?
 
 /// Resolve an object by executing each of the fields concurrently.
 pub async fn resolve_object<'a, T: ObjectType + Send + Sync>(
     ctx: &ContextSelectionSet<'a>,
     root: &'a T,
-) -> Result<serde_json::Value> {
+) -> Result<Value> {
     let mut fields = Fields(Vec::new());
     fields.add_set(ctx, root)?;
     let futures = fields.0;
 
     let res = futures::future::try_join_all(futures).await?;
-    let mut map = serde_json::Map::new();
+    let mut map = IndexMap::new();
     for (name, value) in res {
-        if let serde_json::Value::Object(b) = value {
-            if let Some(serde_json::Value::Object(a)) = map.get_mut(&name) {
-                a.extend(b);
-            } else {
-                map.insert(name, b.into());
-            }
-        } else {
-            map.insert(name, value);
-        }
+        insert_value(&mut map, name, value);
     }
-    Ok(map.into())
+    Ok(Value::Object(map))
 }
 
 /// Resolve an object by executing each of the fields serially.
 pub async fn resolve_object_serial<'a, T: ObjectType + Send + Sync>(
     ctx: &ContextSelectionSet<'a>,
     root: &'a T,
-) -> Result<serde_json::Value> {
+) -> Result<Value> {
     let mut fields = Fields(Vec::new());
     fields.add_set(ctx, root)?;
     let futures = fields.0;
 
-    let mut map = serde_json::Map::new();
+    let mut map = IndexMap::new();
     for field in futures {
         let (name, value) = field.await?;
+        insert_value(&mut map, name, value);
+    }
+    Ok(Value::Object(map))
+}
 
-        if let serde_json::Value::Object(b) = value {
-            if let Some(serde_json::Value::Object(a)) = map.get_mut(&name) {
-                a.extend(b);
-            } else {
-                map.insert(name, b.into());
+/// Inserts a field value in the map of an object, merging it into the existing value if the same
+/// field was selected several times.
+fn insert_value(target: &mut IndexMap<Name, Value>, name: Name, value: Value) {
+    if let Some(prev_value) = target.get_mut(&name) {
+        if let Value::Object(target_map) = prev_value {
+            if let Value::Object(obj) = value {
+                for (key, value) in obj.into_iter() {
+                    insert_value(target_map, key, value);
+                }
+            }
+        } else if let Value::List(target_list) = prev_value {
+            if let Value::List(list) = value {
+                for (idx, value) in list.into_iter().enumerate() {
+                    if let Some(Value::Object(target_map)) = target_list.get_mut(idx) {
+                        if let Value::Object(obj) = value {
+                            for (key, value) in obj.into_iter() {
+                                insert_value(target_map, key, value);
+                            }
+                        }
+                    }
+                }
             }
-        } else {
-            map.insert(name, value);
         }
+    } else {
+        target.insert(name, value);
+    }
+}
+
+/// Resolve a field, failing with `QueryError::Timeout` if the deadline of the context expires
+/// first. The field future is dropped at that point.
+async fn resolve_with_deadline(
+    ctx: &Context<'_>,
+    fut: impl Future<Output = Result<Value>>,
+) -> Result<Value> {
+    let deadline = match ctx.deadline {
+        Some(deadline) => deadline,
+        None => return fut.await,
+    };
+
+    let delay = Delay::new(deadline.saturating_duration_since(Instant::now()));
+    futures::pin_mut!(fut);
+    match future::select(fut, delay).await {
+        Either::Left((res, _)) => res,
+        Either::Right(_) => Err(Error::Query {
+            pos: ctx.item.pos,
+            path: ctx
+                .path_node
+                .as_ref()
+                .and_then(|path| serde_json::to_value(path).ok()),
+            err: QueryError::Timeout,
+        }),
     }
-    Ok(map.into())
 }
 
-type BoxFieldFuture<'a> =
-    Pin<Box<dyn Future<Output = Result<(String, serde_json::Value)>> + 'a + Send>>;
+type BoxFieldFuture<'a> = Pin<Box<dyn Future<Output = Result<(Name, Value)>> + 'a + Send>>;
 
 /// A set of fields on an object that are being selected.
 pub struct Fields<'a>(Vec<BoxFieldFuture<'a>>);
@@ -127,17 +180,11 @@ impl<'a> Fields<'a> {
                     if field.node.name.node == "__typename" {
                         // Get the typename
                         let ctx_field = ctx.with_field(field);
-                        let field_name = ctx_field
-                            .item
-                            .node
-                            .response_key()
-                            .node
-                            .clone()
-                            .into_string();
+                        let field_name = ctx_field.item.node.response_key().node.clone();
                         let typename = root.introspection_type_name().into_owned();
 
                         self.0.push(Box::pin(async move {
-                            Ok((field_name, serde_json::Value::String(typename)))
+                            Ok((field_name, Value::String(typename)))
                         }));
                         continue;
                     }
@@ -156,44 +203,48 @@ impl<'a> Fields<'a> {
                         // TODO: investigate removing this
                         let ctx = ctx.clone();
                         async move {
-                            let ctx_field = ctx.with_field(field);
-                            let field_name = ctx_field
-                                .item
-                                .node
-                                .response_key()
-                                .node
-                                .clone()
-                                .into_string();
+                            let mut ctx_field = ctx.with_field(field);
+                            let field_name = ctx_field.item.node.response_key().node.clone();
+
+                            let meta_type = ctx_field
+                                .schema_env
+                                .registry
+                                .types
+                                .get(T::type_name().as_ref());
+                            let meta_field = match meta_type
+                                .and_then(|ty| ty.field_by_name(field.node.name.node.as_str()))
+                            {
+                                Some(meta_field) => meta_field,
+                                None => {
+                                    return Err(Error::Query {
+                                        pos: field.pos,
+                                        path: None,
+                                        err: QueryError::FieldNotFound {
+                                            field_name: field.node.name.node.clone().into_string(),
+                                            object: T::type_name().to_string(),
+                                        },
+                                    })
+                                }
+                            };
+
+                            if let Some(timeout) = meta_field.timeout {
+                                let deadline = Instant::now() + timeout;
+                                ctx_field.deadline = Some(
+                                    ctx_field
+                                        .deadline
+                                        .map_or(deadline, |parent| parent.min(deadline)),
+                                );
+                            }
+
+                            ctx_field.secret |= meta_field.is_secret;
+                            ctx_field.parent_type = meta_type.map(MetaType::name);
 
                             let resolve_info = ResolveInfo {
                                 resolve_id: ctx_field.resolve_id,
                                 path_node: ctx_field.path_node.as_ref().unwrap(),
                                 parent_type: &T::type_name(),
-                                return_type: match ctx_field
-                                    .schema_env
-                                    .registry
-                                    .types
-                                    .get(T::type_name().as_ref())
-                                    .and_then(|ty| ty.field_by_name(field.node.name.node.as_str()))
-                                    .map(|field| &field.ty)
-                                {
-                                    Some(ty) => &ty,
-                                    None => {
-                                        return Err(Error::Query {
-                                            pos: field.pos,
-                                            path: None,
-                                            err: QueryError::FieldNotFound {
-                                                field_name: field
-                                                    .node
-                                                    .name
-                                                    .node
-                                                    .clone()
-                                                    .into_string(),
-                                                object: T::type_name().to_string(),
-                                            },
-                                        })
-                                    }
-                                },
+                                return_type: &meta_field.ty,
+                                is_secret: ctx_field.secret,
                                 schema_env: ctx.schema_env,
                                 query_env: ctx.query_env,
                             };
@@ -204,11 +255,12 @@ impl<'a> Fields<'a> {
                                 .lock()
                                 .resolve_start(&resolve_info);
 
-                            let res = root
-                                .resolve_field(&ctx_field)
-                                .map_ok(move |value| (field_name, value))
-                                .await
-                                .log_error(&ctx_field.query_env.extensions)?;
+                            let res = ctx_field.schema_env.log_resolver_error(
+                                resolve_with_deadline(&ctx_field, root.resolve_field(&ctx_field))
+                                    .map_ok(move |value| (field_name, value))
+                                    .await,
+                                &ctx_field.query_env.extensions,
+                            )?;
 
                             ctx_field
                                 .query_env
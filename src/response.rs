@@ -1,11 +1,12 @@
-use crate::{CacheControl, Error, Result};
+use crate::http::HeaderMap;
+use crate::{CacheControl, Error, Result, Value};
 use serde::Serialize;
 
 /// Query response
 #[derive(Debug, Default)]
 pub struct Response {
     /// Data of query result
-    pub data: serde_json::Value,
+    pub data: Value,
 
     /// Extensions result
     pub extensions: Option<serde_json::Value>,
@@ -15,12 +16,15 @@ pub struct Response {
 
     /// Error
     pub error: Option<Error>,
+
+    /// HTTP headers set by the resolvers
+    pub http_headers: HeaderMap,
 }
 
 impl Response {
     /// Create a new successful response with the data.
     #[must_use]
-    pub fn new(data: impl Into<serde_json::Value>) -> Self {
+    pub fn new(data: impl Into<Value>) -> Self {
         Self {
             data: data.into(),
             ..Default::default()
@@ -38,7 +42,7 @@ impl Response {
 
     /// Create a response from the result of the data and an error.
     #[must_use]
-    pub fn from_result(result: Result<serde_json::Value>) -> Self {
+    pub fn from_result(result: Result<Value>) -> Self {
         match result {
             Ok(data) => Self::new(data),
             Err(e) => Self::from_error(e),
@@ -60,6 +64,15 @@ impl Response {
         }
     }
 
+    /// Set the HTTP headers of the response.
+    #[must_use]
+    pub fn http_headers(self, http_headers: HeaderMap) -> Self {
+        Self {
+            http_headers,
+            ..self
+        }
+    }
+
     /// Returns `true` if the response is ok.
     #[inline]
     pub fn is_ok(&self) -> bool {
@@ -91,6 +104,7 @@ impl From<Error> for Response {
 }
 
 /// Response for batchable queries
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Serialize)]
 #[serde(untagged)]
 pub enum BatchResponse {
@@ -112,6 +126,22 @@ impl BatchResponse {
         }
     }
 
+    /// Get the HTTP headers set by the resolvers of all the responses.
+    pub fn http_headers(&self) -> HeaderMap {
+        match self {
+            BatchResponse::Single(resp) => resp.http_headers.clone(),
+            BatchResponse::Batch(resp) => {
+                let mut headers = HeaderMap::new();
+                for resp in resp {
+                    for (name, value) in &resp.http_headers {
+                        headers.append(name, value.clone());
+                    }
+                }
+                headers
+            }
+        }
+    }
+
     /// Returns `true` if all responses are ok.
     pub fn is_ok(&self) -> bool {
         match self {
@@ -139,15 +169,15 @@ mod tests {
 
     #[test]
     fn test_batch_response_single() {
-        let resp = BatchResponse::Single(Response::new(serde_json::Value::Bool(true)));
+        let resp = BatchResponse::Single(Response::new(Value::Boolean(true)));
         assert_eq!(serde_json::to_string(&resp).unwrap(), r#"{"data":true}"#);
     }
 
     #[test]
     fn test_batch_response_batch() {
         let resp = BatchResponse::Batch(vec![
-            Response::new(serde_json::Value::Bool(true)),
-            Response::new(serde_json::Value::String("1".to_string())),
+            Response::new(Value::Boolean(true)),
+            Response::new(Value::String("1".to_string())),
         ]);
         assert_eq!(
             serde_json::to_string(&resp).unwrap(),
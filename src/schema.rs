@@ -1,27 +1,33 @@
 use crate::context::{Data, ResolveId};
-use crate::extensions::{BoxExtension, ErrorLogger, Extension, Extensions};
+use crate::extensions::{
+    redact_unparsed, BoxExtension, ErrorLogger, Extension, Extensions, RedactedRequest,
+};
 use crate::model::__DirectiveLocation;
+use crate::node::{Base64GlobalIdCodec, GlobalIdCodec};
 use crate::parser::parse_query;
 use crate::parser::types::{
     DocumentOperations, FragmentDefinition, Name, OperationDefinition, OperationType,
 };
-use crate::registry::{MetaDirective, MetaInputValue, Registry};
+use crate::registry::{MetaDirective, MetaInputValue, Registry, RenameRule};
 use crate::resolver_utils::{resolve_object, resolve_object_serial, ObjectType};
-use crate::subscription::collect_subscription_streams;
+use crate::subscription::{collect_subscription_streams, SubscriptionErrorPolicy};
 use crate::types::QueryRoot;
-use crate::validation::{check_rules, CheckResult, ValidationMode};
+use crate::validation::{check_rules, CheckResult, ValidationMode, ValidationRuleFactory, Visitor};
 use crate::{
-    BatchRequest, BatchResponse, CacheControl, ContextBase, Error, Pos, Positioned, QueryEnv,
-    QueryError, Request, Response, Result, SubscriptionType, Type, Variables, ID,
+    BatchRequest, BatchResponse, CacheControl, ContextBase, Error, FieldError, Pos, Positioned,
+    QueryEnv, QueryError, Request, Response, Result, SchemaErrors, SubscriptionType, Type, ID,
 };
 use futures::stream::{self, Stream, StreamExt};
 use indexmap::map::IndexMap;
 use itertools::Itertools;
 use std::any::Any;
+use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
 use std::ops::Deref;
-use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Schema builder
 pub struct SchemaBuilder<Query, Mutation, Subscription> {
@@ -34,7 +40,16 @@ pub struct SchemaBuilder<Query, Mutation, Subscription> {
     complexity: Option<usize>,
     depth: Option<usize>,
     extensions: Vec<Box<dyn Fn() -> BoxExtension + Send + Sync>>,
+    validation_rules: Vec<ValidationRuleFactory>,
     enable_federation: bool,
+    timeout: Option<Duration>,
+    batch_concurrency: Option<usize>,
+    global_id_codec: Box<dyn GlobalIdCodec>,
+    subscription_error_policy: SubscriptionErrorPolicy,
+    mask_errors: bool,
+    rename_fields: Option<RenameRule>,
+    rename_args: Option<RenameRule>,
+    rename_items: Option<RenameRule>,
 }
 
 impl<Query: ObjectType, Mutation: ObjectType, Subscription: SubscriptionType>
@@ -66,6 +81,24 @@ impl<Query: ObjectType, Mutation: ObjectType, Subscription: SubscriptionType>
         self
     }
 
+    /// Set the maximum time the execution of a request may take. By default there is no limit.
+    ///
+    /// It can be overridden for each request with `Request::timeout`. Subscriptions are not
+    /// affected by this limit.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the maximum number of requests of a batch executed at the same time. By default all the
+    /// requests of a batch are executed concurrently.
+    ///
+    /// The responses are always in the same order as the requests.
+    pub fn batch_concurrency(mut self, concurrency: usize) -> Self {
+        self.batch_concurrency = Some(concurrency.max(1));
+        self
+    }
+
     /// Add an extension to the schema.
     pub fn extension<F: Fn() -> E + Send + Sync + 'static, E: Extension>(
         mut self,
@@ -76,6 +109,20 @@ impl<Query: ObjectType, Mutation: ObjectType, Subscription: SubscriptionType>
         self
     }
 
+    /// Add a custom validation rule to the schema.
+    ///
+    /// The factory creates a new instance of the rule for each request. Custom rules run after
+    /// the built-in rules, in every `ValidationMode`.
+    pub fn validation_rule<F, R>(mut self, rule_factory: F) -> Self
+    where
+        F: Fn() -> R + Send + Sync + 'static,
+        R: for<'a> Visitor<'a> + 'static,
+    {
+        self.validation_rules
+            .push(Box::new(move || Box::new(rule_factory())));
+        self
+    }
+
     /// Add a global data that can be accessed in the `Schema`. You access it with `Context::data`.
     pub fn data<D: Any + Send + Sync>(mut self, data: D) -> Self {
         self.data.insert(data);
@@ -94,13 +141,85 @@ impl<Query: ObjectType, Mutation: ObjectType, Subscription: SubscriptionType>
         self
     }
 
+    /// Set the codec of the global ids of Relay nodes, default is `Base64GlobalIdCodec`.
+    pub fn global_id_codec(mut self, codec: impl GlobalIdCodec) -> Self {
+        self.global_id_codec = Box::new(codec);
+        self
+    }
+
+    /// Set what happens to a subscription when resolving one of its events fails, default is
+    /// `SubscriptionErrorPolicy::Terminate`.
+    ///
+    /// It can be overridden for each field with `#[field(error_policy = "...")]`.
+    pub fn subscription_error_policy(mut self, policy: SubscriptionErrorPolicy) -> Self {
+        self.subscription_error_policy = policy;
+        self
+    }
+
+    /// Mask the errors of resolvers that are not marked as user-facing.
+    ///
+    /// Clients receive `Internal server error` and a generated `correlationId` extension instead
+    /// of the message and extensions of the error. The original error is passed on to the
+    /// extensions of the schema with the same `correlationId` added to its extensions, so it can
+    /// be found in the logs. Mark the errors that are meant for clients with
+    /// `FieldError::user_facing`.
+    pub fn mask_errors(mut self) -> Self {
+        self.mask_errors = true;
+        self
+    }
+
+    /// Set the case convention of the fields of objects, interfaces and input objects.
+    ///
+    /// It applies to the fields named after their Rust items by the derive macros, in types
+    /// without a `rename_fields` attribute. The types of this crate, such as the connection types,
+    /// keep their names.
+    pub fn rename_fields(mut self, rule: RenameRule) -> Self {
+        self.rename_fields = Some(rule);
+        self
+    }
+
+    /// Set the case convention of the arguments of fields.
+    ///
+    /// It applies to the arguments named after their Rust items by the derive macros, in types
+    /// without a `rename_args` attribute.
+    pub fn rename_args(mut self, rule: RenameRule) -> Self {
+        self.rename_args = Some(rule);
+        self
+    }
+
+    /// Set the case convention of the items of enums.
+    ///
+    /// It applies to the items named after their Rust variants by the derive macros, in enums
+    /// without a `rename_items` attribute.
+    pub fn rename_items(mut self, rule: RenameRule) -> Self {
+        self.rename_items = Some(rule);
+        self
+    }
+
     /// Build schema.
+    ///
+    /// The type system of the schema is not validated, use [`try_finish`](Self::try_finish) to
+    /// check it against the rules of the GraphQL specification.
     pub fn finish(mut self) -> Schema<Query, Mutation, Subscription> {
         // federation
         if self.enable_federation || self.registry.has_entities() {
             self.registry.create_federation_types();
         }
 
+        // relay nodes
+        if self.registry.has_nodes() {
+            self.registry.create_node_types();
+        }
+
+        if self.rename_fields.is_some() || self.rename_args.is_some() || self.rename_items.is_some()
+        {
+            self.registry.apply_rename_rules(
+                self.rename_fields,
+                self.rename_args,
+                self.rename_items,
+            );
+        }
+
         Schema(Arc::new(SchemaInner {
             validation_mode: self.validation_mode,
             query: self.query,
@@ -108,19 +227,45 @@ impl<Query: ObjectType, Mutation: ObjectType, Subscription: SubscriptionType>
             subscription: self.subscription,
             complexity: self.complexity,
             depth: self.depth,
+            timeout: self.timeout,
+            batch_concurrency: self.batch_concurrency,
             extensions: self.extensions,
+            validation_rules: self.validation_rules,
             env: SchemaEnv(Arc::new(SchemaEnvInner {
                 registry: self.registry,
                 data: self.data,
+                global_id_codec: self.global_id_codec,
+                subscription_error_policy: self.subscription_error_policy,
+                mask_errors: self.mask_errors,
             })),
         }))
     }
+
+    /// Build schema and validate its type system.
+    ///
+    /// Returns every error found, such as two Rust types registered with the same GraphQL name,
+    /// an implementation of an interface which doesn't provide all of its fields, or an object
+    /// without any fields.
+    pub fn try_finish(
+        self,
+    ) -> std::result::Result<Schema<Query, Mutation, Subscription>, SchemaErrors> {
+        let schema = self.finish();
+        let errors = schema.env.registry.check();
+        if errors.is_empty() {
+            Ok(schema)
+        } else {
+            Err(errors.into())
+        }
+    }
 }
 
 #[doc(hidden)]
 pub struct SchemaEnvInner {
     pub registry: Registry,
     pub data: Data,
+    pub global_id_codec: Box<dyn GlobalIdCodec>,
+    pub subscription_error_policy: SubscriptionErrorPolicy,
+    pub mask_errors: bool,
 }
 
 #[doc(hidden)]
@@ -135,6 +280,104 @@ impl Deref for SchemaEnv {
     }
 }
 
+impl SchemaEnv {
+    /// Pass the error of a resolver to the extensions, and mask it if the schema masks errors.
+    #[doc(hidden)]
+    pub fn log_resolver_error<T>(
+        &self,
+        res: Result<T>,
+        extensions: &spin::Mutex<Extensions>,
+    ) -> Result<T> {
+        let err = match res {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+
+        let (pos, path, mut err) = match err {
+            Error::Query {
+                pos,
+                path,
+                err:
+                    QueryError::FieldError {
+                        err,
+                        extended_error,
+                    },
+            } if self.mask_errors => (
+                pos,
+                path,
+                FieldError {
+                    extensions: extended_error,
+                    ..FieldError::new(err)
+                },
+            ),
+            Error::Query {
+                pos,
+                path,
+                err:
+                    QueryError::DetailedFieldError {
+                        err,
+                        extended_error,
+                        user_facing: false,
+                        source,
+                    },
+            } if self.mask_errors => (
+                pos,
+                path,
+                FieldError {
+                    message: err,
+                    extensions: extended_error,
+                    user_facing: false,
+                    source,
+                },
+            ),
+            err => {
+                extensions.lock().error(&err);
+                return Err(err);
+            }
+        };
+
+        let correlation_id = correlation_id();
+        err.extensions = match err.extensions {
+            Some(serde_json::Value::Object(mut map)) => {
+                map.insert("correlationId".to_string(), correlation_id.clone().into());
+                Some(serde_json::Value::Object(map))
+            }
+            None => Some(serde_json::json!({ "correlationId": correlation_id })),
+            extensions => extensions,
+        };
+        let source = err.source.clone();
+        extensions.lock().error(&Error::Query {
+            pos,
+            path: path.clone(),
+            err: err.into(),
+        });
+
+        Err(Error::Query {
+            pos,
+            path,
+            err: FieldError {
+                extensions: Some(serde_json::json!({ "correlationId": correlation_id })),
+                user_facing: true,
+                source,
+                ..FieldError::new("Internal server error")
+            }
+            .into(),
+        })
+    }
+}
+
+/// Generate an id that is unique enough to find an error in the logs.
+fn correlation_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    if let Ok(elapsed) = SystemTime::now().duration_since(UNIX_EPOCH) {
+        hasher.write_u128(elapsed.as_nanos());
+    }
+    format!("{:016x}", hasher.finish())
+}
+
 #[doc(hidden)]
 pub struct SchemaInner<Query, Mutation, Subscription> {
     pub(crate) validation_mode: ValidationMode,
@@ -143,7 +386,10 @@ pub struct SchemaInner<Query, Mutation, Subscription> {
     pub(crate) subscription: Subscription,
     pub(crate) complexity: Option<usize>,
     pub(crate) depth: Option<usize>,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) batch_concurrency: Option<usize>,
     pub(crate) extensions: Vec<Box<dyn Fn() -> BoxExtension + Send + Sync>>,
+    pub(crate) validation_rules: Vec<ValidationRuleFactory>,
     pub(crate) env: SchemaEnv,
 }
 
@@ -210,7 +456,16 @@ where
             complexity: None,
             depth: None,
             extensions: Default::default(),
+            validation_rules: Default::default(),
             enable_federation: false,
+            timeout: None,
+            batch_concurrency: None,
+            global_id_codec: Box::new(Base64GlobalIdCodec),
+            subscription_error_policy: Default::default(),
+            mask_errors: false,
+            rename_fields: None,
+            rename_args: None,
+            rename_items: None,
         }
     }
 
@@ -219,6 +474,7 @@ where
             types: Default::default(),
             directives: Default::default(),
             implements: Default::default(),
+            nodes: Default::default(),
             query_type: Query::type_name().to_string(),
             mutation_type: if Mutation::is_empty() {
                 None
@@ -230,6 +486,11 @@ where
             } else {
                 Some(Subscription::type_name().to_string())
             },
+            type_sources: Default::default(),
+            builtin_types: Default::default(),
+            type_conflicts: Default::default(),
+            node_conflicts: Default::default(),
+            renames: Default::default(),
         };
 
         registry.add_directive(MetaDirective {
@@ -247,10 +508,14 @@ where
                     description: Some("Included when true."),
                     ty: "Boolean!".to_string(),
                     default_value: None,
+                    deprecation: None,
                     validator: None,
+                    is_secret: false,
+                    default_names: None,
                 });
                 args
-            }
+            },
+            is_repeatable: false,
         });
 
         registry.add_directive(MetaDirective {
@@ -268,10 +533,14 @@ where
                     description: Some("Skipped when true."),
                     ty: "Boolean!".to_string(),
                     default_value: None,
+                    deprecation: None,
                     validator: None,
+                    is_secret: false,
+                    default_names: None,
                 });
                 args
-            }
+            },
+            is_repeatable: false,
         });
 
         registry.add_directive(MetaDirective {
@@ -279,6 +548,67 @@ where
             description: Some("Directs the executor to query only when the field exists."),
             locations: vec![__DirectiveLocation::FIELD],
             args: Default::default(),
+            is_repeatable: false,
+        });
+
+        registry.add_directive(MetaDirective {
+            name: "deprecated",
+            description: Some("Marks an element of a GraphQL schema as no longer supported."),
+            locations: vec![
+                __DirectiveLocation::FIELD_DEFINITION,
+                __DirectiveLocation::ARGUMENT_DEFINITION,
+                __DirectiveLocation::INPUT_FIELD_DEFINITION,
+                __DirectiveLocation::ENUM_VALUE
+            ],
+            args: {
+                let mut args = IndexMap::new();
+                args.insert("reason", MetaInputValue {
+                    name: "reason",
+                    description: Some("Explains why this element was deprecated, usually also including a suggestion for how to access supported similar data."),
+                    ty: "String".to_string(),
+                    default_value: Some("\"No longer supported\"".to_string()),
+                    deprecation: None,
+                    validator: None,
+                    is_secret: false,
+                    default_names: None,
+                });
+                args
+            },
+            is_repeatable: false,
+        });
+
+        registry.add_directive(MetaDirective {
+            name: "specifiedBy",
+            description: Some("Exposes a URL that specifies the behaviour of this scalar."),
+            locations: vec![__DirectiveLocation::SCALAR],
+            args: {
+                let mut args = IndexMap::new();
+                args.insert(
+                    "url",
+                    MetaInputValue {
+                        name: "url",
+                        description: Some("The URL that specifies the behaviour of this scalar."),
+                        ty: "String!".to_string(),
+                        default_value: None,
+                        deprecation: None,
+                        validator: None,
+                        is_secret: false,
+                        default_names: None,
+                    },
+                );
+                args
+            },
+            is_repeatable: false,
+        });
+
+        registry.add_directive(MetaDirective {
+            name: "oneOf",
+            description: Some(
+                "Indicates that exactly one field of this input object must be provided and non-null.",
+            ),
+            locations: vec![__DirectiveLocation::INPUT_OBJECT],
+            args: Default::default(),
+            is_repeatable: false,
         });
 
         // register scalars
@@ -313,6 +643,14 @@ where
         Self::create_registry().export_sdl(false)
     }
 
+    /// Returns SDL(Schema Definition Language) of this schema as it was built.
+    ///
+    /// Unlike [`sdl`](Self::sdl), it includes the changes of the `SchemaBuilder`, such as its
+    /// rename rules.
+    pub fn export_sdl(&self) -> String {
+        self.env.registry.export_sdl(false)
+    }
+
     // TODO: Remove the allow
     #[allow(clippy::type_complexity)]
     fn prepare_request(
@@ -334,13 +672,34 @@ where
                 .collect_vec(),
         ));
 
-        extensions
-            .lock()
-            .parse_start(&request.query, &request.variables);
-        let document = parse_query(&request.query)
-            .map_err(Into::<Error>::into)
-            .log_error(&extensions)?;
+        // Secret values can only be located in a parsed query, so the query is parsed before
+        // `parse_start` to give it the redacted query and variables.
+        let document = parse_query(&request.query).map_err(Into::<Error>::into);
+        match &document {
+            Ok(document) => {
+                let redacted = RedactedRequest::new(
+                    &self.env.registry,
+                    &request.query,
+                    document,
+                    &request.variables,
+                );
+                extensions
+                    .lock()
+                    .parse_start(&redacted.query(), &redacted.variables());
+            }
+            Err(_) => {
+                let (query, variables) = redact_unparsed(&request.variables);
+                extensions.lock().parse_start(query, &variables);
+            }
+        }
+        let document = document.log_error(&extensions)?;
         extensions.lock().parse_end(&document);
+        extensions.lock().redacted_request(&RedactedRequest::new(
+            &self.env.registry,
+            &request.query,
+            &document,
+            &request.variables,
+        ));
 
         // check rules
         extensions.lock().validation_start();
@@ -352,7 +711,9 @@ where
             &self.env.registry,
             &document,
             Some(&request.variables),
+            &[&request.data, &self.env.data],
             self.validation_mode,
+            &self.validation_rules,
         )
         .log_error(&extensions)?;
         extensions.lock().validation_end();
@@ -402,27 +763,16 @@ where
         Ok((operation, document.fragments, cache_control, extensions))
     }
 
-    async fn execute_once(
-        &self,
-        operation: Positioned<OperationDefinition>,
-        fragments: HashMap<Name, Positioned<FragmentDefinition>>,
-        extensions: spin::Mutex<Extensions>,
-        variables: Variables,
-        ctx_data: Data,
-    ) -> Response {
+    async fn execute_once(&self, env: QueryEnv, deadline: Option<Instant>) -> Response {
         // execute
         let inc_resolve_id = AtomicUsize::default();
-        let env = QueryEnv::new(
-            extensions,
-            variables,
-            operation,
-            fragments,
-            Arc::new(ctx_data),
-        );
         let ctx = ContextBase {
             path_node: None,
             resolve_id: ResolveId::root(),
             inc_resolve_id: &inc_resolve_id,
+            deadline,
+            secret: false,
+            parent_type: None,
             item: &env.operation.node.selection_set,
             schema_env: &self.env,
             query_env: &env,
@@ -445,21 +795,43 @@ where
 
         env.extensions.lock().execution_end();
         let extensions = env.extensions.lock().result();
+        let http_headers = std::mem::take(&mut *env.http_headers.lock());
+
+        Response::from_result(data)
+            .extensions(extensions)
+            .http_headers(http_headers)
+    }
 
-        Response::from_result(data).extensions(extensions)
+    fn deadline(&self, request: &Request) -> Option<Instant> {
+        request
+            .timeout
+            .or(self.timeout)
+            .map(|timeout| Instant::now() + timeout)
     }
 
     /// Execute an GraphQL query.
     pub async fn execute(&self, request: impl Into<Request>) -> Response {
-        let request = request.into();
+        self.execute_with_shared_data(request.into(), None).await
+    }
+
+    async fn execute_with_shared_data(
+        &self,
+        request: Request,
+        shared_data: Option<Arc<Data>>,
+    ) -> Response {
+        let deadline = self.deadline(&request);
         match self.prepare_request(&request) {
             Ok((operation, fragments, cache_control, extensions)) => self
                 .execute_once(
-                    operation,
-                    fragments,
-                    extensions,
-                    request.variables,
-                    request.data,
+                    QueryEnv::new(
+                        extensions,
+                        request.variables,
+                        operation,
+                        fragments,
+                        Arc::new(request.data),
+                        shared_data,
+                    ),
+                    deadline,
                 )
                 .await
                 .cache_control(cache_control),
@@ -468,15 +840,45 @@ where
     }
 
     /// Execute an GraphQL batch query.
+    ///
+    /// The requests of a batch are executed concurrently, up to the limit set with
+    /// `SchemaBuilder::batch_concurrency`.
     pub async fn execute_batch(&self, batch_request: BatchRequest) -> BatchResponse {
+        self.execute_batch_inner(batch_request, None).await
+    }
+
+    /// Execute an GraphQL batch query, sharing `data` between all the requests of the batch.
+    ///
+    /// This is useful to share a per-request cache, such as a `DataLoader`, between the requests
+    /// of a batch. The data of each request takes precedence over the shared data.
+    pub async fn execute_batch_with_data(
+        &self,
+        batch_request: BatchRequest,
+        data: Data,
+    ) -> BatchResponse {
+        self.execute_batch_inner(batch_request, Some(Arc::new(data)))
+            .await
+    }
+
+    async fn execute_batch_inner(
+        &self,
+        batch_request: BatchRequest,
+        shared_data: Option<Arc<Data>>,
+    ) -> BatchResponse {
         match batch_request {
-            BatchRequest::Single(request) => BatchResponse::Single(self.execute(request).await),
-            BatchRequest::Batch(requests) => BatchResponse::Batch(
-                futures::stream::iter(requests.into_iter())
-                    .then(|request| self.execute(request))
-                    .collect()
-                    .await,
-            ),
+            BatchRequest::Single(request) => {
+                BatchResponse::Single(self.execute_with_shared_data(request, shared_data).await)
+            }
+            BatchRequest::Batch(requests) => {
+                let concurrency = self.batch_concurrency.unwrap_or(requests.len());
+                BatchResponse::Batch(
+                    futures::stream::iter(requests)
+                        .map(|request| self.execute_with_shared_data(request, shared_data.clone()))
+                        .buffered(concurrency.max(1))
+                        .collect()
+                        .await,
+                )
+            }
         }
     }
 
@@ -489,6 +891,7 @@ where
 
         async_stream::stream! {
             let request = request.into();
+            let deadline = schema.deadline(&request);
             let (operation, fragments, cache_control, extensions) = match schema.prepare_request(&request) {
                 Ok(res) => res,
                 Err(err) => {
@@ -499,7 +902,10 @@ where
 
             if operation.node.ty != OperationType::Subscription {
                 yield schema
-                    .execute_once(operation, fragments, extensions, request.variables, request.data)
+                    .execute_once(
+                        QueryEnv::new(extensions, request.variables, operation, fragments, Arc::new(request.data), None),
+                        deadline,
+                    )
                     .await
                     .cache_control(cache_control);
                 return;
@@ -512,6 +918,7 @@ where
                 operation,
                 fragments,
                 ctx_data,
+                None,
             );
 
             let ctx = env.create_context(
@@ -533,14 +940,12 @@ where
 
             env.extensions.lock().execution_end();
 
+            // The error policy of each field, or the default policy of the schema, decides whether
+            // its stream ends after an error. See `collect_subscription_streams`.
             let mut stream = stream::select_all(streams);
             while let Some(data) = stream.next().await {
-                let is_err = data.is_err();
                 let extensions = env.extensions.lock().result();
                 yield Response::from_result(data).extensions(extensions);
-                if is_err {
-                    break;
-                }
             }
         }
     }
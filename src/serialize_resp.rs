@@ -43,6 +43,11 @@ impl Serialize for Error {
                 if let QueryError::FieldError {
                     err,
                     extended_error,
+                }
+                | QueryError::DetailedFieldError {
+                    err,
+                    extended_error,
+                    ..
                 } = err
                 {
                     let mut map = serde_json::Map::new();
@@ -87,16 +92,18 @@ impl Serialize for Error {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Pos;
+    use crate::parser::types::Name;
+    use crate::{Pos, Value};
     use serde_json::json;
 
     #[test]
     fn test_response_data() {
         let resp = Response {
-            data: json!({"ok": true}),
+            data: Value::from_json(json!({"ok": true})).unwrap(),
             extensions: None,
             cache_control: Default::default(),
             error: None,
+            http_headers: Default::default(),
         };
         assert_eq!(
             serde_json::to_value(resp).unwrap(),
@@ -108,6 +115,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_response_data_order() {
+        let resp = Response::new(Value::Object(
+            vec![
+                (Name::new_unchecked("b".to_string()), Value::Boolean(true)),
+                (Name::new_unchecked("a".to_string()), Value::Null),
+            ]
+            .into_iter()
+            .collect(),
+        ));
+        assert_eq!(
+            serde_json::to_string(&resp).unwrap(),
+            r#"{"data":{"b":true,"a":null}}"#
+        );
+    }
+
     #[test]
     fn test_field_error_with_extension() {
         let resp = Response::from(Error::Query {
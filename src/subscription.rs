@@ -1,8 +1,56 @@
 use crate::parser::types::{Selection, TypeCondition};
-use crate::{Context, ContextSelectionSet, Result, Type};
+use crate::registry::MetaType;
+use crate::{Context, ContextSelectionSet, Result, Type, Value};
 use futures::{Stream, StreamExt};
 use std::pin::Pin;
 
+/// What happens to a subscription when resolving one of its events fails.
+///
+/// It can be set for the whole schema with `SchemaBuilder::subscription_error_policy`, and for a
+/// single field with `#[field(error_policy = "...")]`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SubscriptionErrorPolicy {
+    /// Send the error to the client, and end the subscription. This is the default.
+    Terminate,
+
+    /// Drop the event, and wait for the next one. The error is still reported to the extensions.
+    Skip,
+
+    /// Send the error to the client, and wait for the next event.
+    Emit,
+}
+
+impl Default for SubscriptionErrorPolicy {
+    fn default() -> Self {
+        SubscriptionErrorPolicy::Terminate
+    }
+}
+
+impl SubscriptionErrorPolicy {
+    #[doc(hidden)]
+    pub fn apply<'a, T: Send + 'a>(
+        self,
+        stream: impl Stream<Item = Result<T>> + Send + 'a,
+    ) -> impl Stream<Item = Result<T>> + Send + 'a {
+        stream
+            .scan(false, move |terminated, item| {
+                if *terminated {
+                    return futures::future::ready(None);
+                }
+                let item = match (item, self) {
+                    (Err(err), SubscriptionErrorPolicy::Terminate) => {
+                        *terminated = true;
+                        Some(Err(err))
+                    }
+                    (Err(_), SubscriptionErrorPolicy::Skip) => None,
+                    (item, _) => Some(item),
+                };
+                futures::future::ready(Some(item))
+            })
+            .filter_map(futures::future::ready)
+    }
+}
+
 /// Represents a GraphQL subscription object
 pub trait SubscriptionType: Type {
     /// This function returns true of type `EmptySubscription` only.
@@ -11,33 +59,59 @@ pub trait SubscriptionType: Type {
         false
     }
 
+    /// Returns true if the stream of the field already applies a `SubscriptionErrorPolicy`.
+    /// Otherwise the schema applies its default policy to it.
+    #[doc(hidden)]
+    fn applies_error_policy(_field_name: &str) -> bool {
+        false
+    }
+
     #[doc(hidden)]
     fn create_field_stream<'a>(
         &'a self,
         ctx: &'a Context<'a>,
-    ) -> Pin<Box<dyn Stream<Item = Result<serde_json::Value>> + Send + 'a>>;
+    ) -> Pin<Box<dyn Stream<Item = Result<Value>> + Send + 'a>>;
 }
 
 pub(crate) fn collect_subscription_streams<'a, T: SubscriptionType + Send + Sync + 'static>(
     ctx: &ContextSelectionSet<'a>,
     root: &'a T,
-    streams: &mut Vec<Pin<Box<dyn Stream<Item = Result<serde_json::Value>> + Send + 'a>>>,
+    streams: &mut Vec<Pin<Box<dyn Stream<Item = Result<Value>> + Send + 'a>>>,
 ) -> Result<()> {
     for selection in &ctx.item.node.items {
         if ctx.is_skip(selection.node.directives())? {
             continue;
         }
         match &selection.node {
-            Selection::Field(field) => streams.push(Box::pin({
-                let ctx = ctx.clone();
-                async_stream::stream! {
-                    let ctx = ctx.with_field(field);
-                    let mut stream = root.create_field_stream(&ctx);
-                    while let Some(item) = stream.next().await {
-                        yield item;
+            Selection::Field(field) => {
+                let registry = &ctx.schema_env.registry;
+                let parent_type = registry
+                    .types
+                    .get(T::type_name().as_ref())
+                    .map(MetaType::name);
+                let stream = {
+                    let ctx = ctx.clone();
+                    async_stream::stream! {
+                        let mut ctx = ctx.with_field(field);
+                        ctx.parent_type = parent_type;
+                        let mut stream = root.create_field_stream(&ctx);
+                        while let Some(item) = stream.next().await {
+                            yield item;
+                        }
                     }
+                };
+                let code_name = match parent_type {
+                    Some(parent_type) => registry.code_name(parent_type, &field.node.name.node),
+                    None => &field.node.name.node,
+                };
+                if T::applies_error_policy(code_name) {
+                    streams.push(Box::pin(stream));
+                } else {
+                    streams.push(Box::pin(
+                        ctx.schema_env.subscription_error_policy.apply(stream),
+                    ));
                 }
-            })),
+            }
             Selection::FragmentSpread(fragment_spread) => {
                 if let Some(fragment) = ctx
                     .query_env
@@ -79,10 +153,14 @@ pub(crate) fn collect_subscription_streams<'a, T: SubscriptionType + Send + Sync
 }
 
 impl<T: SubscriptionType + Send + Sync> SubscriptionType for &T {
+    fn applies_error_policy(field_name: &str) -> bool {
+        T::applies_error_policy(field_name)
+    }
+
     fn create_field_stream<'a>(
         &'a self,
         ctx: &'a Context<'a>,
-    ) -> Pin<Box<dyn Stream<Item = Result<serde_json::Value>> + Send + 'a>> {
+    ) -> Pin<Box<dyn Stream<Item = Result<Value>> + Send + 'a>> {
         T::create_field_stream(*self, ctx)
     }
 }
@@ -5,7 +5,8 @@ use crate::resolver_utils::{resolve_object, ObjectType};
 use crate::type_mark::TypeMarkObject;
 use crate::types::connection::{CursorType, EmptyFields};
 use crate::{
-    registry, Context, ContextSelectionSet, FieldResult, OutputValueType, Positioned, Result, Type,
+    registry, Context, ContextSelectionSet, FieldError, FieldResult, OutputValueType, Positioned,
+    Result, Type, Value,
 };
 use futures::{Stream, StreamExt, TryStreamExt};
 use indexmap::map::IndexMap;
@@ -157,6 +158,9 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            timeout: None,
+                            is_secret: false,
+                            default_names: None,
                         },
                     );
 
@@ -174,6 +178,9 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            timeout: None,
+                            is_secret: false,
+                            default_names: None,
                         },
                     );
 
@@ -196,13 +203,20 @@ where
     EC: ObjectType + Sync + Send,
     EE: ObjectType + Sync + Send,
 {
-    async fn resolve_field(&self, ctx: &Context<'_>) -> Result<serde_json::Value> {
+    async fn resolve_field(&self, ctx: &Context<'_>) -> Result<Value> {
         if ctx.item.node.name.node == "pageInfo" {
+            let encode_cursor = |edge: Option<&Edge<C, T, EE>>| {
+                edge.map(|edge| edge.cursor.try_encode_cursor()).transpose()
+            };
+            let into_error = |err: C::Error| {
+                FieldError::new(err.to_string())
+                    .into_error_with_path(ctx.item.pos, ctx.path_node.as_ref())
+            };
             let page_info = PageInfo {
                 has_previous_page: self.has_previous_page,
                 has_next_page: self.has_next_page,
-                start_cursor: self.edges.first().map(|edge| edge.cursor.encode_cursor()),
-                end_cursor: self.edges.last().map(|edge| edge.cursor.encode_cursor()),
+                start_cursor: encode_cursor(self.edges.first()).map_err(into_error)?,
+                end_cursor: encode_cursor(self.edges.last()).map_err(into_error)?,
             };
             let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
             return OutputValueType::resolve(&page_info, &ctx_obj, ctx.item).await;
@@ -227,7 +241,7 @@ where
         &self,
         ctx: &ContextSelectionSet<'_>,
         _field: &Positioned<Field>,
-    ) -> Result<serde_json::Value> {
+    ) -> Result<Value> {
         resolve_object(ctx, self).await
     }
 }
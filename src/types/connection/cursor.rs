@@ -1,14 +1,24 @@
 use crate::ID;
+#[cfg(feature = "signed_cursor")]
+use hmac::{Hmac, Mac, NewMac};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+#[cfg(feature = "signed_cursor")]
+use sha2::Sha256;
 use std::convert::Infallible;
 use std::fmt::Display;
+#[cfg(feature = "signed_cursor")]
+use std::marker::PhantomData;
 use std::num::ParseIntError;
+use std::ops::{Deref, DerefMut};
+use thiserror::Error;
 
 /// Cursor type
 ///
 /// A custom scalar that serializes as a string.
 /// https://relay.dev/graphql/connections.htm#sec-Cursor
 pub trait CursorType: Sized {
-    /// Error type for `decode_cursor`.
+    /// Error type for `decode_cursor` and `try_encode_cursor`.
     type Error: Display;
 
     /// Decode cursor from string.
@@ -16,6 +26,14 @@ pub trait CursorType: Sized {
 
     /// Encode cursor to string.
     fn encode_cursor(&self) -> String;
+
+    /// Encode cursor to string, or fail if the cursor cannot be encoded.
+    ///
+    /// Connections encode their cursors with this method, so a failure is returned as a field
+    /// error. The default implementation calls `encode_cursor`.
+    fn try_encode_cursor(&self) -> Result<String, Self::Error> {
+        Ok(self.encode_cursor())
+    }
 }
 
 impl CursorType for usize {
@@ -53,3 +71,211 @@ impl CursorType for ID {
         self.to_string()
     }
 }
+
+/// Error returned when decoding an [`OpaqueCursor`](struct.OpaqueCursor.html) or a
+/// [`SignedCursor`](struct.SignedCursor.html) fails.
+#[derive(Debug, Error)]
+pub enum CursorError {
+    /// The cursor is not valid base64.
+    #[error("Invalid cursor encoding")]
+    Encoding,
+
+    /// The content of the cursor cannot be deserialized.
+    #[error("Invalid cursor data: {0}")]
+    Data(serde_json::Error),
+
+    /// The value of the cursor cannot be serialized.
+    #[error("Failed to serialize the cursor value: {0}")]
+    Serialize(serde_json::Error),
+
+    /// The signature of the cursor is missing or does not match its content.
+    #[error("Invalid cursor signature")]
+    Signature,
+}
+
+fn encode_payload<T: Serialize>(value: &T) -> Result<String, CursorError> {
+    let data = serde_json::to_vec(value).map_err(CursorError::Serialize)?;
+    Ok(base64::encode_config(data, base64::URL_SAFE_NO_PAD))
+}
+
+fn expect_encoded(res: Result<String, CursorError>) -> String {
+    // An empty payload would still decode, so a value that can't be serialized is a bug, not a
+    // cursor.
+    res.unwrap_or_else(|err| panic!("{}", err))
+}
+
+fn decode_payload<T: DeserializeOwned>(s: &str) -> Result<T, CursorError> {
+    let data =
+        base64::decode_config(s, base64::URL_SAFE_NO_PAD).map_err(|_| CursorError::Encoding)?;
+    serde_json::from_slice(&data).map_err(CursorError::Data)
+}
+
+/// A cursor holding any serializable value, encoded as an opaque base64 string.
+///
+/// This is useful for keyset pagination over composite keys. Note that clients can decode and
+/// forge these cursors, use [`SignedCursor`](struct.SignedCursor.html) if that is a problem.
+///
+/// # Panics
+///
+/// `encode_cursor` panics if the value cannot be serialized to JSON, for example a map with
+/// non-string keys. Connections use `try_encode_cursor` instead, which returns the error.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::connection::*;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Key {
+///     created_at: i64,
+///     id: i32,
+/// }
+///
+/// let cursor = OpaqueCursor(Key { created_at: 100, id: 1 });
+/// let s = cursor.encode_cursor();
+/// let key = OpaqueCursor::<Key>::decode_cursor(&s).unwrap();
+/// assert_eq!((key.created_at, key.id), (100, 1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct OpaqueCursor<T>(pub T);
+
+impl<T> Deref for OpaqueCursor<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for OpaqueCursor<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> CursorType for OpaqueCursor<T> {
+    type Error = CursorError;
+
+    fn decode_cursor(s: &str) -> Result<Self, Self::Error> {
+        decode_payload(s).map(OpaqueCursor)
+    }
+
+    fn encode_cursor(&self) -> String {
+        expect_encoded(self.try_encode_cursor())
+    }
+
+    fn try_encode_cursor(&self) -> Result<String, Self::Error> {
+        encode_payload(&self.0)
+    }
+}
+
+/// The secret key used to sign and verify a [`SignedCursor`](struct.SignedCursor.html).
+///
+/// The key is part of the cursor type so that cursors can be decoded without access to the
+/// context, but it can still be loaded at runtime, for example from the environment.
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "signed_cursor")))]
+#[cfg(feature = "signed_cursor")]
+pub trait CursorKey: Send + Sync + 'static {
+    /// Returns the secret key.
+    fn key() -> &'static [u8];
+}
+
+/// A cursor holding any serializable value, signed with HMAC-SHA256.
+///
+/// Unlike [`OpaqueCursor`](struct.OpaqueCursor.html), a cursor that was not produced by the
+/// server, or that was modified by the client, fails to decode.
+///
+/// # Panics
+///
+/// Like [`OpaqueCursor`](struct.OpaqueCursor.html), `encode_cursor` panics if the value cannot be
+/// serialized to JSON, and `try_encode_cursor` returns the error.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::connection::*;
+///
+/// struct Key;
+///
+/// impl CursorKey for Key {
+///     fn key() -> &'static [u8] {
+///         b"secret"
+///     }
+/// }
+///
+/// let s = SignedCursor::<(String, i32), Key>::new(("tenant-1".to_string(), 10)).encode_cursor();
+/// let cursor = SignedCursor::<(String, i32), Key>::decode_cursor(&s).unwrap();
+/// assert_eq!(cursor.0, ("tenant-1".to_string(), 10));
+///
+/// let forged = OpaqueCursor(("tenant-2".to_string(), 10)).encode_cursor();
+/// assert!(SignedCursor::<(String, i32), Key>::decode_cursor(&forged).is_err());
+/// ```
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "signed_cursor")))]
+#[cfg(feature = "signed_cursor")]
+pub struct SignedCursor<T, K>(pub T, PhantomData<K>);
+
+#[cfg(feature = "signed_cursor")]
+impl<T, K> SignedCursor<T, K> {
+    /// Create a signed cursor.
+    pub fn new(value: T) -> Self {
+        Self(value, PhantomData)
+    }
+
+    /// Consumes this cursor, returning the value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[cfg(feature = "signed_cursor")]
+impl<T, K> Deref for SignedCursor<T, K> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(feature = "signed_cursor")]
+impl<T, K: CursorKey> SignedCursor<T, K> {
+    fn mac(payload: &str) -> Hmac<Sha256> {
+        let mut mac =
+            Hmac::<Sha256>::new_varkey(K::key()).expect("HMAC can take a key of any size");
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+#[cfg(feature = "signed_cursor")]
+impl<T: Serialize + DeserializeOwned, K: CursorKey> CursorType for SignedCursor<T, K> {
+    type Error = CursorError;
+
+    fn decode_cursor(s: &str) -> Result<Self, Self::Error> {
+        let mut parts = s.splitn(2, '.');
+        let payload = parts.next().unwrap_or_default();
+        let signature = parts.next().ok_or(CursorError::Signature)?;
+        let signature = base64::decode_config(signature, base64::URL_SAFE_NO_PAD)
+            .map_err(|_| CursorError::Encoding)?;
+        Self::mac(payload)
+            .verify(&signature)
+            .map_err(|_| CursorError::Signature)?;
+        decode_payload(payload).map(Self::new)
+    }
+
+    fn encode_cursor(&self) -> String {
+        expect_encoded(self.try_encode_cursor())
+    }
+
+    fn try_encode_cursor(&self) -> Result<String, Self::Error> {
+        let payload = encode_payload(&self.0)?;
+        let signature = Self::mac(&payload).finalize().into_bytes();
+        Ok(format!(
+            "{}.{}",
+            payload,
+            base64::encode_config(signature, base64::URL_SAFE_NO_PAD)
+        ))
+    }
+}
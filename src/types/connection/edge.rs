@@ -3,7 +3,10 @@ use crate::parser::types::Field;
 use crate::resolver_utils::{resolve_object, ObjectType};
 use crate::type_mark::TypeMarkObject;
 use crate::types::connection::CursorType;
-use crate::{registry, Context, ContextSelectionSet, OutputValueType, Positioned, Result, Type};
+use crate::{
+    registry, Context, ContextSelectionSet, FieldError, OutputValueType, Positioned, Result, Type,
+    Value,
+};
 use indexmap::map::IndexMap;
 use std::borrow::Cow;
 
@@ -75,6 +78,9 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            timeout: None,
+                            is_secret: false,
+                            default_names: None,
                         },
                     );
 
@@ -90,6 +96,9 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            timeout: None,
+                            is_secret: false,
+                            default_names: None,
                         },
                     );
 
@@ -111,12 +120,19 @@ where
     T: OutputValueType + Send + Sync,
     E: ObjectType + Sync + Send,
 {
-    async fn resolve_field(&self, ctx: &Context<'_>) -> Result<serde_json::Value> {
+    async fn resolve_field(&self, ctx: &Context<'_>) -> Result<Value> {
         if ctx.item.node.name.node == "node" {
             let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
             return OutputValueType::resolve(&self.node, &ctx_obj, ctx.item).await;
         } else if ctx.item.node.name.node == "cursor" {
-            return Ok(self.cursor.encode_cursor().into());
+            return self
+                .cursor
+                .try_encode_cursor()
+                .map(Value::String)
+                .map_err(|err| {
+                    FieldError::new(err.to_string())
+                        .into_error_with_path(ctx.item.pos, ctx.path_node.as_ref())
+                });
         }
 
         self.additional_fields.resolve_field(ctx).await
@@ -134,7 +150,7 @@ where
         &self,
         ctx: &ContextSelectionSet<'_>,
         _field: &Positioned<Field>,
-    ) -> Result<serde_json::Value> {
+    ) -> Result<Value> {
         resolve_object(ctx, self).await
     }
 }
@@ -4,13 +4,17 @@ mod connection_type;
 mod cursor;
 mod edge;
 mod page_info;
+mod slice;
 
-use crate::{FieldResult, SimpleObject};
+use crate::{FieldError, FieldResult, SimpleObject};
 pub use connection_type::Connection;
-pub use cursor::CursorType;
+pub use cursor::{CursorError, CursorType, OpaqueCursor};
+#[cfg(feature = "signed_cursor")]
+pub use cursor::{CursorKey, SignedCursor};
 pub use edge::Edge;
 use futures::Future;
 pub use page_info::PageInfo;
+pub use slice::{query_iter, query_offset_limit, FromTotalCount, TotalCount};
 use std::fmt::Display;
 
 /// Empty additional fields
@@ -102,13 +106,31 @@ where
     F: FnOnce(Option<Cursor>, Option<Cursor>, Option<usize>, Option<usize>) -> R,
     R: Future<Output = FieldResult<Connection<Cursor, Node, ConnectionFields, EdgeFields>>>,
 {
+    let (after, before, first, last) = parse_arguments(after, before, first, last)?;
+    f(after, before, first, last).await
+}
+
+/// Validates the pagination arguments and decodes the cursors.
+#[allow(clippy::type_complexity)]
+fn parse_arguments<Cursor: CursorType>(
+    after: Option<String>,
+    before: Option<String>,
+    first: Option<i32>,
+    last: Option<i32>,
+) -> FieldResult<(Option<Cursor>, Option<Cursor>, Option<usize>, Option<usize>)> {
     if first.is_some() && last.is_some() {
-        return Err("The \"first\" and \"last\" parameters cannot exist at the same time".into());
+        return Err(FieldError::new(
+            "The \"first\" and \"last\" parameters cannot exist at the same time",
+        )
+        .user_facing());
     }
 
     let first = match first {
         Some(first) if first < 0 => {
-            return Err("The \"first\" parameter must be a non-negative number".into());
+            return Err(
+                FieldError::new("The \"first\" parameter must be a non-negative number")
+                    .user_facing(),
+            );
         }
         Some(first) => Some(first as usize),
         None => None,
@@ -116,21 +138,36 @@ where
 
     let last = match last {
         Some(last) if last < 0 => {
-            return Err("The \"last\" parameter must be a non-negative number".into());
+            return Err(
+                FieldError::new("The \"last\" parameter must be a non-negative number")
+                    .user_facing(),
+            );
         }
         Some(last) => Some(last as usize),
         None => None,
     };
 
     let before = match before {
-        Some(before) => Some(Cursor::decode_cursor(&before)?),
+        Some(before) => Some(Cursor::decode_cursor(&before).map_err(|err| {
+            FieldError::new(format!(
+                "The \"before\" parameter is not a valid cursor: {}",
+                err
+            ))
+            .user_facing()
+        })?),
         None => None,
     };
 
     let after = match after {
-        Some(after) => Some(Cursor::decode_cursor(&after)?),
+        Some(after) => Some(Cursor::decode_cursor(&after).map_err(|err| {
+            FieldError::new(format!(
+                "The \"after\" parameter is not a valid cursor: {}",
+                err
+            ))
+            .user_facing()
+        })?),
         None => None,
     };
 
-    f(after, before, first, last).await
+    Ok((after, before, first, last))
 }
@@ -0,0 +1,224 @@
+use crate::connection::{parse_arguments, Connection, Edge, EmptyFields};
+use crate::{FieldResult, SimpleObject};
+use futures::Future;
+
+/// Additional connection fields exposing the total number of items
+///
+/// Use it as the `ConnectionFields` of the connections built by [`query_iter`](fn.query_iter.html)
+/// or [`query_offset_limit`](fn.query_offset_limit.html) to add a `totalCount` field.
+#[derive(SimpleObject)]
+#[graphql(internal)]
+pub struct TotalCount {
+    /// The total number of items, regardless of pagination.
+    pub total_count: u64,
+}
+
+/// Additional connection fields that can be built from the total number of items.
+pub trait FromTotalCount {
+    /// Create the fields from the total number of items.
+    fn from_total_count(total_count: usize) -> Self;
+}
+
+impl FromTotalCount for EmptyFields {
+    fn from_total_count(_total_count: usize) -> Self {
+        EmptyFields
+    }
+}
+
+impl FromTotalCount for TotalCount {
+    fn from_total_count(total_count: usize) -> Self {
+        TotalCount {
+            total_count: total_count as u64,
+        }
+    }
+}
+
+/// Computes the range of the requested page within `count` items.
+fn page_range(
+    after: Option<usize>,
+    before: Option<usize>,
+    first: Option<usize>,
+    last: Option<usize>,
+    count: usize,
+) -> (usize, usize) {
+    let mut start = after.map(|after| after.saturating_add(1)).unwrap_or(0);
+    let mut end = before.unwrap_or(count).min(count);
+    start = start.min(end);
+    if let Some(first) = first {
+        end = end.min(start.saturating_add(first));
+    }
+    if let Some(last) = last {
+        start = start.max(end.saturating_sub(last));
+    }
+    (start, end)
+}
+
+fn new_connection<T, EC: FromTotalCount>(
+    start: usize,
+    end: usize,
+    count: usize,
+    nodes: impl IntoIterator<Item = T>,
+) -> Connection<usize, T, EC> {
+    let mut connection =
+        Connection::with_additional_fields(start > 0, end < count, EC::from_total_count(count));
+    connection.append(
+        nodes
+            .into_iter()
+            .take(end - start)
+            .enumerate()
+            .map(|(idx, node)| Edge::new(start + idx, node)),
+    );
+    connection
+}
+
+/// Builds a connection from an iterator of known length, such as a slice or a `Vec`.
+///
+/// The cursor of each edge is the index of its node. The connection fields can be either
+/// `EmptyFields` or [`TotalCount`](struct.TotalCount.html).
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::connection::*;
+///
+/// struct QueryRoot {
+///     numbers: Vec<i32>,
+/// }
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn numbers(&self,
+///         after: Option<String>,
+///         before: Option<String>,
+///         first: Option<i32>,
+///         last: Option<i32>
+///     ) -> FieldResult<Connection<usize, &i32, TotalCount>> {
+///         query_iter(after, before, first, last, &self.numbers)
+///     }
+/// }
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let schema = Schema::new(QueryRoot { numbers: (0..10).collect() }, EmptyMutation, EmptySubscription);
+///
+///     assert_eq!(schema.execute(r#"{ numbers(after: "7") { totalCount edges { node } pageInfo { hasNextPage } } }"#).await.into_result().unwrap().data, serde_json::json!({
+///         "numbers": {
+///             "totalCount": 10,
+///             "edges": [{"node": 8}, {"node": 9}],
+///             "pageInfo": {"hasNextPage": false},
+///         },
+///     }));
+/// }
+/// ```
+pub fn query_iter<I, T, EC>(
+    after: Option<String>,
+    before: Option<String>,
+    first: Option<i32>,
+    last: Option<i32>,
+    items: I,
+) -> FieldResult<Connection<usize, T, EC>>
+where
+    I: IntoIterator<Item = T>,
+    I::IntoIter: ExactSizeIterator,
+    EC: FromTotalCount,
+{
+    let (after, before, first, last) = parse_arguments(after, before, first, last)?;
+    let items = items.into_iter();
+    let count = items.len();
+    let (start, end) = page_range(after, before, first, last, count);
+    Ok(new_connection(start, end, count, items.skip(start)))
+}
+
+/// Builds a connection from a function returning the total number of items and a function
+/// fetching the items at a given offset, such as a SQL `COUNT(*)` query and a query with
+/// `OFFSET` and `LIMIT`.
+///
+/// The cursor of each edge is the offset of its node. The connection fields can be either
+/// `EmptyFields` or [`TotalCount`](struct.TotalCount.html). The fetch function is not called if
+/// the page is empty.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::connection::*;
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn numbers(&self,
+///         after: Option<String>,
+///         before: Option<String>,
+///         first: Option<i32>,
+///         last: Option<i32>
+///     ) -> FieldResult<Connection<usize, i32>> {
+///         query_offset_limit(
+///             after, before, first, last,
+///             || async { Ok(10000) },
+///             |offset, limit| async move { Ok((offset..offset + limit).map(|n| n as i32)) },
+///         ).await
+///     }
+/// }
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+///
+///     assert_eq!(schema.execute("{ numbers(last: 2) { edges { node } pageInfo { hasPreviousPage } } }").await.into_result().unwrap().data, serde_json::json!({
+///         "numbers": {
+///             "edges": [{"node": 9998}, {"node": 9999}],
+///             "pageInfo": {"hasPreviousPage": true},
+///         },
+///     }));
+/// }
+/// ```
+pub async fn query_offset_limit<T, EC, C, CR, F, FR, I>(
+    after: Option<String>,
+    before: Option<String>,
+    first: Option<i32>,
+    last: Option<i32>,
+    count: C,
+    fetch: F,
+) -> FieldResult<Connection<usize, T, EC>>
+where
+    EC: FromTotalCount,
+    C: FnOnce() -> CR,
+    CR: Future<Output = FieldResult<usize>>,
+    F: FnOnce(usize, usize) -> FR,
+    FR: Future<Output = FieldResult<I>>,
+    I: IntoIterator<Item = T>,
+{
+    let (after, before, first, last) = parse_arguments(after, before, first, last)?;
+    let count = count().await?;
+    let (start, end) = page_range(after, before, first, last, count);
+    if start == end {
+        return Ok(new_connection(start, end, count, Vec::new()));
+    }
+    let nodes = fetch(start, end - start).await?;
+    Ok(new_connection(start, end, count, nodes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_range() {
+        assert_eq!(page_range(None, None, None, None, 10), (0, 10));
+        assert_eq!(page_range(None, None, Some(3), None, 10), (0, 3));
+        assert_eq!(page_range(None, None, None, Some(3), 10), (7, 10));
+        assert_eq!(page_range(Some(2), None, Some(3), None, 10), (3, 6));
+        assert_eq!(page_range(None, Some(5), None, Some(3), 10), (2, 5));
+        assert_eq!(page_range(Some(2), Some(5), None, None, 10), (3, 5));
+        assert_eq!(page_range(Some(2), Some(5), Some(10), None, 10), (3, 5));
+        assert_eq!(page_range(Some(2), Some(5), None, Some(1), 10), (4, 5));
+        assert_eq!(page_range(Some(9), None, Some(3), None, 10), (10, 10));
+        assert_eq!(page_range(Some(20), None, None, None, 10), (10, 10));
+        assert_eq!(page_range(Some(5), Some(3), None, None, 10), (3, 3));
+        assert_eq!(page_range(None, Some(20), None, Some(2), 10), (8, 10));
+        assert_eq!(page_range(None, None, Some(0), None, 10), (0, 0));
+        assert_eq!(page_range(Some(usize::MAX), None, None, None, 10), (10, 10));
+    }
+}
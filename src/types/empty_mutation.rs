@@ -3,7 +3,7 @@ use crate::resolver_utils::ObjectType;
 use crate::type_mark::TypeMarkObject;
 use crate::{
     registry, Context, ContextSelectionSet, Error, OutputValueType, Positioned, QueryError, Result,
-    Type,
+    Type, Value,
 };
 use std::borrow::Cow;
 
@@ -49,7 +49,7 @@ impl ObjectType for EmptyMutation {
         true
     }
 
-    async fn resolve_field(&self, _ctx: &Context<'_>) -> Result<serde_json::Value> {
+    async fn resolve_field(&self, _ctx: &Context<'_>) -> Result<Value> {
         unreachable!()
     }
 }
@@ -60,7 +60,7 @@ impl OutputValueType for EmptyMutation {
         &self,
         _ctx: &ContextSelectionSet<'_>,
         field: &Positioned<Field>,
-    ) -> Result<serde_json::Value> {
+    ) -> Result<Value> {
         Err(Error::Query {
             pos: field.pos,
             path: None,
@@ -1,5 +1,5 @@
 use crate::type_mark::TypeMarkSubscription;
-use crate::{registry, Context, Error, Pos, QueryError, Result, SubscriptionType, Type};
+use crate::{registry, Context, Error, Pos, QueryError, Result, SubscriptionType, Type, Value};
 use futures::{stream, Stream};
 use std::borrow::Cow;
 use std::pin::Pin;
@@ -35,7 +35,7 @@ impl SubscriptionType for EmptySubscription {
     fn create_field_stream<'a>(
         &'a self,
         _ctx: &'a Context<'a>,
-    ) -> Pin<Box<dyn Stream<Item = Result<serde_json::Value>> + Send + 'a>>
+    ) -> Pin<Box<dyn Stream<Item = Result<Value>> + Send + 'a>>
     where
         Self: Send + Sync + 'static + Sized,
     {
@@ -2,7 +2,8 @@ use crate::parser::types::Name;
 use crate::{
     InputValueError, InputValueResult, InputValueType, OutputValueType, Scalar, ScalarType, Value,
 };
-use std::collections::{BTreeMap, HashMap};
+use indexmap::IndexMap;
+use std::collections::HashMap;
 
 /// A scalar that can represent any JSON Object value.
 #[Scalar(internal, name = "JSONObject")]
@@ -24,7 +25,7 @@ where
     }
 
     fn to_value(&self) -> Value {
-        let mut map = BTreeMap::new();
+        let mut map = IndexMap::new();
         for (name, value) in self {
             if let Ok(name) = Name::new(name.clone()) {
                 map.insert(name, value.to_value());
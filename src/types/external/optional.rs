@@ -21,6 +21,19 @@ impl<T: Type> Type for Option<T> {
 }
 
 impl<T: InputValueType> InputValueType for Option<T> {
+    fn input_type_name() -> Cow<'static, str> {
+        T::input_type_name()
+    }
+
+    fn qualified_input_type_name() -> String {
+        T::input_type_name().to_string()
+    }
+
+    fn create_input_type_info(registry: &mut registry::Registry) -> String {
+        T::create_input_type_info(registry);
+        T::input_type_name().to_string()
+    }
+
     fn parse(value: Option<Value>) -> InputValueResult<Self> {
         match value.unwrap_or_default() {
             Value::Null => Ok(None),
@@ -42,11 +55,11 @@ impl<T: OutputValueType + Sync> OutputValueType for Option<T> {
         &self,
         ctx: &ContextSelectionSet<'_>,
         field: &Positioned<Field>,
-    ) -> Result<serde_json::Value> where {
+    ) -> Result<Value> where {
         if let Some(inner) = self {
             OutputValueType::resolve(inner, ctx, field).await
         } else {
-            Ok(serde_json::Value::Null)
+            Ok(Value::Null)
         }
     }
 }
@@ -85,6 +85,7 @@ impl<T> Type for OutputJson<T> {
             name: Self::type_name().to_string(),
             description: None,
             is_valid: |_| true,
+            specified_by_url: None,
         })
     }
 }
@@ -95,8 +96,11 @@ impl<T: Serialize + Send + Sync> OutputValueType for OutputJson<T> {
         &self,
         _ctx: &ContextSelectionSet<'_>,
         _field: &Positioned<Field>,
-    ) -> Result<serde_json::Value> {
-        Ok(serde_json::to_value(&self.0).unwrap_or_else(|_| serde_json::Value::Null))
+    ) -> Result<Value> {
+        Ok(serde_json::to_value(&self.0)
+            .ok()
+            .and_then(|json| Value::from_json(json).ok())
+            .unwrap_or(Value::Null))
     }
 }
 
@@ -4,7 +4,7 @@ use crate::resolver_utils::{resolve_object, ObjectType};
 use crate::type_mark::{TypeMarkObject, TypeMarkSubscription};
 use crate::{
     CacheControl, Context, ContextSelectionSet, Error, OutputValueType, Positioned, QueryError,
-    Result, SimpleObject, Subscription, SubscriptionType, Type,
+    Result, SimpleObject, Subscription, SubscriptionType, Type, Value,
 };
 use futures::{future::Either, stream, Stream, StreamExt};
 use indexmap::IndexMap;
@@ -74,7 +74,7 @@ where
     A: ObjectType + Send + Sync,
     B: ObjectType + Send + Sync,
 {
-    async fn resolve_field(&self, ctx: &Context<'_>) -> Result<serde_json::Value> {
+    async fn resolve_field(&self, ctx: &Context<'_>) -> Result<Value> {
         match self.0.resolve_field(ctx).await {
             Ok(value) => Ok(value),
             Err(Error::Query {
@@ -96,7 +96,7 @@ where
         &self,
         ctx: &ContextSelectionSet<'_>,
         _field: &Positioned<Field>,
-    ) -> Result<serde_json::Value> {
+    ) -> Result<Value> {
         resolve_object(ctx, self).await
     }
 }
@@ -108,10 +108,14 @@ where
     A: SubscriptionType + Send + Sync,
     B: SubscriptionType + Send + Sync,
 {
+    fn applies_error_policy(field_name: &str) -> bool {
+        A::applies_error_policy(field_name) || B::applies_error_policy(field_name)
+    }
+
     fn create_field_stream<'a>(
         &'a self,
         ctx: &'a Context<'a>,
-    ) -> Pin<Box<dyn Stream<Item = Result<serde_json::Value>> + Send + 'a>> {
+    ) -> Pin<Box<dyn Stream<Item = Result<Value>> + Send + 'a>> {
         let left_stream = self.0.create_field_stream(ctx);
         let mut right_stream = Some(self.1.create_field_stream(ctx));
         Box::pin(left_stream.flat_map(move |res| match res {
@@ -1,6 +1,7 @@
 //! Useful GraphQL types.
 
 pub mod connection;
+pub mod node;
 
 mod any;
 mod empty_mutation;
@@ -0,0 +1,197 @@
+//! Types for Relay global object identification
+//!
+//! **Reference:** <https://relay.dev/graphql/objectidentification.htm>
+//!
+//! When an object is declared with `node(loader = "...")`, the schema adds the `Node` interface and
+//! the `node` and `nodes` query fields. A type named `Node` or query fields with these names that
+//! are already defined by the schema are kept, and reported by
+//! [`SchemaBuilder::try_finish`](crate::SchemaBuilder::try_finish). Node types must have an
+//! `id: ID!` field, which is checked by `try_finish` like any other interface field.
+
+use crate::parser::types::Field;
+use crate::registry::Registry;
+use crate::{
+    Context, ContextSelectionSet, FieldError, OutputValueType, Positioned, Result, Type, Value, ID,
+};
+use std::borrow::Cow;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// A globally unique object id, made of the name of the GraphQL type and the id of the object
+/// within that type.
+///
+/// It is output as an opaque `ID` encoded by the [`GlobalIdCodec`](trait.GlobalIdCodec.html) of
+/// the schema, which is [`Base64GlobalIdCodec`](struct.Base64GlobalIdCodec.html) by default.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::node::GlobalId;
+///
+/// struct User {
+///     id: i32,
+/// }
+///
+/// #[Object(node(loader = "load_user"))]
+/// impl User {
+///     async fn id(&self) -> GlobalId {
+///         GlobalId::of::<Self>(self.id)
+///     }
+///
+///     async fn name(&self) -> String {
+///         format!("user{}", self.id)
+///     }
+/// }
+///
+/// async fn load_user(_ctx: &Context<'_>, id: &str) -> FieldResult<Option<User>> {
+///     Ok(Some(User { id: id.parse()? }))
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn me(&self) -> User {
+///         User { id: 1 }
+///     }
+/// }
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+///
+///     // "VXNlcjox" is the base64 encoding of "User:1".
+///     assert_eq!(schema.execute(r#"{ node(id: "VXNlcjox") { id ... on User { name } } }"#).await.into_result().unwrap().data, serde_json::json!({
+///         "node": { "id": "VXNlcjox", "name": "user1" },
+///     }));
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct GlobalId {
+    /// The name of the GraphQL type of the object.
+    pub type_name: String,
+
+    /// The id of the object within its type.
+    pub id: String,
+}
+
+impl GlobalId {
+    /// Create a global id from a type name and the id of the object within that type.
+    pub fn new(type_name: impl Into<String>, id: impl Display) -> Self {
+        Self {
+            type_name: type_name.into(),
+            id: id.to_string(),
+        }
+    }
+
+    /// Create a global id for an object of the GraphQL type `T`.
+    pub fn of<T: Type>(id: impl Display) -> Self {
+        Self::new(T::type_name().into_owned(), id)
+    }
+
+    /// Parse the id of the object within its type.
+    pub fn parse_id<T: FromStr>(&self) -> std::result::Result<T, T::Err> {
+        self.id.parse()
+    }
+}
+
+impl Display for GlobalId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.type_name, self.id)
+    }
+}
+
+impl Type for GlobalId {
+    fn type_name() -> Cow<'static, str> {
+        ID::type_name()
+    }
+
+    fn create_type_info(registry: &mut Registry) -> String {
+        ID::create_type_info(registry)
+    }
+}
+
+#[async_trait::async_trait]
+impl OutputValueType for GlobalId {
+    async fn resolve(
+        &self,
+        ctx: &ContextSelectionSet<'_>,
+        _field: &Positioned<Field>,
+    ) -> Result<Value> {
+        Ok(Value::String(ctx.schema_env.global_id_codec.encode(self)))
+    }
+}
+
+/// Encodes and decodes the global ids of a schema.
+///
+/// Set it with `SchemaBuilder::global_id_codec`.
+pub trait GlobalIdCodec: Send + Sync + 'static {
+    /// Encode a global id to an opaque string.
+    fn encode(&self, id: &GlobalId) -> String;
+
+    /// Decode a global id, returning `None` if the string is not a valid global id.
+    fn decode(&self, s: &str) -> Option<GlobalId>;
+}
+
+/// The default global id codec, which encodes `TypeName:id` in base64.
+///
+/// This is the same format as the one used by the reference JavaScript implementation.
+#[derive(Default)]
+pub struct Base64GlobalIdCodec;
+
+impl GlobalIdCodec for Base64GlobalIdCodec {
+    fn encode(&self, id: &GlobalId) -> String {
+        base64::encode(id.to_string())
+    }
+
+    fn decode(&self, s: &str) -> Option<GlobalId> {
+        let s = String::from_utf8(base64::decode(s).ok()?).ok()?;
+        let mut parts = s.splitn(2, ':');
+        let type_name = parts.next()?;
+        let id = parts.next()?;
+        if type_name.is_empty() {
+            return None;
+        }
+        Some(GlobalId::new(type_name, id))
+    }
+}
+
+/// Resolve the `node(id:)` root field, or one item of the `nodes(ids:)` root field.
+pub(crate) async fn resolve_node(
+    ctx: &Context<'_>,
+    ctx_obj: &ContextSelectionSet<'_>,
+    id: &ID,
+) -> Result<Value> {
+    let global_id = ctx.schema_env.global_id_codec.decode(id).ok_or_else(|| {
+        FieldError::new(format!("Invalid global id \"{}\".", id.as_str()))
+            .user_facing()
+            .into_error_with_path(ctx.item.pos, ctx_obj.path_node.as_ref())
+    })?;
+
+    match ctx.schema_env.registry.nodes.get(&global_id.type_name) {
+        Some(loader) => Ok(loader(ctx, ctx_obj, &global_id.id)
+            .await?
+            .unwrap_or(Value::Null)),
+        None => Ok(Value::Null),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_codec() {
+        let codec = Base64GlobalIdCodec;
+        let id = GlobalId::new("User", 1);
+        assert_eq!(codec.encode(&id), "VXNlcjox");
+        assert_eq!(codec.decode("VXNlcjox"), Some(id));
+        assert_eq!(
+            codec.decode(&base64::encode("Post:a:b")),
+            Some(GlobalId::new("Post", "a:b"))
+        );
+        assert_eq!(codec.decode("VXNlcjox!"), None);
+        assert_eq!(codec.decode(&base64::encode("User")), None);
+    }
+}
@@ -1,9 +1,10 @@
 use crate::model::{__Schema, __Type};
+use crate::node::resolve_node;
 use crate::parser::types::Field;
 use crate::resolver_utils::{resolve_object, ObjectType};
 use crate::{
     registry, Any, Context, ContextSelectionSet, Error, OutputValueType, Positioned, QueryError,
-    Result, SimpleObject, Type,
+    Result, SimpleObject, Type, Value, ID,
 };
 
 use crate::type_mark::TypeMarkObject;
@@ -45,6 +46,9 @@ impl<T: Type> Type for QueryRoot<T> {
                     external: false,
                     requires: None,
                     provides: None,
+                    timeout: None,
+                    is_secret: false,
+                    default_names: None,
                 },
             );
 
@@ -62,7 +66,10 @@ impl<T: Type> Type for QueryRoot<T> {
                                 description: None,
                                 ty: "String!".to_string(),
                                 default_value: None,
+                                deprecation: None,
                                 validator: None,
+                                is_secret: false,
+                                default_names: None,
                             },
                         );
                         args
@@ -73,6 +80,9 @@ impl<T: Type> Type for QueryRoot<T> {
                     external: false,
                     requires: None,
                     provides: None,
+                    timeout: None,
+                    is_secret: false,
+                    default_names: None,
                 },
             );
         }
@@ -82,7 +92,7 @@ impl<T: Type> Type for QueryRoot<T> {
 
 #[async_trait::async_trait]
 impl<T: ObjectType + Send + Sync> ObjectType for QueryRoot<T> {
-    async fn resolve_field(&self, ctx: &Context<'_>) -> Result<serde_json::Value> {
+    async fn resolve_field(&self, ctx: &Context<'_>) -> Result<Value> {
         if ctx.item.node.name.node == "__schema" {
             if self.disable_introspection {
                 return Err(Error::Query {
@@ -126,7 +136,22 @@ impl<T: ObjectType + Send + Sync> ObjectType for QueryRoot<T> {
             for item in representations {
                 res.push(self.inner.find_entity(ctx, &item.0).await?);
             }
-            return Ok(res.into());
+            return Ok(Value::List(res));
+        } else if ctx.item.node.name.node == "node" && ctx.schema_env.registry.is_node_field("node")
+        {
+            let id: ID = ctx.param_value("id", None)?;
+            let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+            return resolve_node(ctx, &ctx_obj, &id).await;
+        } else if ctx.item.node.name.node == "nodes"
+            && ctx.schema_env.registry.is_node_field("nodes")
+        {
+            let ids: Vec<ID> = ctx.param_value("ids", None)?;
+            let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+            let futures = ids.iter().enumerate().map(|(idx, id)| {
+                let ctx_obj = &ctx_obj;
+                async move { resolve_node(ctx, &ctx_obj.with_index(idx), id).await }
+            });
+            return Ok(Value::List(futures::future::try_join_all(futures).await?));
         } else if ctx.item.node.name.node == "_service" {
             let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
             return OutputValueType::resolve(
@@ -149,7 +174,7 @@ impl<T: ObjectType + Send + Sync> OutputValueType for QueryRoot<T> {
         &self,
         ctx: &ContextSelectionSet<'_>,
         _field: &Positioned<Field>,
-    ) -> Result<serde_json::Value> {
+    ) -> Result<Value> {
         resolve_object(ctx, self).await
     }
 }
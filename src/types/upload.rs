@@ -1,7 +1,9 @@
-use crate::parser::types::UploadValue;
+use crate::parser::types::{UploadContent, UploadValue};
 use crate::{registry, InputValueError, InputValueResult, InputValueType, Type, Value};
 use std::borrow::Cow;
-use std::io::Read;
+use std::fs::File;
+use std::io::{self, Cursor, Read};
+use std::sync::Arc;
 
 /// Uploaded file
 ///
@@ -56,22 +58,60 @@ impl Upload {
     }
 
     /// Returns the size of the file, in bytes.
-    pub fn size(&self) -> std::io::Result<u64> {
-        self.0.content.metadata().map(|meta| meta.len())
+    pub fn size(&self) -> io::Result<u64> {
+        self.0.content.size()
+    }
+
+    /// Returns where the data of the file is stored.
+    pub fn content(&self) -> &UploadContent {
+        &self.0.content
+    }
+
+    /// Returns the value describing where the file was sent, if it was stored by a custom
+    /// [`UploadStorage`](http/trait.UploadStorage.html) and the value is of type `T`.
+    pub fn custom_value<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        match &self.0.content {
+            UploadContent::Custom { value, .. } => value.downcast_ref::<T>(),
+            _ => None,
+        }
     }
 
     /// Convert to a `Read`.
     ///
-    /// **Note**: this is a *synchronous/blocking* reader.
+    /// **Note**: this is a *synchronous/blocking* reader. Reading a file which was sent somewhere
+    /// else by a custom storage always fails.
     pub fn into_read(self) -> impl Read + Sync + Send + 'static {
-        self.0.content
+        match self.0.content {
+            UploadContent::File(file) => UploadReader::File(file),
+            UploadContent::Memory(data) => UploadReader::Memory(Cursor::new(data)),
+            UploadContent::Custom { .. } => UploadReader::Custom,
+        }
     }
 
     #[cfg(feature = "unblock")]
     #[cfg_attr(feature = "nightly", doc(cfg(feature = "unblock")))]
     /// Convert to a `AsyncRead`.
     pub fn into_async_read(self) -> impl futures::AsyncRead + Sync + Send + 'static {
-        blocking::Unblock::new(self.0.content)
+        blocking::Unblock::new(self.into_read())
+    }
+}
+
+enum UploadReader {
+    File(File),
+    Memory(Cursor<Arc<[u8]>>),
+    Custom,
+}
+
+impl Read for UploadReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            UploadReader::File(file) => file.read(buf),
+            UploadReader::Memory(cursor) => cursor.read(buf),
+            UploadReader::Custom => Err(io::Error::new(
+                io::ErrorKind::Other,
+                "The file was sent to a custom upload storage and cannot be read",
+            )),
+        }
     }
 }
 
@@ -85,6 +125,7 @@ impl Type for Upload {
             name: Self::type_name().to_string(),
             description: None,
             is_valid: |value| matches!(value, Value::Upload(_)),
+            specified_by_url: None,
         })
     }
 }
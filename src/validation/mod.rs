@@ -1,3 +1,10 @@
+//! Query validation.
+//!
+//! Besides the rules of the GraphQL specification, a schema can run custom rules added with
+//! [`SchemaBuilder::validation_rule`](../struct.SchemaBuilder.html#method.validation_rule). A
+//! rule is a [`Visitor`](trait.Visitor.html) that reports errors through the
+//! [`VisitorContext`](struct.VisitorContext.html).
+
 #[cfg(test)]
 #[macro_use]
 mod test_harness;
@@ -8,12 +15,19 @@ mod utils;
 mod visitor;
 mod visitors;
 
+use crate::context::Data;
 use crate::parser::types::ExecutableDocument;
 use crate::registry::Registry;
 use crate::{CacheControl, Error, Result, Variables};
-use visitor::{visit, VisitorContext, VisitorNil};
+use visitor::{visit, CustomRules, VisitorNil};
+
+pub use visitor::{Visitor, VisitorContext};
+
+pub(crate) type BoxValidationRule = Box<dyn for<'a> Visitor<'a>>;
 
-pub struct CheckResult {
+pub(crate) type ValidationRuleFactory = Box<dyn Fn() -> BoxValidationRule + Send + Sync>;
+
+pub(crate) struct CheckResult {
     pub cache_control: CacheControl,
     pub complexity: usize,
     pub depth: usize,
@@ -26,16 +40,24 @@ pub enum ValidationMode {
     Strict,
 
     /// The executor itself also has error handling, so it can improve performance, but it can lose some error messages.
+    ///
+    /// Custom rules are always executed.
     Fast,
 }
 
-pub fn check_rules(
-    registry: &Registry,
-    doc: &ExecutableDocument,
-    variables: Option<&Variables>,
+pub(crate) fn check_rules<'a>(
+    registry: &'a Registry,
+    doc: &'a ExecutableDocument,
+    variables: Option<&'a Variables>,
+    data: &[&'a Data],
     mode: ValidationMode,
+    custom_rules: &[ValidationRuleFactory],
 ) -> Result<CheckResult> {
     let mut ctx = VisitorContext::new(registry, doc, variables);
+    for data in data {
+        ctx.add_data(data);
+    }
+    let custom_rules = CustomRules(custom_rules.iter().map(|factory| factory()).collect());
     let mut cache_control = CacheControl::default();
     let mut complexity = 0;
     let mut depth = 0;
@@ -43,6 +65,7 @@ pub fn check_rules(
     match mode {
         ValidationMode::Strict => {
             let mut visitor = VisitorNil
+                .with(rules::ExecutableDefinitions)
                 .with(rules::ArgumentsOfCorrectType::default())
                 .with(rules::DefaultValuesOfCorrectType)
                 .with(rules::FieldsOnCorrectType)
@@ -55,6 +78,7 @@ pub fn check_rules(
                 .with(rules::NoUnusedFragments::default())
                 .with(rules::NoUnusedVariables::default())
                 .with(rules::UniqueArgumentNames::default())
+                .with(rules::UniqueInputFieldNames)
                 .with(rules::UniqueVariableNames::default())
                 .with(rules::VariablesAreInputTypes)
                 .with(rules::VariableInAllowedPosition::default())
@@ -62,8 +86,11 @@ pub fn check_rules(
                 .with(rules::PossibleFragmentSpreads::default())
                 .with(rules::ProvidedNonNullArguments)
                 .with(rules::KnownDirectives::default())
+                .with(rules::UniqueDirectivesPerLocation)
+                .with(rules::SingleFieldSubscriptions)
                 .with(rules::OverlappingFieldsCanBeMerged)
                 .with(rules::UploadFile)
+                .with(custom_rules)
                 .with(visitors::CacheControlCalculate {
                     cache_control: &mut cache_control,
                 })
@@ -77,6 +104,7 @@ pub fn check_rules(
             let mut visitor = VisitorNil
                 .with(rules::NoFragmentCycles::default())
                 .with(rules::UploadFile)
+                .with(custom_rules)
                 .with(visitors::CacheControlCalculate {
                     cache_control: &mut cache_control,
                 })
@@ -135,4 +135,108 @@ mod tests {
         "#,
         );
     }
+
+    #[test]
+    fn variables_with_valid_default_null_values() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          query WithDefaultValues(
+            $a: Int = null,
+            $b: String = null,
+            $c: ComplexInput = { requiredField: true, intField: null }
+          ) {
+            dog { name }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn variables_with_invalid_default_null_values() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          query WithDefaultValues($a: ComplexInput = { requiredField: null, intField: null }) {
+            dog { name }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn complex_variables_with_invalid_object_value() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          query InvalidObject($a: ComplexInput = "notverycomplex") {
+            dog { name }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn complex_variables_with_unknown_field() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          query UnknownField($a: ComplexInput = { requiredField: true, unknownField: 1 }) {
+            dog { name }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn complex_variables_with_valid_nested_list() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          query ValidNestedList(
+            $a: ComplexInput = { requiredField: true, stringListField: ["one", null, "two"] }
+          ) {
+            dog { name }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn complex_variables_with_invalid_nested_list_item() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          query InvalidNestedListItem(
+            $a: ComplexInput = { requiredField: true, stringListField: ["one", 2] }
+          ) {
+            dog { name }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn list_of_complex_variables_missing_required_field() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          query MissingRequiredField($a: [ComplexInput] = [{ requiredField: true }, { intField: 3 }]) {
+            dog { name }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn variables_with_unknown_type() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          query UnknownType($a: UnknownType = 1) {
+            dog { name }
+          }
+        "#,
+        );
+    }
 }
@@ -0,0 +1,119 @@
+use crate::parser::types::{ExecutableDocument, TypeSystemDefinition};
+use crate::validation::visitor::{Visitor, VisitorContext};
+
+pub struct ExecutableDefinitions;
+
+impl<'a> Visitor<'a> for ExecutableDefinitions {
+    fn enter_document(&mut self, ctx: &mut VisitorContext<'a>, doc: &'a ExecutableDocument) {
+        for definition in &doc.type_system_definitions {
+            let (pos, name) = match definition {
+                TypeSystemDefinition::Schema(schema) => (schema.pos, "schema".to_string()),
+                TypeSystemDefinition::Type(ty) => (ty.pos, format!("\"{}\"", ty.node.name.node)),
+                TypeSystemDefinition::Directive(directive) => {
+                    (directive.pos, format!("\"{}\"", directive.node.name.node))
+                }
+            };
+            ctx.report_error(
+                vec![pos],
+                format!("The {} definition is not executable", name),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub fn factory() -> ExecutableDefinitions {
+        ExecutableDefinitions
+    }
+
+    #[test]
+    fn with_only_operation() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          query Foo {
+            dog {
+              name
+            }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn with_operation_and_fragment() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          query Foo {
+            dog {
+              name
+              ...Frag
+            }
+          }
+          fragment Frag on Dog {
+            name
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn with_type_definition() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          query Foo {
+            dog {
+              name
+            }
+          }
+          type Cow {
+            name: String
+          }
+          extend type Dog {
+            color: String
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn with_schema_definition() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          schema {
+            query: Query
+          }
+          type Query {
+            test: String
+          }
+          extend schema @directive
+          query Foo {
+            dog {
+              name
+            }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn with_directive_definition() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          directive @foo on FIELD
+          query Foo {
+            dog @foo {
+              name
+            }
+          }
+        "#,
+        );
+    }
+}
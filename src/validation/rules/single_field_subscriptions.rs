@@ -0,0 +1,210 @@
+use crate::parser::types::{Name, OperationDefinition, OperationType, Selection, SelectionSet};
+use crate::validation::visitor::{Visitor, VisitorContext};
+use crate::Positioned;
+use std::collections::HashSet;
+
+pub struct SingleFieldSubscriptions;
+
+impl<'a> Visitor<'a> for SingleFieldSubscriptions {
+    fn enter_operation_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        name: Option<&'a Name>,
+        operation_definition: &'a Positioned<OperationDefinition>,
+    ) {
+        if operation_definition.node.ty != OperationType::Subscription {
+            return;
+        }
+
+        let mut response_keys = HashSet::new();
+        collect_response_keys(
+            ctx,
+            &operation_definition.node.selection_set.node,
+            &mut response_keys,
+            &mut HashSet::new(),
+        );
+
+        if response_keys.len() > 1 {
+            ctx.report_error(
+                vec![operation_definition.pos],
+                match name {
+                    Some(name) => format!(
+                        "Subscription \"{}\" must select only one top level field",
+                        name
+                    ),
+                    None => "Anonymous Subscription must select only one top level field".into(),
+                },
+            );
+        }
+    }
+}
+
+fn collect_response_keys<'a>(
+    ctx: &VisitorContext<'a>,
+    selection_set: &'a SelectionSet,
+    response_keys: &mut HashSet<&'a str>,
+    visited_fragments: &mut HashSet<&'a str>,
+) {
+    for selection in &selection_set.items {
+        match &selection.node {
+            Selection::Field(field) => {
+                response_keys.insert(field.node.response_key().node.as_str());
+            }
+            Selection::FragmentSpread(fragment_spread) => {
+                let fragment_name = fragment_spread.node.fragment_name.node.as_str();
+                if !visited_fragments.insert(fragment_name) {
+                    continue;
+                }
+                if let Some(fragment) = ctx.fragment(fragment_name) {
+                    collect_response_keys(
+                        ctx,
+                        &fragment.node.selection_set.node,
+                        response_keys,
+                        visited_fragments,
+                    );
+                }
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                collect_response_keys(
+                    ctx,
+                    &inline_fragment.node.selection_set.node,
+                    response_keys,
+                    visited_fragments,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub fn factory() -> SingleFieldSubscriptions {
+        SingleFieldSubscriptions
+    }
+
+    #[test]
+    fn valid_subscription() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          subscription ImportantEmails {
+            values
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn valid_subscription_with_fragment() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          subscription sub {
+            ...newMessageFields
+          }
+          fragment newMessageFields on SubscriptionRoot {
+            values
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn valid_subscription_with_same_field_twice() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          subscription sub {
+            values
+            ... on SubscriptionRoot {
+              values
+            }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn fails_with_more_than_one_root_field() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          subscription ImportantEmails {
+            values
+            notImportantValues
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn fails_with_more_than_one_root_field_including_introspection() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          subscription ImportantEmails {
+            values
+            __typename
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn fails_with_many_more_than_one_root_field() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          subscription ImportantEmails {
+            values
+            notImportantValues
+            spamValues
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn fails_with_more_than_one_root_field_via_fragments() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          subscription ImportantEmails {
+            values
+            ...moreValues
+          }
+          fragment moreValues on SubscriptionRoot {
+            notImportantValues
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn fails_with_more_than_one_root_field_in_anonymous_subscription() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          subscription {
+            values
+            notImportantValues
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn fails_with_aliased_root_fields() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          subscription ImportantEmails {
+            a: values
+            b: values
+          }
+        "#,
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use crate::parser::types::{
+    Directive, Field, FragmentDefinition, FragmentSpread, InlineFragment, Name, OperationDefinition,
+};
+use crate::validation::visitor::{Visitor, VisitorContext};
+use crate::Positioned;
+use std::collections::HashSet;
+
+pub struct UniqueDirectivesPerLocation;
+
+fn check_directives(ctx: &mut VisitorContext<'_>, directives: &[Positioned<Directive>]) {
+    let mut names = HashSet::new();
+    for directive in directives {
+        if !names.insert(directive.node.name.node.as_str()) {
+            ctx.report_error(
+                vec![directive.pos],
+                format!(
+                    "The directive \"@{}\" can only be used once at this location",
+                    directive.node.name.node
+                ),
+            );
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for UniqueDirectivesPerLocation {
+    fn enter_operation_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        _name: Option<&'a Name>,
+        operation_definition: &'a Positioned<OperationDefinition>,
+    ) {
+        check_directives(ctx, &operation_definition.node.directives);
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        _name: &'a Name,
+        fragment_definition: &'a Positioned<FragmentDefinition>,
+    ) {
+        check_directives(ctx, &fragment_definition.node.directives);
+    }
+
+    fn enter_field(&mut self, ctx: &mut VisitorContext<'a>, field: &'a Positioned<Field>) {
+        check_directives(ctx, &field.node.directives);
+    }
+
+    fn enter_fragment_spread(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        fragment_spread: &'a Positioned<FragmentSpread>,
+    ) {
+        check_directives(ctx, &fragment_spread.node.directives);
+    }
+
+    fn enter_inline_fragment(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        inline_fragment: &'a Positioned<InlineFragment>,
+    ) {
+        check_directives(ctx, &inline_fragment.node.directives);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub fn factory() -> UniqueDirectivesPerLocation {
+        UniqueDirectivesPerLocation
+    }
+
+    #[test]
+    fn no_directives() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          fragment Test on Dog {
+            name
+          }
+          { dog { ...Test } }
+        "#,
+        );
+    }
+
+    #[test]
+    fn unique_directives_in_different_locations() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          fragment Test on Dog @skip(if: true) {
+            name @include(if: true)
+          }
+          { dog { ...Test } }
+        "#,
+        );
+    }
+
+    #[test]
+    fn unique_directives_in_same_locations() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          fragment Test on Dog @skip(if: true) @include(if: true) {
+            name @skip(if: true) @include(if: true)
+          }
+          { dog { ...Test } }
+        "#,
+        );
+    }
+
+    #[test]
+    fn same_directives_in_different_locations() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          fragment Test on Dog @skip(if: true) {
+            name @skip(if: true)
+          }
+          { dog { ...Test } }
+        "#,
+        );
+    }
+
+    #[test]
+    fn same_directives_in_similar_locations() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          fragment Test on Dog {
+            name @skip(if: true)
+            name @skip(if: true)
+          }
+          { dog { ...Test } }
+        "#,
+        );
+    }
+
+    #[test]
+    fn duplicate_directives_in_one_location() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          fragment Test on Dog {
+            name @skip(if: true) @skip(if: false)
+          }
+          { dog { ...Test } }
+        "#,
+        );
+    }
+
+    #[test]
+    fn many_duplicate_directives_in_one_location() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          fragment Test on Dog {
+            name @skip(if: true) @skip(if: false) @skip(if: true)
+          }
+          { dog { ...Test } }
+        "#,
+        );
+    }
+
+    #[test]
+    fn different_duplicate_directives_in_one_location() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          fragment Test on Dog {
+            name @skip(if: true) @include(if: true) @skip(if: false) @include(if: false)
+          }
+          { dog { ...Test } }
+        "#,
+        );
+    }
+
+    #[test]
+    fn duplicate_directives_on_fragment_definition() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          fragment Test on Dog @skip(if: true) @skip(if: false) {
+            name
+          }
+          { dog { ...Test } }
+        "#,
+        );
+    }
+
+    #[test]
+    fn duplicate_directives_on_fragment_spread_and_inline_fragment() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          {
+            dog {
+              ...Test @include(if: true) @include(if: false)
+              ... on Dog @skip(if: true) @skip(if: false) { name }
+            }
+          }
+          fragment Test on Dog { name }
+        "#,
+        );
+    }
+
+    #[test]
+    fn duplicate_directives_on_operation() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          query Test @skip(if: true) @skip(if: false) {
+            dog { name }
+          }
+        "#,
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use crate::parser::types::ExecutableDocument;
+use crate::validation::visitor::{Visitor, VisitorContext};
+
+pub struct UniqueInputFieldNames;
+
+impl<'a> Visitor<'a> for UniqueInputFieldNames {
+    fn enter_document(&mut self, ctx: &mut VisitorContext<'a>, doc: &'a ExecutableDocument) {
+        for field in &doc.duplicate_input_fields {
+            ctx.report_error(
+                vec![field.first, field.second],
+                format!("There can only be one input field named \"{}\"", field.name),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub fn factory() -> UniqueInputFieldNames {
+        UniqueInputFieldNames
+    }
+
+    #[test]
+    fn input_object_with_fields() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          {
+            field(arg: { f: true })
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn same_input_object_within_two_args() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          {
+            field(arg1: { f: true }, arg2: { f: true })
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn multiple_input_object_fields() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          {
+            field(arg: { f1: "value", f2: "value", f3: "value" })
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn allows_for_nested_input_objects_with_similar_fields() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          {
+            field(arg: {
+              deep: {
+                deep: {
+                  id: 1
+                }
+                id: 1
+              }
+              id: 1
+            })
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn duplicate_input_object_fields() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          {
+            field(arg: { f1: "value", f1: "value" })
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn many_duplicate_input_object_fields() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          {
+            field(arg: { f1: "value", f1: "value", f1: "value" })
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn nested_duplicate_input_object_fields() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          {
+            field(arg: { f1: {f2: "value", f2: "value" }})
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn duplicate_fields_in_variable_default_value() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          query ($arg: ComplexInput = { requiredField: true, requiredField: false }) {
+            field(arg: $arg)
+          }
+        "#,
+        );
+    }
+}
@@ -46,7 +46,7 @@ impl<'a> VariableInAllowedPosition<'a> {
                             vec![def.pos, *usage_pos],
                             format!(
                                 "Variable \"{}\" of type \"{}\" used in position expecting type \"{}\"",
-                                var_name, var_type, expected_type
+                                var_name, expected_type, var_type
                             ),
                         );
                     }
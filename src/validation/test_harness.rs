@@ -5,6 +5,7 @@
 use crate::parser::types::ExecutableDocument;
 use crate::validation::visitor::{visit, Visitor, VisitorContext};
 use crate::*;
+use futures::Stream;
 use once_cell::sync::Lazy;
 
 #[derive(InputObject)]
@@ -341,8 +342,25 @@ impl MutationRoot {
     }
 }
 
-static TEST_HARNESS: Lazy<Schema<QueryRoot, MutationRoot, EmptySubscription>> =
-    Lazy::new(|| Schema::new(QueryRoot, MutationRoot, EmptySubscription));
+pub struct SubscriptionRoot;
+
+#[Subscription(internal)]
+impl SubscriptionRoot {
+    async fn values(&self) -> impl Stream<Item = i32> {
+        futures::stream::empty()
+    }
+
+    async fn not_important_values(&self) -> impl Stream<Item = i32> {
+        futures::stream::empty()
+    }
+
+    async fn spam_values(&self) -> impl Stream<Item = i32> {
+        futures::stream::empty()
+    }
+}
+
+static TEST_HARNESS: Lazy<Schema<QueryRoot, MutationRoot, SubscriptionRoot>> =
+    Lazy::new(|| Schema::new(QueryRoot, MutationRoot, SubscriptionRoot));
 
 pub fn validate<'a, V, F>(doc: &'a ExecutableDocument, factory: F) -> Result<()>
 where
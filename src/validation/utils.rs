@@ -67,7 +67,13 @@ pub fn is_valid_input_value(
                 return None;
             }
 
-            match registry.types.get(type_name).unwrap() {
+            let ty = match registry.types.get(type_name) {
+                Some(ty) => ty,
+                // Unknown types are reported by `KnownTypeNames`.
+                None => return None,
+            };
+
+            match ty {
                 registry::MetaType::Scalar { is_valid, .. } => {
                     if is_valid(&value) {
                         None
@@ -117,6 +123,7 @@ pub fn is_valid_input_value(
                 registry::MetaType::InputObject {
                     input_fields,
                     name: object_name,
+                    oneof,
                     ..
                 } => match value {
                     ConstValue::Object(values) => {
@@ -171,9 +178,25 @@ pub fn is_valid_input_value(
                             ));
                         }
 
+                        if *oneof
+                            && (values.len() != 1
+                                || matches!(values.values().next(), Some(ConstValue::Null)))
+                        {
+                            return Some(valid_error(
+                                &path_node,
+                                format!(
+                                    "oneof input object \"{}\" requires exactly one non-null field",
+                                    object_name
+                                ),
+                            ));
+                        }
+
                         None
                     }
-                    _ => None,
+                    _ => Some(valid_error(
+                        &path_node,
+                        format!("expected type \"{}\"", type_name),
+                    )),
                 },
                 _ => None,
             }
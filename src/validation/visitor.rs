@@ -1,3 +1,4 @@
+use crate::context::Data;
 use crate::error::RuleError;
 use crate::parser::types::{
     Directive, ExecutableDocument, Field, FragmentDefinition, FragmentSpread, InlineFragment, Name,
@@ -6,19 +7,22 @@ use crate::parser::types::{
 };
 use crate::registry::{self, MetaType, MetaTypeName};
 use crate::{Pos, Positioned, Variables};
+use std::any::{Any, TypeId};
 use std::collections::HashMap;
 
+/// The state of a validation pass, shared by all the rules.
 pub struct VisitorContext<'a> {
-    pub registry: &'a registry::Registry,
-    pub variables: Option<&'a Variables>,
-    pub errors: Vec<RuleError>,
+    pub(crate) registry: &'a registry::Registry,
+    pub(crate) variables: Option<&'a Variables>,
+    pub(crate) errors: Vec<RuleError>,
     type_stack: Vec<Option<&'a registry::MetaType>>,
     input_type: Vec<Option<MetaTypeName<'a>>>,
     fragments: &'a HashMap<Name, Positioned<FragmentDefinition>>,
+    data: Vec<&'a Data>,
 }
 
 impl<'a> VisitorContext<'a> {
-    pub fn new(
+    pub(crate) fn new(
         registry: &'a registry::Registry,
         doc: &'a ExecutableDocument,
         variables: Option<&'a Variables>,
@@ -30,9 +34,15 @@ impl<'a> VisitorContext<'a> {
             type_stack: Default::default(),
             input_type: Default::default(),
             fragments: &doc.fragments,
+            data: Default::default(),
         }
     }
 
+    pub(crate) fn add_data(&mut self, data: &'a Data) {
+        self.data.push(data);
+    }
+
+    /// Report a validation error at the given positions.
     pub fn report_error<T: Into<String>>(&mut self, locations: Vec<Pos>, msg: T) {
         self.errors.push(RuleError {
             locations,
@@ -40,11 +50,11 @@ impl<'a> VisitorContext<'a> {
         })
     }
 
-    pub fn append_errors(&mut self, errors: Vec<RuleError>) {
+    pub(crate) fn append_errors(&mut self, errors: Vec<RuleError>) {
         self.errors.extend(errors);
     }
 
-    pub fn with_type<F: FnMut(&mut VisitorContext<'a>)>(
+    pub(crate) fn with_type<F: FnMut(&mut VisitorContext<'a>)>(
         &mut self,
         ty: Option<&'a registry::MetaType>,
         mut f: F,
@@ -54,7 +64,7 @@ impl<'a> VisitorContext<'a> {
         self.type_stack.pop();
     }
 
-    pub fn with_input_type<F: FnMut(&mut VisitorContext<'a>)>(
+    pub(crate) fn with_input_type<F: FnMut(&mut VisitorContext<'a>)>(
         &mut self,
         ty: Option<MetaTypeName<'a>>,
         mut f: F,
@@ -64,6 +74,26 @@ impl<'a> VisitorContext<'a> {
         self.input_type.pop();
     }
 
+    /// Gets the registry of the schema.
+    pub fn registry(&self) -> &'a registry::Registry {
+        self.registry
+    }
+
+    /// Gets the variables of the request, if they are known.
+    pub fn variables(&self) -> Option<&'a Variables> {
+        self.variables
+    }
+
+    /// Gets the data of the request or the schema, or `None` if the specified type data does
+    /// not exist.
+    pub fn data_opt<D: Any + Send + Sync>(&self) -> Option<&'a D> {
+        self.data
+            .iter()
+            .find_map(|data| data.get(&TypeId::of::<D>()))
+            .and_then(|d| d.downcast_ref::<D>())
+    }
+
+    /// Gets the type that contains the current field.
     pub fn parent_type(&self) -> Option<&'a registry::MetaType> {
         if self.type_stack.len() >= 2 {
             self.type_stack
@@ -75,23 +105,34 @@ impl<'a> VisitorContext<'a> {
         }
     }
 
+    /// Gets the type of the current field, operation or fragment.
     pub fn current_type(&self) -> Option<&'a registry::MetaType> {
         self.type_stack.last().copied().flatten()
     }
 
+    /// Returns `true` if the document defines a fragment with this name.
     pub fn is_known_fragment(&self, name: &str) -> bool {
         self.fragments.contains_key(name)
     }
 
+    /// Gets the fragment with this name.
     pub fn fragment(&self, name: &str) -> Option<&'a Positioned<FragmentDefinition>> {
         self.fragments.get(name)
     }
 }
 
+/// A validation rule.
+///
+/// The methods are called while the document is walked, and report errors with
+/// [`VisitorContext::report_error`](struct.VisitorContext.html#method.report_error). All the
+/// methods do nothing by default.
 pub trait Visitor<'a> {
+    /// Called before the document is visited.
     fn enter_document(&mut self, _ctx: &mut VisitorContext<'a>, _doc: &'a ExecutableDocument) {}
+    /// Called after the document is visited.
     fn exit_document(&mut self, _ctx: &mut VisitorContext<'a>, _doc: &'a ExecutableDocument) {}
 
+    /// Called before an operation is visited.
     fn enter_operation_definition(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -99,6 +140,7 @@ pub trait Visitor<'a> {
         _operation_definition: &'a Positioned<OperationDefinition>,
     ) {
     }
+    /// Called after an operation is visited.
     fn exit_operation_definition(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -107,6 +149,7 @@ pub trait Visitor<'a> {
     ) {
     }
 
+    /// Called before a fragment definition is visited.
     fn enter_fragment_definition(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -114,6 +157,7 @@ pub trait Visitor<'a> {
         _fragment_definition: &'a Positioned<FragmentDefinition>,
     ) {
     }
+    /// Called after a fragment definition is visited.
     fn exit_fragment_definition(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -122,12 +166,14 @@ pub trait Visitor<'a> {
     ) {
     }
 
+    /// Called before a variable definition is visited.
     fn enter_variable_definition(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _variable_definition: &'a Positioned<VariableDefinition>,
     ) {
     }
+    /// Called after a variable definition is visited.
     fn exit_variable_definition(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -135,12 +181,14 @@ pub trait Visitor<'a> {
     ) {
     }
 
+    /// Called before a directive is visited.
     fn enter_directive(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _directive: &'a Positioned<Directive>,
     ) {
     }
+    /// Called after a directive is visited.
     fn exit_directive(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -148,6 +196,7 @@ pub trait Visitor<'a> {
     ) {
     }
 
+    /// Called before an argument of a field or a directive is visited.
     fn enter_argument(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -155,6 +204,7 @@ pub trait Visitor<'a> {
         _value: &'a Positioned<Value>,
     ) {
     }
+    /// Called after an argument of a field or a directive is visited.
     fn exit_argument(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -163,12 +213,14 @@ pub trait Visitor<'a> {
     ) {
     }
 
+    /// Called before a selection set is visited.
     fn enter_selection_set(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _selection_set: &'a Positioned<SelectionSet>,
     ) {
     }
+    /// Called after a selection set is visited.
     fn exit_selection_set(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -176,12 +228,14 @@ pub trait Visitor<'a> {
     ) {
     }
 
+    /// Called before a selection is visited.
     fn enter_selection(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _selection: &'a Positioned<Selection>,
     ) {
     }
+    /// Called after a selection is visited.
     fn exit_selection(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -189,15 +243,19 @@ pub trait Visitor<'a> {
     ) {
     }
 
+    /// Called before a field is visited.
     fn enter_field(&mut self, _ctx: &mut VisitorContext<'a>, _field: &'a Positioned<Field>) {}
+    /// Called after a field is visited.
     fn exit_field(&mut self, _ctx: &mut VisitorContext<'a>, _field: &'a Positioned<Field>) {}
 
+    /// Called before a fragment spread is visited.
     fn enter_fragment_spread(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _fragment_spread: &'a Positioned<FragmentSpread>,
     ) {
     }
+    /// Called after a fragment spread is visited.
     fn exit_fragment_spread(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -205,12 +263,14 @@ pub trait Visitor<'a> {
     ) {
     }
 
+    /// Called before an inline fragment is visited.
     fn enter_inline_fragment(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _inline_fragment: &'a Positioned<InlineFragment>,
     ) {
     }
+    /// Called after an inline fragment is visited.
     fn exit_inline_fragment(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -218,6 +278,7 @@ pub trait Visitor<'a> {
     ) {
     }
 
+    /// Called before an input value is visited, with its expected type.
     fn enter_input_value(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -226,6 +287,7 @@ pub trait Visitor<'a> {
         _value: &'a Value,
     ) {
     }
+    /// Called after an input value is visited.
     fn exit_input_value(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -454,6 +516,267 @@ where
         self.0.exit_inline_fragment(ctx, inline_fragment);
         self.1.exit_inline_fragment(ctx, inline_fragment);
     }
+
+    fn enter_input_value(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        pos: Pos,
+        expected_type: &Option<MetaTypeName<'a>>,
+        value: &'a Value,
+    ) {
+        self.0.enter_input_value(ctx, pos, expected_type, value);
+        self.1.enter_input_value(ctx, pos, expected_type, value);
+    }
+
+    fn exit_input_value(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        pos: Pos,
+        expected_type: &Option<MetaTypeName<'a>>,
+        value: &Value,
+    ) {
+        self.0.exit_input_value(ctx, pos, expected_type, value);
+        self.1.exit_input_value(ctx, pos, expected_type, value);
+    }
+}
+
+/// The validation rules added with `SchemaBuilder::validation_rule`.
+pub(crate) struct CustomRules(pub(crate) Vec<Box<dyn for<'r> Visitor<'r>>>);
+
+impl<'a> Visitor<'a> for CustomRules {
+    fn enter_document(&mut self, ctx: &mut VisitorContext<'a>, doc: &'a ExecutableDocument) {
+        for rule in &mut self.0 {
+            rule.enter_document(ctx, doc);
+        }
+    }
+
+    fn exit_document(&mut self, ctx: &mut VisitorContext<'a>, doc: &'a ExecutableDocument) {
+        for rule in &mut self.0 {
+            rule.exit_document(ctx, doc);
+        }
+    }
+
+    fn enter_operation_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        name: Option<&'a Name>,
+        operation_definition: &'a Positioned<OperationDefinition>,
+    ) {
+        for rule in &mut self.0 {
+            rule.enter_operation_definition(ctx, name, operation_definition);
+        }
+    }
+
+    fn exit_operation_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        name: Option<&'a Name>,
+        operation_definition: &'a Positioned<OperationDefinition>,
+    ) {
+        for rule in &mut self.0 {
+            rule.exit_operation_definition(ctx, name, operation_definition);
+        }
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        name: &'a Name,
+        fragment_definition: &'a Positioned<FragmentDefinition>,
+    ) {
+        for rule in &mut self.0 {
+            rule.enter_fragment_definition(ctx, name, fragment_definition);
+        }
+    }
+
+    fn exit_fragment_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        name: &'a Name,
+        fragment_definition: &'a Positioned<FragmentDefinition>,
+    ) {
+        for rule in &mut self.0 {
+            rule.exit_fragment_definition(ctx, name, fragment_definition);
+        }
+    }
+
+    fn enter_variable_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        variable_definition: &'a Positioned<VariableDefinition>,
+    ) {
+        for rule in &mut self.0 {
+            rule.enter_variable_definition(ctx, variable_definition);
+        }
+    }
+
+    fn exit_variable_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        variable_definition: &'a Positioned<VariableDefinition>,
+    ) {
+        for rule in &mut self.0 {
+            rule.exit_variable_definition(ctx, variable_definition);
+        }
+    }
+
+    fn enter_directive(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        directive: &'a Positioned<Directive>,
+    ) {
+        for rule in &mut self.0 {
+            rule.enter_directive(ctx, directive);
+        }
+    }
+
+    fn exit_directive(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        directive: &'a Positioned<Directive>,
+    ) {
+        for rule in &mut self.0 {
+            rule.exit_directive(ctx, directive);
+        }
+    }
+
+    fn enter_argument(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        name: &'a Positioned<Name>,
+        value: &'a Positioned<Value>,
+    ) {
+        for rule in &mut self.0 {
+            rule.enter_argument(ctx, name, value);
+        }
+    }
+
+    fn exit_argument(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        name: &'a Positioned<Name>,
+        value: &'a Positioned<Value>,
+    ) {
+        for rule in &mut self.0 {
+            rule.exit_argument(ctx, name, value);
+        }
+    }
+
+    fn enter_selection_set(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        selection_set: &'a Positioned<SelectionSet>,
+    ) {
+        for rule in &mut self.0 {
+            rule.enter_selection_set(ctx, selection_set);
+        }
+    }
+
+    fn exit_selection_set(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        selection_set: &'a Positioned<SelectionSet>,
+    ) {
+        for rule in &mut self.0 {
+            rule.exit_selection_set(ctx, selection_set);
+        }
+    }
+
+    fn enter_selection(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        selection: &'a Positioned<Selection>,
+    ) {
+        for rule in &mut self.0 {
+            rule.enter_selection(ctx, selection);
+        }
+    }
+
+    fn exit_selection(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        selection: &'a Positioned<Selection>,
+    ) {
+        for rule in &mut self.0 {
+            rule.exit_selection(ctx, selection);
+        }
+    }
+
+    fn enter_field(&mut self, ctx: &mut VisitorContext<'a>, field: &'a Positioned<Field>) {
+        for rule in &mut self.0 {
+            rule.enter_field(ctx, field);
+        }
+    }
+
+    fn exit_field(&mut self, ctx: &mut VisitorContext<'a>, field: &'a Positioned<Field>) {
+        for rule in &mut self.0 {
+            rule.exit_field(ctx, field);
+        }
+    }
+
+    fn enter_fragment_spread(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        fragment_spread: &'a Positioned<FragmentSpread>,
+    ) {
+        for rule in &mut self.0 {
+            rule.enter_fragment_spread(ctx, fragment_spread);
+        }
+    }
+
+    fn exit_fragment_spread(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        fragment_spread: &'a Positioned<FragmentSpread>,
+    ) {
+        for rule in &mut self.0 {
+            rule.exit_fragment_spread(ctx, fragment_spread);
+        }
+    }
+
+    fn enter_inline_fragment(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        inline_fragment: &'a Positioned<InlineFragment>,
+    ) {
+        for rule in &mut self.0 {
+            rule.enter_inline_fragment(ctx, inline_fragment);
+        }
+    }
+
+    fn exit_inline_fragment(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        inline_fragment: &'a Positioned<InlineFragment>,
+    ) {
+        for rule in &mut self.0 {
+            rule.exit_inline_fragment(ctx, inline_fragment);
+        }
+    }
+
+    fn enter_input_value(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        pos: Pos,
+        expected_type: &Option<MetaTypeName<'a>>,
+        value: &'a Value,
+    ) {
+        for rule in &mut self.0 {
+            rule.enter_input_value(ctx, pos, expected_type, value);
+        }
+    }
+
+    fn exit_input_value(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        pos: Pos,
+        expected_type: &Option<MetaTypeName<'a>>,
+        value: &Value,
+    ) {
+        for rule in &mut self.0 {
+            rule.exit_input_value(ctx, pos, expected_type, value);
+        }
+    }
 }
 
 pub fn visit<'a, V: Visitor<'a>>(
@@ -621,16 +944,23 @@ fn visit_input_value<'a, V: Visitor<'a>>(
                         .types
                         .get(MetaTypeName::concrete_typename(expected_ty))
                     {
-                        if let MetaType::InputObject { input_fields, .. } = ty {
+                        if let MetaType::InputObject {
+                            input_fields,
+                            oneof,
+                            ..
+                        } = ty
+                        {
                             for (item_key, item_value) in values {
                                 if let Some(input_value) = input_fields.get(item_key.as_str()) {
-                                    visit_input_value(
-                                        v,
-                                        ctx,
-                                        pos,
-                                        Some(MetaTypeName::create(&input_value.ty)),
-                                        item_value,
-                                    );
+                                    let field_ty = MetaTypeName::create(&input_value.ty);
+                                    // The only field provided to a oneof input object must not be null,
+                                    // so variables used there are expected to be non-null too.
+                                    let field_ty = if *oneof && !field_ty.is_non_null() {
+                                        MetaTypeName::NonNull(&input_value.ty)
+                                    } else {
+                                        field_ty
+                                    };
+                                    visit_input_value(v, ctx, pos, Some(field_ty), item_value);
                                 }
                             }
                         }
@@ -31,3 +31,164 @@ pub async fn test_batch_request() {
         ])
     );
 }
+
+#[async_std::test]
+pub async fn test_batch_concurrency() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    const BATCH_SIZE: usize = 6;
+
+    // The request `n` waits until the previous requests have finished and every request that can
+    // run at the same time as it is running, so the test does not depend on timing. It panics
+    // instead of hanging if the requests are not executed concurrently.
+    struct Running {
+        concurrency: usize,
+        current: AtomicUsize,
+        finished: AtomicUsize,
+        max: AtomicUsize,
+    }
+
+    impl Running {
+        fn new(concurrency: usize) -> Arc<Self> {
+            Arc::new(Running {
+                concurrency,
+                current: AtomicUsize::new(0),
+                finished: AtomicUsize::new(0),
+                max: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self, ctx: &Context<'_>, n: i32) -> i32 {
+            let running = ctx.data_unchecked::<Arc<Running>>();
+            let current = running.current.fetch_add(1, Ordering::SeqCst) + 1;
+            running.max.fetch_max(current, Ordering::SeqCst);
+            let index = n as usize;
+            let mut polls = 0;
+            while running.finished.load(Ordering::SeqCst) < index
+                || running.current.load(Ordering::SeqCst)
+                    < running.concurrency.min(BATCH_SIZE - index)
+            {
+                polls += 1;
+                assert!(polls < 1000, "the requests are not executed concurrently");
+                async_std::task::yield_now().await;
+            }
+            running.current.fetch_sub(1, Ordering::SeqCst);
+            running.finished.fetch_add(1, Ordering::SeqCst);
+            n
+        }
+    }
+
+    let batch = || -> BatchRequest {
+        (0..BATCH_SIZE)
+            .map(|n| Request::new(format!("{{ value(n: {}) }}", n)))
+            .collect::<Vec<_>>()
+            .into()
+    };
+    let expected = serde_json::json!([
+        {"data": { "value": 0 }},
+        {"data": { "value": 1 }},
+        {"data": { "value": 2 }},
+        {"data": { "value": 3 }},
+        {"data": { "value": 4 }},
+        {"data": { "value": 5 }},
+    ]);
+
+    let running = Running::new(BATCH_SIZE);
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(running.clone())
+        .finish();
+    let resp = schema.execute_batch(batch()).await;
+    assert_eq!(serde_json::to_value(&resp).unwrap(), expected);
+    assert_eq!(running.max.load(Ordering::SeqCst), BATCH_SIZE);
+
+    let running = Running::new(2);
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(running.clone())
+        .batch_concurrency(2)
+        .finish();
+    let resp = schema.execute_batch(batch()).await;
+    assert_eq!(serde_json::to_value(&resp).unwrap(), expected);
+    assert_eq!(running.max.load(Ordering::SeqCst), 2);
+}
+
+#[async_std::test]
+pub async fn test_batch_shared_data() {
+    use std::sync::atomic::{AtomicI32, Ordering};
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn next(&self, ctx: &Context<'_>) -> i32 {
+            ctx.data_unchecked::<AtomicI32>()
+                .fetch_add(1, Ordering::SeqCst)
+        }
+
+        async fn name(&self, ctx: &Context<'_>) -> String {
+            ctx.data_unchecked::<String>().clone()
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .batch_concurrency(1)
+        .finish();
+    let mut data = Data::default();
+    data.insert(AtomicI32::new(10));
+    data.insert("shared".to_string());
+    let batch: BatchRequest = vec![
+        Request::new("{ next name }"),
+        Request::new("{ next name }").data("own".to_string()),
+    ]
+    .into();
+    let resp = schema.execute_batch_with_data(batch, data).await;
+    assert_eq!(
+        serde_json::to_value(&resp).unwrap(),
+        serde_json::json!([
+            {"data": { "next": 10, "name": "shared" }},
+            {"data": { "next": 11, "name": "own" }},
+        ])
+    );
+}
+
+#[test]
+pub fn test_batch_size() {
+    let batch: BatchRequest = vec![Request::new("{ a }"), Request::new("{ b }")].into();
+    assert!(batch.check_batch_size(2).is_ok());
+    assert!(matches!(
+        batch.check_batch_size(1),
+        Err(ParseRequestError::BatchTooLarge(1))
+    ));
+    assert!(BatchRequest::from(Request::new("{ a }"))
+        .check_batch_size(0)
+        .is_ok());
+}
+
+#[cfg(feature = "multipart")]
+#[async_std::test]
+pub async fn test_max_batch_size() {
+    use async_graphql::http::{receive_batch_body, MultipartOptions};
+
+    let opts = MultipartOptions::default().max_batch_size(2);
+    let receive = |body: &'static str| {
+        receive_batch_body(Some("application/json"), body.as_bytes(), opts.clone())
+    };
+
+    assert!(matches!(
+        receive(r#"[{"query": "{ a }"}, {"query": "{ b }"}]"#).await,
+        Ok(BatchRequest::Batch(requests)) if requests.len() == 2
+    ));
+    assert!(matches!(
+        receive(r#"[{"query": "{ a }"}, {"query": "{ b }"}, {"query": "{ c }"}]"#).await,
+        Err(ParseRequestError::BatchTooLarge(2))
+    ));
+    assert!(matches!(
+        receive(r#"{"query": "{ a }"}"#).await,
+        Ok(BatchRequest::Single(_))
+    ));
+}
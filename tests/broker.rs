@@ -0,0 +1,270 @@
+use async_graphql::broker::{Broker, BrokerError, BrokerExt, MemoryBroker};
+use async_graphql::*;
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+
+#[derive(SimpleObject, Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Event {
+    channel: String,
+    value: i32,
+}
+
+#[async_std::test]
+pub async fn test_memory_broker() {
+    let broker = MemoryBroker::default();
+    let mut events = broker.subscribe::<Event>("events");
+    let mut numbers = broker.subscribe::<i32>("numbers");
+    let mut others = broker.subscribe::<Event>("others");
+    assert_eq!(broker.subscriber_count("events"), 1);
+    assert_eq!(broker.subscriber_count("numbers"), 1);
+
+    let event = Event {
+        channel: "a".to_string(),
+        value: 1,
+    };
+    broker.publish("events", event.clone()).await.unwrap();
+    broker.publish("numbers", 10).await.unwrap();
+    broker
+        .publish(
+            "others",
+            Event {
+                channel: "b".to_string(),
+                value: 2,
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(events.next().await, Some(Ok(event)));
+    assert_eq!(numbers.next().await, Some(Ok(10)));
+    assert_eq!(others.next().await.unwrap().unwrap().value, 2);
+
+    drop(events);
+    assert_eq!(broker.subscriber_count("events"), 0);
+    broker
+        .publish(
+            "events",
+            Event {
+                channel: "a".to_string(),
+                value: 3,
+            },
+        )
+        .await
+        .unwrap();
+    assert!(futures::poll!(others.next()).is_pending());
+}
+
+#[async_std::test]
+pub async fn test_memory_broker_lagged() {
+    let broker = MemoryBroker::new(2);
+    let mut stream = broker.subscribe::<i32>("numbers");
+    for i in 0..5 {
+        broker.publish("numbers", i).await.unwrap();
+    }
+
+    assert_eq!(stream.next().await, Some(Err(BrokerError::Lagged(3))));
+    assert_eq!(stream.next().await, Some(Ok(0)));
+    assert_eq!(stream.next().await, Some(Ok(1)));
+
+    broker.publish("numbers", 5).await.unwrap();
+    assert_eq!(stream.next().await, Some(Ok(5)));
+}
+
+#[async_std::test]
+pub async fn test_memory_broker_invalid_message() {
+    let broker = MemoryBroker::default();
+    let mut stream = broker.subscribe::<i32>("numbers");
+    broker.publish("numbers", "ten".to_string()).await.unwrap();
+    broker.publish("numbers", 10).await.unwrap();
+
+    assert!(matches!(
+        stream.next().await,
+        Some(Err(BrokerError::InvalidMessage(_)))
+    ));
+    assert_eq!(stream.next().await, Some(Ok(10)));
+}
+
+#[async_std::test]
+pub async fn test_dyn_broker() {
+    use std::sync::Arc;
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct MutationRoot;
+
+    #[Object]
+    impl MutationRoot {
+        async fn send(&self, ctx: &Context<'_>, value: i32) -> FieldResult<bool> {
+            ctx.data::<Arc<dyn Broker>>()?
+                .publish("numbers", value)
+                .await?;
+            Ok(true)
+        }
+    }
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn numbers<'a>(
+            &self,
+            ctx: &'a Context<'a>,
+        ) -> impl Stream<Item = FieldResult<i32>> + 'a {
+            ctx.data_unchecked::<Arc<dyn Broker>>().subscribe_filter(
+                ctx,
+                "numbers",
+                |_, _: &i32| true,
+            )
+        }
+    }
+
+    let broker = MemoryBroker::default();
+    let schema = Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
+        .data(Arc::new(broker.clone()) as Arc<dyn Broker>)
+        .finish();
+
+    let mut stream = schema
+        .execute_stream("subscription { numbers }")
+        .map(|resp| resp.into_result().unwrap().data.into_json().unwrap())
+        .boxed();
+    assert!(futures::poll!(stream.next()).is_pending());
+    assert_eq!(broker.subscriber_count("numbers"), 1);
+
+    schema
+        .execute("mutation { send(value: 1) }")
+        .await
+        .into_result()
+        .unwrap();
+    assert_eq!(
+        stream.next().await,
+        Some(serde_json::json!({ "numbers": 1 }))
+    );
+}
+
+#[async_std::test]
+pub async fn test_broker_subscription() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct MutationRoot;
+
+    #[Object]
+    impl MutationRoot {
+        async fn send(&self, ctx: &Context<'_>, channel: String, value: i32) -> FieldResult<bool> {
+            ctx.data::<MemoryBroker>()?
+                .publish("events", Event { channel, value })
+                .await?;
+            Ok(true)
+        }
+    }
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn events<'a>(
+            &self,
+            ctx: &'a Context<'a>,
+            channel: String,
+        ) -> impl Stream<Item = FieldResult<Event>> + 'a {
+            ctx.data_unchecked::<MemoryBroker>().subscribe_filter(
+                ctx,
+                "events",
+                move |_, event: &Event| event.channel == channel,
+            )
+        }
+
+        async fn my_events<'a>(
+            &self,
+            ctx: &'a Context<'a>,
+        ) -> impl Stream<Item = FieldResult<Event>> + 'a {
+            ctx.data_unchecked::<MemoryBroker>().subscribe_filter(
+                ctx,
+                "events",
+                |ctx, event: &Event| event.channel == *ctx.data_unchecked::<String>(),
+            )
+        }
+    }
+
+    let broker = MemoryBroker::new(4);
+    let schema = Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
+        .data(broker.clone())
+        .finish();
+
+    let mut stream_a = schema
+        .execute_stream(r#"subscription { events(channel: "a") { value } }"#)
+        .map(|resp| resp.into_result().unwrap().data.into_json().unwrap())
+        .boxed();
+    let mut stream_b = schema
+        .execute_stream(Request::new("subscription { myEvents { value } }").data("b".to_string()))
+        .boxed();
+    assert!(futures::poll!(stream_a.next()).is_pending());
+    assert!(futures::poll!(stream_b.next()).is_pending());
+    assert_eq!(broker.subscriber_count("events"), 2);
+
+    for (channel, value) in &[("a", 1), ("b", 2), ("a", 3)] {
+        schema
+            .execute(format!(
+                r#"mutation {{ send(channel: "{}", value: {}) }}"#,
+                channel, value
+            ))
+            .await
+            .into_result()
+            .unwrap();
+    }
+
+    assert_eq!(
+        stream_a.next().await,
+        Some(serde_json::json!({ "events": { "value": 1 } }))
+    );
+    assert_eq!(
+        stream_a.next().await,
+        Some(serde_json::json!({ "events": { "value": 3 } }))
+    );
+    assert_eq!(
+        stream_b.next().await.unwrap().data,
+        serde_json::json!({ "myEvents": { "value": 2 } })
+    );
+
+    // The second subscriber lags behind, which ends its subscription with an error.
+    for value in 4..10 {
+        schema
+            .execute(format!(
+                r#"mutation {{ send(channel: "b", value: {}) }}"#,
+                value
+            ))
+            .await
+            .into_result()
+            .unwrap();
+    }
+    assert_eq!(
+        stream_b.next().await.unwrap().into_result().unwrap_err(),
+        Error::Query {
+            pos: Pos {
+                line: 1,
+                column: 16
+            },
+            path: Some(serde_json::json!(["myEvents"])),
+            err: QueryError::DetailedFieldError {
+                err: "The subscriber lagged behind and missed 3 messages".to_string(),
+                extended_error: None,
+                user_facing: false,
+                source: Some(ErrorSource::new(BrokerError::Lagged(3))),
+            },
+        }
+    );
+    assert!(stream_b.next().await.is_none());
+}
@@ -0,0 +1,123 @@
+use async_graphql::guard::Guard;
+use async_graphql::*;
+
+struct Admin;
+
+struct AdminGuard;
+
+#[async_trait::async_trait]
+impl Guard for AdminGuard {
+    async fn check(&self, ctx: &Context<'_>) -> FieldResult<()> {
+        if ctx.data_opt::<Admin>().is_some() {
+            Ok(())
+        } else {
+            Err("Forbidden".into())
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+struct MyObj {
+    a: i32,
+    #[field(guard(AdminGuard()))]
+    b: i32,
+}
+
+#[ComplexObject]
+impl MyObj {
+    /// Sum of a, b and c
+    async fn sum(&self, #[arg(default = 0)] c: i32) -> i32 {
+        self.a + self.b + c
+    }
+
+    #[field(name = "aTimes", cache_control(max_age = 60))]
+    async fn a_times(&self, ctx: &Context<'_>, n: i32) -> FieldResult<i32> {
+        if n < 0 {
+            return Err("n must not be negative".into());
+        }
+        Ok(self.a * n + ctx.data_opt::<i32>().copied().unwrap_or_default())
+    }
+
+    #[field(guard(AdminGuard()))]
+    async fn secret(&self) -> String {
+        "secret".to_string()
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn obj(&self) -> MyObj {
+        MyObj { a: 10, b: 20 }
+    }
+}
+
+#[async_std::test]
+pub async fn test_complex_object() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let resp = schema
+        .execute("{ obj { a sum sum2: sum(c: 5) aTimes(n: 3) } }")
+        .await;
+    assert_eq!(resp.cache_control.max_age, 60);
+    assert_eq!(
+        resp.into_result().unwrap().data,
+        serde_json::json!({
+            "obj": { "a": 10, "sum": 30, "sum2": 35, "aTimes": 30 },
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ obj { aTimes(n: -1) } }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Query {
+            pos: Pos { line: 1, column: 9 },
+            path: Some(serde_json::json!(["obj", "aTimes"])),
+            err: QueryError::FieldError {
+                err: "n must not be negative".to_string(),
+                extended_error: None,
+            },
+        }
+    );
+
+    for query in &["{ obj { b } }", "{ obj { secret } }"] {
+        assert!(schema.execute(*query).await.is_err());
+        assert!(schema
+            .execute(Request::new(*query).data(Admin))
+            .await
+            .into_result()
+            .is_ok());
+    }
+}
+
+#[async_std::test]
+pub async fn test_complex_object_introspection() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .try_finish()
+        .unwrap();
+
+    assert_eq!(
+        schema
+            .execute(r#"{ __type(name: "MyObj") { fields { name description args { name } } } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "__type": {
+                "fields": [
+                    { "name": "a", "description": null, "args": [] },
+                    { "name": "b", "description": null, "args": [] },
+                    { "name": "sum", "description": "Sum of a, b and c", "args": [{ "name": "c" }] },
+                    { "name": "aTimes", "description": null, "args": [{ "name": "n" }] },
+                    { "name": "secret", "description": null, "args": [] },
+                ]
+            }
+        })
+    );
+}
@@ -92,3 +92,260 @@ pub async fn test_connection_additional_fields() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_opaque_cursor() {
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Key {
+        group: String,
+        id: usize,
+    }
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn items(
+            &self,
+            after: Option<String>,
+            first: Option<i32>,
+        ) -> FieldResult<Connection<OpaqueCursor<Key>, i32>> {
+            connection::query(
+                after,
+                None,
+                first,
+                None,
+                |after: Option<OpaqueCursor<Key>>, _, first, _| async move {
+                    let start = after.map(|key| key.id + 1).unwrap_or(0);
+                    let end = start + first.unwrap_or(2);
+                    let mut connection = Connection::new(start > 0, true);
+                    connection.append((start..end).map(|id| {
+                        Edge::new(
+                            OpaqueCursor(Key {
+                                group: "a".to_string(),
+                                id,
+                            }),
+                            id as i32,
+                        )
+                    }));
+                    Ok(connection)
+                },
+            )
+            .await
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+
+    let data = schema
+        .execute("{ items { edges { cursor node } } }")
+        .await
+        .into_result()
+        .unwrap()
+        .data
+        .into_json()
+        .unwrap();
+    let cursor = data["items"]["edges"][1]["cursor"].as_str().unwrap();
+    assert_eq!(OpaqueCursor::<Key>::decode_cursor(cursor).unwrap().id, 1);
+
+    assert_eq!(
+        schema
+            .execute(format!(
+                r#"{{ items(after: "{}") {{ edges {{ node }} }} }}"#,
+                cursor
+            ))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "items": { "edges": [{ "node": 2 }, { "node": 3 }] },
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute(r#"{ items(after: "!!!") { edges { node } } }"#)
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Query {
+            pos: Pos { line: 1, column: 3 },
+            path: Some(serde_json::json!(["items"])),
+            err: QueryError::DetailedFieldError {
+                err: "The \"after\" parameter is not a valid cursor: Invalid cursor encoding"
+                    .to_string(),
+                extended_error: None,
+                user_facing: true,
+                source: None,
+            },
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "Failed to serialize the cursor value")]
+pub fn test_opaque_cursor_unserializable() {
+    let mut key = std::collections::HashMap::new();
+    key.insert((1, 2), 3);
+    OpaqueCursor(key).encode_cursor();
+}
+
+#[async_std::test]
+pub async fn test_opaque_cursor_encode_error() {
+    use std::collections::HashMap;
+
+    // A map with non-string keys can't be serialized to JSON.
+    type Key = HashMap<(i32, i32), i32>;
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn items(&self) -> Connection<OpaqueCursor<Key>, i32> {
+            let mut key = HashMap::new();
+            key.insert((1, 2), 3);
+            let mut connection = Connection::new(false, false);
+            connection.append(vec![Edge::new(OpaqueCursor(key), 1)]);
+            connection
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    assert!(OpaqueCursor(Key::new()).try_encode_cursor().is_ok());
+
+    for (query, path) in &[
+        (
+            "{ items { edges { cursor } } }",
+            serde_json::json!(["items", "edges", 0, "cursor"]),
+        ),
+        (
+            "{ items { pageInfo { startCursor } } }",
+            serde_json::json!(["items", "pageInfo"]),
+        ),
+    ] {
+        let err = schema.execute(*query).await.into_result().unwrap_err();
+        match err {
+            Error::Query {
+                path: err_path,
+                err: QueryError::FieldError { err, .. },
+                ..
+            } => {
+                assert_eq!(err_path.as_ref(), Some(path));
+                assert!(err.starts_with("Failed to serialize the cursor value"));
+            }
+            err => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+#[async_std::test]
+pub async fn test_connection_helpers() {
+    struct QueryRoot {
+        items: Vec<String>,
+    }
+
+    #[Object]
+    impl QueryRoot {
+        async fn items(
+            &self,
+            after: Option<String>,
+            before: Option<String>,
+            first: Option<i32>,
+            last: Option<i32>,
+        ) -> FieldResult<Connection<usize, &String, TotalCount>> {
+            connection::query_iter(after, before, first, last, &self.items)
+        }
+
+        async fn rows(
+            &self,
+            after: Option<String>,
+            before: Option<String>,
+            first: Option<i32>,
+            last: Option<i32>,
+        ) -> FieldResult<Connection<usize, String, TotalCount>> {
+            connection::query_offset_limit(
+                after,
+                before,
+                first,
+                last,
+                || async move { Ok(self.items.len()) },
+                |offset, limit| async move { Ok(self.items[offset..offset + limit].to_vec()) },
+            )
+            .await
+        }
+    }
+
+    let schema = Schema::new(
+        QueryRoot {
+            items: vec!["a", "b", "c", "d", "e"]
+                .into_iter()
+                .map(ToString::to_string)
+                .collect(),
+        },
+        EmptyMutation,
+        EmptySubscription,
+    );
+
+    for field in &["items", "rows"] {
+        for (args, nodes, has_previous_page, has_next_page) in &[
+            ("first: 2", vec!["a", "b"], false, true),
+            ("last: 2", vec!["d", "e"], true, false),
+            (r#"after: "1", first: 2"#, vec!["c", "d"], true, true),
+            (r#"after: "1", before: "4""#, vec!["c", "d"], true, true),
+            (r#"before: "3", last: 1"#, vec!["c"], true, true),
+            (r#"after: "4""#, vec![], true, false),
+            ("first: 10", vec!["a", "b", "c", "d", "e"], false, false),
+        ] {
+            let query = format!(
+                "{{ {}({}) {{ edges {{ node }} pageInfo {{ hasPreviousPage hasNextPage }} }} }}",
+                field, args
+            );
+            assert_eq!(
+                schema.execute(query).await.into_result().unwrap().data,
+                serde_json::json!({
+                    *field: {
+                        "edges": nodes.iter().map(|node| serde_json::json!({ "node": node })).collect::<Vec<_>>(),
+                        "pageInfo": {
+                            "hasPreviousPage": has_previous_page,
+                            "hasNextPage": has_next_page,
+                        },
+                    },
+                }),
+                "{} ({})",
+                field,
+                args
+            );
+        }
+    }
+
+    assert_eq!(
+        schema
+            .execute(r#"{ rows(first: 1) { totalCount edges { cursor } } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "rows": { "totalCount": 5, "edges": [{ "cursor": "0" }] },
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute(r#"{ items(first: -1) { edges { node } } }"#)
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Query {
+            pos: Pos { line: 1, column: 3 },
+            path: Some(serde_json::json!(["items"])),
+            err: QueryError::DetailedFieldError {
+                err: "The \"first\" parameter must be a non-negative number".to_string(),
+                extended_error: None,
+                user_facing: true,
+                source: None,
+            },
+        }
+    );
+}
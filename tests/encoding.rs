@@ -0,0 +1,68 @@
+use async_graphql::http::{receive_batch_encoded, Encoding};
+use async_graphql::*;
+
+#[async_std::test]
+pub async fn test_encoding_round_trip() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn echo(&self, value: i64) -> i64 {
+            value
+        }
+
+        async fn max(&self) -> u64 {
+            u64::MAX
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let encodings = vec![
+        Encoding::Json,
+        #[cfg(feature = "cbor")]
+        Encoding::Cbor,
+        #[cfg(feature = "msgpack")]
+        Encoding::MessagePack,
+    ];
+
+    for encoding in encodings {
+        let body = encoding
+            .encode(&serde_json::json!([
+                {
+                    "query": "query ($value: Int!) { echo(value: $value) max }",
+                    "variables": { "value": i64::MIN },
+                },
+                { "query": "{ max }" },
+            ]))
+            .unwrap();
+        let request = receive_batch_encoded(futures::io::Cursor::new(body), encoding)
+            .await
+            .unwrap();
+
+        let response = schema.execute_batch(request).await;
+        let data: serde_json::Value = encoding
+            .decode(&encoding.encode(&response).unwrap())
+            .unwrap();
+        assert_eq!(
+            data,
+            serde_json::json!([
+                { "data": { "echo": i64::MIN, "max": u64::MAX } },
+                { "data": { "max": u64::MAX } },
+            ]),
+            "{:?}",
+            encoding
+        );
+    }
+}
+
+#[cfg(feature = "cbor")]
+#[async_std::test]
+pub async fn test_encoding_invalid_request() {
+    let body = Encoding::Json
+        .encode(&serde_json::json!({ "query": "{ max }" }))
+        .unwrap();
+    assert!(matches!(
+        receive_batch_encoded(futures::io::Cursor::new(body), Encoding::Cbor).await,
+        Err(ParseRequestError::InvalidEncodedRequest(_))
+    ));
+}
@@ -70,3 +70,91 @@ pub async fn test_error_extensions() {
         })
     );
 }
+
+#[test]
+pub fn test_error_extensions_keep_source() {
+    let err = FieldError::from("abc".parse::<i32>().unwrap_err())
+        .extend_with(|_| serde_json::json!({ "code": 400 }));
+    assert_eq!(err.message, "invalid digit found in string");
+    assert_eq!(err.extensions, Some(serde_json::json!({ "code": 400 })));
+    assert!(err
+        .source
+        .as_ref()
+        .and_then(|source| source.downcast_ref::<std::num::ParseIntError>())
+        .is_some());
+
+    let err = "abc"
+        .parse::<i32>()
+        .map_err(FieldError::from)
+        .extend_err(|_| serde_json::json!({ "code": 400 }))
+        .unwrap_err()
+        .extend_with(|_| serde_json::json!({ "details": "not a number" }));
+    assert_eq!(
+        err.extensions,
+        Some(serde_json::json!({ "code": 400, "details": "not a number" }))
+    );
+    assert!(err
+        .source
+        .as_ref()
+        .and_then(|source| source.downcast_ref::<std::num::ParseIntError>())
+        .is_some());
+
+    let err = "my error".extend_with(|_| serde_json::json!({ "code": 100 }));
+    assert!(err.source.is_none());
+    assert!(FieldError::from("my error").source.is_none());
+    assert!(FieldError::from("my error".to_string()).source.is_none());
+}
+
+#[test]
+pub fn test_custom_error_extensions() {
+    #[derive(Debug)]
+    struct NotFound;
+
+    impl std::fmt::Display for NotFound {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "not found")
+        }
+    }
+
+    impl ErrorExtensions for NotFound {
+        fn extend(&self) -> FieldError {
+            FieldError::from(NotFound).extend_with(|_| serde_json::json!({ "code": "NOT_FOUND" }))
+        }
+    }
+
+    let err = Err::<(), _>(NotFound)
+        .extend_err(|_| serde_json::json!({ "id": 1 }))
+        .unwrap_err();
+    assert_eq!(err.message, "not found");
+    assert_eq!(
+        err.extensions,
+        Some(serde_json::json!({ "code": "NOT_FOUND", "id": 1 }))
+    );
+    assert!(err
+        .source
+        .as_ref()
+        .and_then(|source| source.downcast_ref::<NotFound>())
+        .is_some());
+
+    let err = FieldError::from(NotFound);
+    assert_eq!(err.message, "not found");
+    assert!(err.source.unwrap().as_error().is_none());
+}
+
+#[test]
+pub fn test_error_source_eq() {
+    #[derive(Debug)]
+    struct OtherError;
+
+    impl std::fmt::Display for OtherError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "invalid digit found in string")
+        }
+    }
+
+    impl std::error::Error for OtherError {}
+
+    let parse_err = || "abc".parse::<i32>().unwrap_err();
+    assert_eq!(ErrorSource::new(parse_err()), ErrorSource::new(parse_err()));
+    assert_ne!(ErrorSource::new(parse_err()), ErrorSource::new(OtherError));
+}
@@ -128,7 +128,7 @@ pub async fn test_field_features() {
         stream
             .next()
             .await
-            .map(|resp| resp.into_result().unwrap().data),
+            .map(|resp| resp.into_result().unwrap().data.into_json().unwrap()),
         Some(serde_json::json!({
             "values": 10
         }))
@@ -136,7 +136,10 @@ pub async fn test_field_features() {
 
     let mut stream = schema.execute_stream("subscription { valuesBson }").boxed();
     assert_eq!(
-        stream.next().await.map(|resp| resp.data),
+        stream
+            .next()
+            .await
+            .map(|resp| resp.data.into_json().unwrap()),
         Some(serde_json::json!({
             "valuesBson": 10
         }))
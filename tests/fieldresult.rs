@@ -1,4 +1,6 @@
+use async_graphql::extensions::Extension;
 use async_graphql::*;
+use std::sync::{Arc, Mutex};
 
 #[async_std::test]
 pub async fn test_fieldresult() {
@@ -65,3 +67,202 @@ pub async fn test_fieldresult() {
         }
     );
 }
+
+#[async_std::test]
+pub async fn test_mask_errors() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn error(&self) -> FieldResult<i32> {
+            Err(FieldError::new("connection refused")
+                .extend_with(|_| serde_json::json!({ "host": "db" })))
+        }
+
+        async fn user_error(&self) -> FieldResult<i32> {
+            Err(FieldError::new("Invalid email")
+                .extend_with(|_| serde_json::json!({ "code": "INVALID_EMAIL" }))
+                .user_facing())
+        }
+    }
+
+    type Logged = Arc<Mutex<Vec<(String, Option<serde_json::Value>)>>>;
+
+    struct Capture(Logged);
+
+    impl Extension for Capture {
+        fn error(&mut self, err: &Error) {
+            if let Error::Query {
+                err:
+                    QueryError::FieldError {
+                        err,
+                        extended_error,
+                        ..
+                    },
+                ..
+            } = err
+            {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((err.clone(), extended_error.clone()));
+            }
+        }
+    }
+
+    let logged = Logged::default();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .extension({
+            let logged = logged.clone();
+            move || Capture(logged.clone())
+        })
+        .mask_errors()
+        .finish();
+
+    let err = schema.execute("{ error }").await.into_result().unwrap_err();
+    let correlation_id = match err {
+        Error::Query {
+            err:
+                QueryError::DetailedFieldError {
+                    err,
+                    extended_error: Some(extended_error),
+                    user_facing: true,
+                    source: None,
+                },
+            ..
+        } => {
+            assert_eq!(err, "Internal server error");
+            assert_eq!(extended_error.as_object().unwrap().len(), 1);
+            extended_error["correlationId"].clone()
+        }
+        err => panic!("unexpected error: {:?}", err),
+    };
+    assert_eq!(
+        logged.lock().unwrap()[0],
+        (
+            "connection refused".to_string(),
+            Some(serde_json::json!({
+                "host": "db",
+                "correlationId": correlation_id,
+            }))
+        )
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ userError }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Query {
+            pos: Pos { line: 1, column: 3 },
+            path: Some(serde_json::json!(["userError"])),
+            err: QueryError::DetailedFieldError {
+                err: "Invalid email".to_string(),
+                extended_error: Some(serde_json::json!({ "code": "INVALID_EMAIL" })),
+                user_facing: true,
+                source: None,
+            },
+        }
+    );
+}
+
+#[async_std::test]
+pub async fn test_error_source() {
+    #[derive(Debug)]
+    struct MyError(std::io::Error);
+
+    impl std::fmt::Display for MyError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Failed to load the user")
+        }
+    }
+
+    impl std::error::Error for MyError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn error(&self) -> FieldResult<i32> {
+            load().map_err(FieldError::from_error)
+        }
+
+        async fn converted_error(&self) -> FieldResult<i32> {
+            Ok(load()?)
+        }
+    }
+
+    fn load() -> std::result::Result<i32, MyError> {
+        Err(MyError(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "connection reset",
+        )))
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let err = schema
+        .execute("{ convertedError }")
+        .await
+        .into_result()
+        .unwrap_err();
+    let source = match &err {
+        Error::Query { err, .. } => err.error_source().unwrap(),
+        err => panic!("unexpected error: {:?}", err),
+    };
+    assert_eq!(source.to_string(), "Failed to load the user");
+    assert!(source.as_error().is_none());
+    assert_eq!(
+        source
+            .downcast_ref::<MyError>()
+            .and_then(|err| std::error::Error::source(err))
+            .map(|err| err.to_string()),
+        Some("connection reset".to_string())
+    );
+
+    let err = schema.execute("{ error }").await.into_result().unwrap_err();
+
+    let source = match &err {
+        Error::Query {
+            err:
+                QueryError::DetailedFieldError {
+                    err,
+                    source: Some(source),
+                    ..
+                },
+            ..
+        } => {
+            assert_eq!(err, "Failed to load the user");
+            source
+        }
+        err => panic!("unexpected error: {:?}", err),
+    };
+    assert!(source.downcast_ref::<MyError>().is_some());
+    assert_eq!(
+        source
+            .as_error()
+            .and_then(|err| err.source())
+            .map(|err| err.to_string()),
+        Some("connection reset".to_string())
+    );
+
+    let mut chain = Vec::new();
+    let mut cause = std::error::Error::source(&err);
+    while let Some(err) = cause {
+        chain.push(err.to_string());
+        cause = err.source();
+    }
+    assert_eq!(
+        chain,
+        vec![
+            "Failed to resolve field: Failed to load the user",
+            "Failed to load the user",
+            "connection reset",
+        ]
+    );
+}
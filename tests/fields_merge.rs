@@ -82,3 +82,39 @@ pub async fn test_field_object_merge() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_field_order() {
+    #[derive(SimpleObject)]
+    struct MyObject {
+        a: i32,
+        b: i32,
+        c: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn objs(&self) -> Vec<MyObject> {
+            vec![MyObject { a: 1, b: 2, c: 3 }, MyObject { a: 4, b: 5, c: 6 }]
+        }
+
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"
+        {
+            value
+            objs { c a }
+            ... { objs { b } }
+        }
+    "#;
+    assert_eq!(
+        serde_json::to_string(&schema.execute(query).await).unwrap(),
+        r#"{"data":{"value":10,"objs":[{"c":3,"a":1,"b":2},{"c":6,"a":4,"b":5}]}}"#
+    );
+}
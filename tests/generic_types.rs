@@ -47,3 +47,117 @@ pub async fn test_generic_object() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_concrete_simple_object() {
+    #[derive(SimpleObject)]
+    struct User {
+        name: String,
+    }
+
+    /// A page of items
+    #[derive(SimpleObject)]
+    #[graphql(
+        concrete(name = "IntPage", params(i32)),
+        concrete(name = "UserPage", params(User))
+    )]
+    struct Page<T: OutputValueType + Send + Sync> {
+        items: Vec<T>,
+        has_next_page: bool,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn ints(&self) -> Page<i32> {
+            Page {
+                items: vec![1, 2, 3],
+                has_next_page: true,
+            }
+        }
+
+        async fn users(&self) -> Page<User> {
+            Page {
+                items: vec![User {
+                    name: "abc".to_string(),
+                }],
+                has_next_page: false,
+            }
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    ints { items hasNextPage }
+                    users { items { name } hasNextPage }
+                    intPage: __type(name: "IntPage") { description }
+                    userPage: __type(name: "UserPage") { fields { name } }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "ints": { "items": [1, 2, 3], "hasNextPage": true },
+            "users": { "items": [{ "name": "abc" }], "hasNextPage": false },
+            "intPage": { "description": "A page of items" },
+            "userPage": { "fields": [{ "name": "items" }, { "name": "hasNextPage" }] },
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_concrete_input_object() {
+    #[derive(SimpleObject, InputObject)]
+    #[graphql(
+        concrete(name = "IntRange", input_name = "IntRangeInput", params(i32)),
+        concrete(name = "StringRange", input_name = "StringRangeInput", params(String))
+    )]
+    struct Range<T: OutputValueType + InputValueType + Send + Sync> {
+        start: T,
+        end: T,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn int_range(&self, input: Range<i32>) -> Range<i32> {
+            input
+        }
+
+        async fn string_range(&self, input: Range<String>) -> Range<String> {
+            input
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    intRange(input: { start: 1, end: 10 }) { start end }
+                    stringRange(input: { start: "a", end: "z" }) { start end }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "intRange": { "start": 1, "end": 10 },
+            "stringRange": { "start": "a", "end": "z" },
+        })
+    );
+
+    let sdl = Schema::<Query, EmptyMutation, EmptySubscription>::sdl();
+    assert!(sdl.contains("input IntRangeInput {\nstart: Int!\nend: Int!\n}"));
+    assert!(sdl.contains("input StringRangeInput {\nstart: String!\nend: String!\n}"));
+    assert!(sdl.contains("intRange(input: IntRangeInput!): IntRange!"));
+    assert!(sdl.contains("stringRange(input: StringRangeInput!): StringRange!"));
+}
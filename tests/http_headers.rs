@@ -0,0 +1,77 @@
+use async_graphql::*;
+
+#[async_std::test]
+pub async fn test_http_headers() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self, ctx: &Context<'_>) -> i32 {
+            assert!(ctx
+                .insert_http_header("Retry-After", "60")
+                .unwrap()
+                .is_none());
+            assert_eq!(
+                ctx.insert_http_header("Retry-After", "120").unwrap(),
+                Some(http::HeaderValue::from_static("60"))
+            );
+            assert!(ctx.append_http_header("Set-Cookie", "a=1").is_ok());
+            assert!(ctx.append_http_header("Set-Cookie", "b=2").is_ok());
+            assert!(matches!(
+                ctx.append_http_header("Set-Cookie", "c=\n"),
+                Err(http::HeaderError::InvalidValue(_))
+            ));
+            assert!(matches!(
+                ctx.insert_http_header("Set Cookie", "c=3"),
+                Err(http::HeaderError::InvalidName(_))
+            ));
+            10
+        }
+
+        async fn replace(&self, ctx: &Context<'_>) -> bool {
+            ctx.insert_http_header(http::HeaderName::from_static("deprecation"), "true")
+                .unwrap()
+                .is_none()
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+
+    let resp = schema.execute("{ value }").await;
+    assert_eq!(resp.data, serde_json::json!({ "value": 10 }));
+    assert_eq!(resp.http_headers.get("retry-after").unwrap(), "120");
+    let cookies: Vec<_> = resp.http_headers.get_all("set-cookie").iter().collect();
+    assert_eq!(cookies, vec!["a=1", "b=2"]);
+
+    let resp = schema.execute("{ replace }").await;
+    assert_eq!(resp.http_headers.len(), 1);
+    assert_eq!(resp.http_headers.get("deprecation").unwrap(), "true");
+
+    let resp = schema
+        .execute_batch(BatchRequest::Batch(vec![
+            Request::new("{ value }"),
+            Request::new("{ replace }"),
+        ]))
+        .await;
+    let headers = resp.http_headers();
+    assert_eq!(headers.get("deprecation").unwrap(), "true");
+    assert_eq!(headers.get_all("set-cookie").iter().count(), 2);
+}
+
+#[async_std::test]
+pub async fn test_http_headers_on_error() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn login(&self, ctx: &Context<'_>) -> FieldResult<bool> {
+            ctx.append_http_header("Set-Cookie", "session=1")?;
+            Err("failed".into())
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    let resp = schema.execute("{ login }").await;
+    assert!(resp.is_err());
+    assert_eq!(resp.http_headers.get("set-cookie").unwrap(), "session=1");
+}
@@ -295,3 +295,113 @@ pub async fn test_inputobject_flatten_multiple() {
         .unwrap()
     );
 }
+
+#[async_std::test]
+pub async fn test_both_input_output() {
+    /// A postal address
+    #[derive(SimpleObject, InputObject)]
+    #[graphql(input_name = "AddressInput")]
+    struct Address {
+        street: String,
+        #[field(input(default = "Berlin"), output(desc = "The city"))]
+        city: String,
+        #[field(output(skip))]
+        note: Option<String>,
+    }
+
+    #[derive(SimpleObject, InputObject)]
+    #[graphql(name = "Route", input_name = "RouteInput")]
+    struct Route {
+        stops: Vec<Address>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn echo(&self, address: Address) -> Address {
+            address
+        }
+
+        async fn route(&self, route: Route) -> Route {
+            route
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .try_finish()
+        .unwrap();
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    echo(address: { street: "Main St", note: "ignored" }) { street city }
+                    route(route: { stops: [{ street: "A", city: "Paris" }, { street: "B" }] }) {
+                        stops { street city }
+                    }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "echo": { "street": "Main St", "city": "Berlin" },
+            "route": {
+                "stops": [
+                    { "street": "A", "city": "Paris" },
+                    { "street": "B", "city": "Berlin" },
+                ]
+            },
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    input: __type(name: "AddressInput") { kind inputFields { name } }
+                    output: __type(name: "Address") { kind fields { name description } }
+                    query: __type(name: "Query") {
+                        fields { name args { type { ofType { name } } } type { ofType { name } } }
+                    }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "input": {
+                "kind": "INPUT_OBJECT",
+                "inputFields": [{ "name": "street" }, { "name": "city" }, { "name": "note" }],
+            },
+            "output": {
+                "kind": "OBJECT",
+                "fields": [
+                    { "name": "street", "description": null },
+                    { "name": "city", "description": "The city" },
+                ],
+            },
+            "query": {
+                "fields": [
+                    {
+                        "name": "echo",
+                        "args": [{ "type": { "ofType": { "name": "AddressInput" } } }],
+                        "type": { "ofType": { "name": "Address" } },
+                    },
+                    {
+                        "name": "route",
+                        "args": [{ "type": { "ofType": { "name": "RouteInput" } } }],
+                        "type": { "ofType": { "name": "Route" } },
+                    },
+                ],
+            },
+        })
+    );
+
+    let sdl = Schema::<Query, EmptyMutation, EmptySubscription>::sdl();
+    assert!(sdl.contains("input RouteInput {\nstops: [AddressInput!]!\n}"));
+    assert!(sdl.contains("type Route {\n\tstops: [Address!]!\n}"));
+}
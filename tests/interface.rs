@@ -331,3 +331,94 @@ pub async fn test_interface_field_method() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_interface_implements_interface() {
+    #[derive(SimpleObject)]
+    struct Image {
+        id: i32,
+        url: String,
+        width: i32,
+    }
+
+    #[derive(SimpleObject)]
+    struct Video {
+        id: i32,
+        url: String,
+        duration: i32,
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "&i32"))]
+    enum Node {
+        Image(Image),
+        Video(Video),
+    }
+
+    #[derive(Interface)]
+    #[graphql(
+        implements = "Node",
+        field(name = "id", type = "&i32"),
+        field(name = "url", type = "&String")
+    )]
+    enum Resource {
+        Image(Image),
+        Video(Video),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn resources(&self) -> Vec<Resource> {
+            vec![
+                Image {
+                    id: 1,
+                    url: "a.png".to_string(),
+                    width: 100,
+                }
+                .into(),
+                Video {
+                    id: 2,
+                    url: "b.mp4".to_string(),
+                    duration: 60,
+                }
+                .into(),
+            ]
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .register_type::<Node>()
+        .try_finish()
+        .unwrap();
+
+    let query = r#"{
+            resources {
+                ... on Node { id }
+                url
+                ... on Video { duration }
+            }
+            __type(name: "Resource") {
+                interfaces { name }
+            }
+        }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        serde_json::json!({
+            "resources": [
+                { "id": 1, "url": "a.png" },
+                { "id": 2, "url": "b.mp4", "duration": 60 },
+            ],
+            "__type": {
+                "interfaces": [{ "name": "Node" }],
+            }
+        })
+    );
+
+    let sdl = Schema::<Query, EmptyMutation, EmptySubscription>::sdl();
+    assert!(sdl.contains("interface Resource implements Node {"));
+    // Interfaces are listed in a stable order.
+    assert!(sdl.contains("type Image implements Node & Resource {"));
+    assert!(sdl.contains("type Video implements Node & Resource {"));
+}
@@ -71,7 +71,10 @@ struct SimpleOption {
 #[derive(Clone, Debug)]
 struct TestScalar(i32);
 
-#[Scalar(desc = "Test scalar")]
+#[Scalar(
+    desc = "Test scalar",
+    specified_by_url = "https://example.com/test-scalar"
+)]
 impl ScalarType for TestScalar {
     fn parse(_value: Value) -> InputValueResult<Self> {
         Ok(TestScalar(42))
@@ -673,6 +676,7 @@ pub async fn test_introspection_scalar() {
             kind
             name
             description
+            specifiedByURL
         }
    }
    "#;
@@ -682,6 +686,7 @@ pub async fn test_introspection_scalar() {
             "kind": "SCALAR",
             "name": "TestScalar",
             "description": "Test scalar",
+            "specifiedByURL": "https://example.com/test-scalar",
         }
     });
 
@@ -886,6 +891,132 @@ pub async fn test_introspection_input_object() {
     assert_eq!(res, res_json)
 }
 
+#[async_std::test]
+pub async fn test_introspection_input_value_deprecation() {
+    #[derive(InputObject)]
+    struct MyInput {
+        a: i32,
+        #[field(deprecation = "Use a")]
+        b: Option<i32>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(
+            &self,
+            input: MyInput,
+            #[arg(deprecation = "Use input")] _old: Option<i32>,
+        ) -> i32 {
+            input.a
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let query = r#"
+    {
+        query: __type(name: "Query") {
+            fields {
+                args { name }
+                allArgs: args(includeDeprecated: true) {
+                    name
+                    isDeprecated
+                    deprecationReason
+                }
+            }
+        }
+        input: __type(name: "MyInput") {
+            inputFields { name }
+            allInputFields: inputFields(includeDeprecated: true) {
+                name
+                isDeprecated
+                deprecationReason
+            }
+        }
+    }
+    "#;
+
+    let res_json = serde_json::json!({
+        "query": {
+            "fields": [
+                {
+                    "args": [{ "name": "input" }],
+                    "allArgs": [
+                        {
+                            "name": "input",
+                            "isDeprecated": false,
+                            "deprecationReason": null
+                        },
+                        {
+                            "name": "old",
+                            "isDeprecated": true,
+                            "deprecationReason": "Use input"
+                        }
+                    ]
+                }
+            ]
+        },
+        "input": {
+            "inputFields": [{ "name": "a" }],
+            "allInputFields": [
+                {
+                    "name": "a",
+                    "isDeprecated": false,
+                    "deprecationReason": null
+                },
+                {
+                    "name": "b",
+                    "isDeprecated": true,
+                    "deprecationReason": "Use a"
+                }
+            ]
+        }
+    });
+
+    let res = schema.execute(query).await.into_result().unwrap().data;
+
+    assert_eq!(res, res_json);
+
+    let sdl = Schema::<Query, EmptyMutation, EmptySubscription>::sdl();
+    assert!(sdl.contains(r#"old: Int @deprecated(reason: "Use input")"#));
+    assert!(sdl.contains(r#"b: Int @deprecated(reason: "Use a")"#));
+}
+
+#[async_std::test]
+pub async fn test_introspection_directives() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let query = r#"
+    {
+        __schema {
+            directives {
+                name
+                isRepeatable
+            }
+        }
+    }
+    "#;
+
+    let res = schema.execute(query).await.into_result().unwrap().data;
+    let res = serde_json::to_value(&res).unwrap();
+    let mut directives = res["__schema"]["directives"].as_array().unwrap().clone();
+    directives.sort_by_key(|directive| directive["name"].as_str().unwrap().to_string());
+
+    assert_eq!(
+        directives,
+        vec![
+            serde_json::json!({ "name": "deprecated", "isRepeatable": false }),
+            serde_json::json!({ "name": "ifdef", "isRepeatable": false }),
+            serde_json::json!({ "name": "include", "isRepeatable": false }),
+            serde_json::json!({ "name": "oneOf", "isRepeatable": false }),
+            serde_json::json!({ "name": "skip", "isRepeatable": false }),
+            serde_json::json!({ "name": "specifiedBy", "isRepeatable": false }),
+        ]
+    );
+}
+
 #[async_std::test]
 pub async fn test_introspection_mutation() {
     let schema = Schema::new(Query, Mutation, EmptySubscription);
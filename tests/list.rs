@@ -84,7 +84,7 @@ pub async fn test_list_type() {
             "#,
         json_value
     );
-    let mut res = schema.execute(&query).await.data;
+    let mut res = schema.execute(&query).await.data.into_json().unwrap();
 
     if let serde_json::Value::Object(obj) = &mut res {
         if let Some(value_hash_set) = obj.get_mut("valueHashSet") {
@@ -0,0 +1,316 @@
+use async_graphql::node::{GlobalId, GlobalIdCodec};
+use async_graphql::*;
+
+struct User {
+    id: i32,
+}
+
+#[Object(node(loader = "load_user"))]
+impl User {
+    async fn id(&self) -> GlobalId {
+        GlobalId::of::<Self>(self.id)
+    }
+
+    async fn name(&self) -> String {
+        format!("user{}", self.id)
+    }
+}
+
+async fn load_user(_ctx: &Context<'_>, id: &str) -> FieldResult<Option<User>> {
+    let id: i32 = id.parse()?;
+    Ok(if id < 10 { Some(User { id }) } else { None })
+}
+
+#[derive(SimpleObject)]
+#[graphql(node(loader = "Post::load"))]
+struct Post {
+    id: GlobalId,
+    title: String,
+}
+
+impl Post {
+    async fn load(_ctx: &Context<'_>, id: &str) -> FieldResult<Option<Post>> {
+        Ok(Some(Post {
+            id: GlobalId::new("Post", id),
+            title: format!("post {}", id),
+        }))
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn me(&self) -> User {
+        User { id: 1 }
+    }
+
+    async fn post(&self) -> Post {
+        Post {
+            id: GlobalId::new("Post", "a"),
+            title: "post a".to_string(),
+        }
+    }
+
+    async fn user_id(&self, ctx: &Context<'_>, id: ID) -> FieldResult<i32> {
+        let global_id = ctx.decode_global_id(&id)?;
+        Ok(global_id.parse_id()?)
+    }
+}
+
+fn global_id(type_name: &str, id: &str) -> String {
+    base64::encode(format!("{}:{}", type_name, id))
+}
+
+#[async_std::test]
+pub async fn test_node() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .try_finish()
+        .unwrap();
+
+    assert_eq!(
+        schema
+            .execute("{ me { id name } post { id title } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "me": { "id": global_id("User", "1"), "name": "user1" },
+            "post": { "id": global_id("Post", "a"), "title": "post a" },
+        })
+    );
+
+    let query = format!(
+        r#"{{
+            user: node(id: "{}") {{ id __typename ... on User {{ name }} }}
+            post: node(id: "{}") {{ id ... on Post {{ title }} }}
+            notFound: node(id: "{}") {{ id }}
+            unknownType: node(id: "{}") {{ id }}
+        }}"#,
+        global_id("User", "2"),
+        global_id("Post", "b"),
+        global_id("User", "20"),
+        global_id("Comment", "1"),
+    );
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        serde_json::json!({
+            "user": { "id": global_id("User", "2"), "__typename": "User", "name": "user2" },
+            "post": { "id": global_id("Post", "b"), "title": "post b" },
+            "notFound": null,
+            "unknownType": null,
+        })
+    );
+
+    let query = format!(
+        r#"{{ nodes(ids: ["{}", "{}", "{}"]) {{ id ... on Node {{ ... on User {{ name }} }} }} }}"#,
+        global_id("User", "3"),
+        global_id("User", "30"),
+        global_id("Post", "c"),
+    );
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        serde_json::json!({
+            "nodes": [
+                { "id": global_id("User", "3"), "name": "user3" },
+                null,
+                { "id": global_id("Post", "c") },
+            ]
+        })
+    );
+
+    let query = format!(r#"{{ userId(id: "{}") }}"#, global_id("User", "7"));
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        serde_json::json!({ "userId": 7 })
+    );
+}
+
+#[async_std::test]
+pub async fn test_node_errors() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute(r#"{ node(id: "abc") { id } }"#)
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Query {
+            pos: Pos { line: 1, column: 3 },
+            path: Some(serde_json::json!(["node"])),
+            err: QueryError::DetailedFieldError {
+                err: "Invalid global id \"abc\".".to_string(),
+                extended_error: None,
+                user_facing: true,
+                source: None,
+            },
+        }
+    );
+
+    let query = format!(r#"{{ node(id: "{}") {{ id }} }}"#, global_id("User", "x"));
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err(),
+        Error::Query {
+            pos: Pos { line: 1, column: 3 },
+            path: Some(serde_json::json!(["node"])),
+            err: QueryError::DetailedFieldError {
+                err: "invalid digit found in string".to_string(),
+                extended_error: None,
+                user_facing: false,
+                source: Some(ErrorSource::new("x".parse::<i32>().unwrap_err())),
+            },
+        }
+    );
+}
+
+#[async_std::test]
+pub async fn test_node_introspection() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    __type(name: "Node") { kind possibleTypes { name } fields { name } }
+                    user: __type(name: "User") { interfaces { name } }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "__type": {
+                "kind": "INTERFACE",
+                "possibleTypes": [{ "name": "User" }, { "name": "Post" }],
+                "fields": [{ "name": "id" }],
+            },
+            "user": { "interfaces": [{ "name": "Node" }] },
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_global_id_codec() {
+    struct PlainCodec;
+
+    impl GlobalIdCodec for PlainCodec {
+        fn encode(&self, id: &GlobalId) -> String {
+            format!("{}/{}", id.type_name, id.id)
+        }
+
+        fn decode(&self, s: &str) -> Option<GlobalId> {
+            let mut parts = s.splitn(2, '/');
+            Some(GlobalId::new(parts.next()?, parts.next()?))
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .global_id_codec(PlainCodec)
+        .finish();
+
+    assert_eq!(
+        schema
+            .execute(r#"{ me { id } node(id: "User/5") { ... on User { name } } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "me": { "id": "User/1" },
+            "node": { "name": "user5" },
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_node_conflicts() {
+    #[derive(SimpleObject)]
+    #[graphql(name = "Node")]
+    struct MyNode {
+        value: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn me(&self) -> User {
+            User { id: 1 }
+        }
+
+        async fn node(&self) -> MyNode {
+            MyNode { value: 1 }
+        }
+
+        async fn nodes(&self) -> Vec<MyNode> {
+            Vec::new()
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription).finish();
+    let errors = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .try_finish()
+        .err()
+        .unwrap();
+    for name in &["Node", "Query.node", "Query.nodes"] {
+        assert!(errors.contains(&SchemaError::NodeConflict {
+            name: name.to_string()
+        }));
+    }
+
+    // The types and fields defined by the schema are kept.
+    assert_eq!(
+        schema
+            .execute("{ node { value } nodes { value } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "node": { "value": 1 },
+            "nodes": [],
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_node_without_global_id() {
+    #[derive(SimpleObject)]
+    #[graphql(node(loader = "Comment::load"))]
+    struct Comment {
+        id: i32,
+    }
+
+    impl Comment {
+        async fn load(_ctx: &Context<'_>, id: &str) -> FieldResult<Option<Comment>> {
+            Ok(Some(Comment { id: id.parse()? }))
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn comment(&self) -> Comment {
+            Comment { id: 1 }
+        }
+    }
+
+    let errors = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .try_finish()
+        .err()
+        .unwrap();
+    assert_eq!(
+        errors.into_iter().collect::<Vec<_>>(),
+        vec![SchemaError::InterfaceFieldTypeMismatch {
+            interface: "Node".to_string(),
+            ty: "Comment".to_string(),
+            field: "id".to_string(),
+            expected: "ID!".to_string(),
+            found: "Int!".to_string(),
+        }]
+    );
+}
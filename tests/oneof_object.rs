@@ -0,0 +1,163 @@
+use async_graphql::*;
+
+#[derive(OneofObject)]
+enum UserBy {
+    Id(ID),
+    Email(String),
+    #[item(name = "login")]
+    Username(String),
+}
+
+fn find_user(by: UserBy) -> String {
+    match by {
+        UserBy::Id(id) => format!("id:{}", *id),
+        UserBy::Email(email) => format!("email:{}", email),
+        UserBy::Username(username) => format!("username:{}", username),
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn user(&self, by: UserBy) -> String {
+        find_user(by)
+    }
+
+    async fn user_or_default(
+        &self,
+        #[arg(default_with = "UserBy::Username(\"admin\".to_string())")] by: UserBy,
+    ) -> String {
+        find_user(by)
+    }
+}
+
+#[async_std::test]
+pub async fn test_oneof_object() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    a: user(by: { id: "1" })
+                    b: user(by: { email: "a@example.com" })
+                    c: user(by: { login: "abc" })
+                    d: userOrDefault
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "a": "id:1",
+            "b": "email:a@example.com",
+            "c": "username:abc",
+            "d": "username:admin",
+        })
+    );
+
+    for query in &[
+        r#"{ user(by: {}) }"#,
+        r#"{ user(by: { id: "1", email: "a@example.com" }) }"#,
+        r#"{ user(by: { id: null }) }"#,
+        r#"{ user(by: { name: "abc" }) }"#,
+    ] {
+        assert!(
+            schema.execute(*query).await.is_err(),
+            "query should fail: {}",
+            query
+        );
+    }
+}
+
+#[async_std::test]
+pub async fn test_oneof_object_variables() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let query = Request::new(
+        r#"query($by: UserBy!, $email: String!) {
+            a: user(by: $by)
+            b: user(by: { email: $email })
+        }"#,
+    )
+    .variables(Variables::from_json(serde_json::json!({
+        "by": { "id": "1" },
+        "email": "a@example.com",
+    })));
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        serde_json::json!({
+            "a": "id:1",
+            "b": "email:a@example.com",
+        })
+    );
+
+    for by in vec![
+        serde_json::json!({}),
+        serde_json::json!({ "id": "1", "email": "a@example.com" }),
+        serde_json::json!({ "id": null }),
+    ] {
+        let query = Request::new(r#"query($by: UserBy!) { user(by: $by) }"#)
+            .variables(Variables::from_json(serde_json::json!({ "by": by })));
+        assert!(schema.execute(query).await.is_err());
+    }
+
+    // A variable used as a field of a oneof input object must be non-null.
+    let query = Request::new(r#"query($email: String) { user(by: { email: $email }) }"#).variables(
+        Variables::from_json(serde_json::json!({ "email": "a@example.com" })),
+    );
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err(),
+        Error::Rule {
+            errors: vec![RuleError {
+                locations: vec![
+                    Pos { line: 1, column: 7 },
+                    Pos {
+                        line: 1,
+                        column: 25
+                    }
+                ],
+                message:
+                    r#"Variable "email" of type "String" used in position expecting type "String!""#
+                        .to_string(),
+            }]
+            .into(),
+        }
+    );
+}
+
+#[async_std::test]
+pub async fn test_oneof_object_introspection() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    __type(name: "UserBy") {
+                        isOneOf
+                        inputFields { name type { kind name } }
+                    }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "__type": {
+                "isOneOf": true,
+                "inputFields": [
+                    { "name": "id", "type": { "kind": "SCALAR", "name": "ID" } },
+                    { "name": "email", "type": { "kind": "SCALAR", "name": "String" } },
+                    { "name": "login", "type": { "kind": "SCALAR", "name": "String" } },
+                ],
+            }
+        })
+    );
+
+    assert!(Schema::<Query, EmptyMutation, EmptySubscription>::sdl()
+        .contains("input UserBy @oneOf {\nid: ID\nemail: String\nlogin: String\n}"));
+}
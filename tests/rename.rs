@@ -0,0 +1,402 @@
+use async_graphql::*;
+use futures::{Stream, StreamExt};
+
+#[async_std::test]
+pub async fn test_rename_enum_items() {
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    #[graphql(rename_items = "PascalCase")]
+    enum Color {
+        LightRed,
+        DarkGreen,
+        #[item(name = "blue")]
+        Blue,
+    }
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    #[graphql(rename_items = "lowercase")]
+    enum Size {
+        Small,
+        ExtraLarge,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn colors(&self, input: Vec<Color>) -> Vec<Color> {
+            input
+        }
+
+        async fn size(&self, input: Size) -> Size {
+            input
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ colors(input: [LightRed, DarkGreen, blue]) size(input: extralarge) }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "colors": ["LightRed", "DarkGreen", "blue"],
+            "size": "extralarge",
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_rename_object_fields_and_args() {
+    #[derive(SimpleObject)]
+    #[graphql(rename_fields = "snake_case")]
+    struct MyObj {
+        user_id: i32,
+        #[field(name = "DisplayName")]
+        display_name: String,
+    }
+
+    #[derive(InputObject)]
+    #[graphql(rename_fields = "SCREAMING_SNAKE_CASE")]
+    struct MyInput {
+        user_id: i32,
+        display_name: String,
+    }
+
+    struct Query;
+
+    #[Object(rename_fields = "PascalCase", rename_args = "snake_case")]
+    impl Query {
+        async fn get_obj(&self, user_id: i32, #[arg(name = "Name")] name: String) -> MyObj {
+            MyObj {
+                user_id,
+                display_name: name,
+            }
+        }
+
+        async fn from_input(&self, input_value: MyInput) -> MyObj {
+            MyObj {
+                user_id: input_value.user_id,
+                display_name: input_value.display_name,
+            }
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    GetObj(user_id: 10, Name: "abc") { user_id DisplayName }
+                    FromInput(input_value: { USER_ID: 20, DISPLAY_NAME: "def" }) { user_id DisplayName }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "GetObj": { "user_id": 10, "DisplayName": "abc" },
+            "FromInput": { "user_id": 20, "DisplayName": "def" },
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_rename_interface() {
+    struct MyObj {
+        user_id: i32,
+    }
+
+    #[Object(rename_fields = "snake_case", rename_args = "snake_case")]
+    impl MyObj {
+        async fn add_value(&self, other_value: i32) -> i32 {
+            self.user_id + other_value
+        }
+    }
+
+    #[derive(Interface)]
+    #[graphql(
+        rename_fields = "snake_case",
+        rename_args = "snake_case",
+        field(
+            name = "add_value",
+            type = "i32",
+            arg(name = "other_value", type = "i32")
+        )
+    )]
+    enum Node {
+        MyObj(MyObj),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn node(&self) -> Node {
+            MyObj { user_id: 10 }.into()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ node { add_value(other_value: 5) } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "node": { "add_value": 15 },
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_rename_subscription() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription(rename_fields = "snake_case", rename_args = "PascalCase")]
+    impl Subscription {
+        async fn create_values(&self, start_value: i32) -> impl Stream<Item = i32> {
+            futures::stream::iter(start_value..start_value + 2)
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let mut stream = schema
+        .execute_stream("subscription { create_values(StartValue: 10) }")
+        .map(|resp| resp.into_result().unwrap().data.into_json().unwrap())
+        .boxed();
+    for i in 10..12 {
+        assert_eq!(
+            Some(serde_json::json!({ "create_values": i })),
+            stream.next().await
+        );
+    }
+    assert!(stream.next().await.is_none());
+}
+
+#[async_std::test]
+pub async fn test_schema_rename_rules() {
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum Color {
+        LightRed,
+        #[item(name = "green")]
+        Green,
+    }
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    #[graphql(rename_items = "lowercase")]
+    enum Size {
+        ExtraLarge,
+    }
+
+    #[derive(InputObject)]
+    struct MyInput {
+        user_id: i32,
+        #[field(default_with = "Color::LightRed")]
+        main_color: Color,
+    }
+
+    #[derive(SimpleObject)]
+    struct MyObj {
+        user_id: i32,
+        main_color: Color,
+        #[field(name = "displayName")]
+        display_name: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn get_obj(
+            &self,
+            input_value: MyInput,
+            #[arg(default_with = "Color::Green")] other_color: Color,
+        ) -> MyObj {
+            MyObj {
+                user_id: input_value.user_id,
+                main_color: input_value.main_color,
+                display_name: format!("{}", other_color == Color::Green),
+            }
+        }
+
+        async fn colors(&self, input: Vec<Color>) -> Vec<Color> {
+            input
+        }
+
+        async fn size(&self, size_value: Size) -> Size {
+            size_value
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .rename_fields(RenameRule::SnakeCase)
+        .rename_args(RenameRule::PascalCase)
+        .rename_items(RenameRule::Lowercase)
+        .finish();
+
+    let sdl = schema.export_sdl();
+    for definition in &[
+        "get_obj(InputValue: MyInput!, OtherColor: Color! = green): MyObj!",
+        "input MyInput {\nuser_id: Int!\nmain_color: Color! = lightred\n}",
+        "enum Color {\n\tlightred\n\tgreen\n}",
+        "enum Size {\n\textralarge\n}",
+        "type MyObj {\n\tuser_id: Int!\n\tmain_color: Color!\n\tdisplayName: String!\n}",
+        "size(SizeValue: Size!): Size!",
+    ] {
+        assert!(sdl.contains(definition), "{} not in {}", definition, sdl);
+    }
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    a: get_obj(InputValue: { user_id: 10 }) { user_id main_color displayName }
+                    b: get_obj(InputValue: { user_id: 20, main_color: green }, OtherColor: lightred) {
+                        main_color displayName
+                    }
+                    colors(Input: [lightred, green])
+                    size(SizeValue: extralarge)
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "a": { "user_id": 10, "main_color": "lightred", "displayName": "true" },
+            "b": { "main_color": "green", "displayName": "false" },
+            "colors": ["lightred", "green"],
+            "size": "extralarge",
+        })
+    );
+
+    let query = r#"query($input: MyInput!, $colors: [Color!]!) {
+        get_obj(InputValue: $input) { user_id main_color }
+        colors(Input: $colors)
+    }"#;
+    let request = Request::new(query).variables(Variables::from_json(serde_json::json!({
+        "input": { "user_id": 30, "main_color": "green" },
+        "colors": ["lightred"],
+    })));
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap().data,
+        serde_json::json!({
+            "get_obj": { "user_id": 30, "main_color": "green" },
+            "colors": ["lightred"],
+        })
+    );
+
+    // The names in the code are not accepted.
+    assert!(schema
+        .execute("{ colors(Input: [LIGHT_RED]) }")
+        .await
+        .is_err());
+    assert!(schema
+        .execute("{ getObj(InputValue: { user_id: 1 }) { user_id } }")
+        .await
+        .is_err());
+
+    assert_eq!(
+        schema
+            .execute(r#"{ __type(name: "MyInput") { inputFields { name defaultValue } } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "__type": { "inputFields": [
+                { "name": "user_id", "defaultValue": null },
+                { "name": "main_color", "defaultValue": "lightred" },
+            ] }
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_schema_rename_rules_interface_and_subscription() {
+    struct MyObj;
+
+    #[Object]
+    impl MyObj {
+        async fn add_value(&self, other_value: i32) -> i32 {
+            other_value + 1
+        }
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(
+        name = "add_value",
+        type = "i32",
+        arg(name = "other_value", type = "i32")
+    ))]
+    enum MyInterface {
+        MyObj(MyObj),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn my_interface(&self) -> MyInterface {
+            MyObj.into()
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn create_values(&self, start_value: i32) -> impl Stream<Item = i32> {
+            futures::stream::iter(start_value..start_value + 2)
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, Subscription)
+        .rename_fields(RenameRule::SnakeCase)
+        .rename_args(RenameRule::SnakeCase)
+        .finish();
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    my_interface {
+                        add_value(other_value: 1)
+                        ... on MyObj { other: add_value(other_value: 2) }
+                    }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "my_interface": { "add_value": 2, "other": 3 },
+        })
+    );
+
+    let mut stream = schema
+        .execute_stream("subscription { create_values(start_value: 10) }")
+        .map(|resp| resp.into_result().unwrap().data.into_json().unwrap())
+        .boxed();
+    for i in 10..12 {
+        assert_eq!(
+            Some(serde_json::json!({ "create_values": i })),
+            stream.next().await
+        );
+    }
+    assert!(stream.next().await.is_none());
+}
@@ -9,3 +9,419 @@ pub async fn test_schema_default() {
 
     let _schema = MySchema::default();
 }
+
+#[async_std::test]
+pub async fn test_try_finish() {
+    #[derive(SimpleObject)]
+    struct MyObj {
+        id: i32,
+        title: String,
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "&i32"))]
+    enum Node {
+        MyObj(MyObj),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn node(&self) -> Node {
+            MyObj {
+                id: 1,
+                title: "haha".to_string(),
+            }
+            .into()
+        }
+    }
+
+    assert!(Schema::build(Query, EmptyMutation, EmptySubscription)
+        .try_finish()
+        .is_ok());
+}
+
+#[async_std::test]
+pub async fn test_try_finish_duplicate_type_name() {
+    mod a {
+        use async_graphql::*;
+
+        #[derive(SimpleObject)]
+        #[graphql(name = "User")]
+        pub struct User {
+            pub id: i32,
+        }
+    }
+
+    mod b {
+        use async_graphql::*;
+
+        #[derive(SimpleObject)]
+        #[graphql(name = "User")]
+        pub struct User {
+            pub name: String,
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn a(&self) -> a::User {
+            a::User { id: 1 }
+        }
+
+        async fn b(&self) -> b::User {
+            b::User {
+                name: "b".to_string(),
+            }
+        }
+    }
+
+    let errors = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .try_finish()
+        .err()
+        .unwrap();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        SchemaError::DuplicateTypeName { name, first, second }
+            if name == "User" && first.ends_with("a::User") && second.ends_with("b::User")
+    ));
+}
+
+#[async_std::test]
+pub async fn test_try_finish_duplicate_generic_type_name() {
+    struct MyObj<T> {
+        value: T,
+    }
+
+    #[Object(name = "MyObj")]
+    impl MyObj<i32> {
+        async fn value(&self) -> i32 {
+            self.value
+        }
+    }
+
+    #[Object(name = "MyObj")]
+    impl MyObj<i64> {
+        async fn value(&self) -> i64 {
+            self.value
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn a(&self) -> MyObj<i32> {
+            MyObj { value: 1 }
+        }
+
+        async fn b(&self) -> MyObj<i64> {
+            MyObj { value: 2 }
+        }
+    }
+
+    let errors = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .try_finish()
+        .err()
+        .unwrap();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        SchemaError::DuplicateTypeName { name, first, second }
+            if name == "MyObj" && first.ends_with("MyObj<i32>") && second.ends_with("MyObj<i64>")
+    ));
+}
+
+#[async_std::test]
+pub async fn test_try_finish_duplicate_scalar_name() {
+    struct Money(i64);
+
+    #[Scalar(name = "Amount")]
+    impl ScalarType for Money {
+        fn parse(value: Value) -> InputValueResult<Self> {
+            Ok(Money(<i64 as ScalarType>::parse(value)?))
+        }
+
+        fn to_value(&self) -> Value {
+            ScalarType::to_value(&self.0)
+        }
+    }
+
+    struct Weight(i64);
+
+    #[Scalar(name = "Amount")]
+    impl ScalarType for Weight {
+        fn parse(value: Value) -> InputValueResult<Self> {
+            Ok(Weight(<i64 as ScalarType>::parse(value)?))
+        }
+
+        fn to_value(&self) -> Value {
+            ScalarType::to_value(&self.0)
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn money(&self) -> Money {
+            Money(1)
+        }
+
+        async fn weight(&self) -> Weight {
+            Weight(2)
+        }
+
+        // Several Rust types share the built-in scalars and a generic scalar like `Json<T>`.
+        async fn values(&self, a: i32, b: i64, c: Json<Vec<i32>>) -> Json<Vec<String>> {
+            Json(vec![a.to_string(), b.to_string(), c.len().to_string()])
+        }
+    }
+
+    let errors = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .try_finish()
+        .err()
+        .unwrap();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        SchemaError::DuplicateTypeName { name, first, second }
+            if name == "Amount" && first.ends_with("Money") && second.ends_with("Weight")
+    ));
+}
+
+#[async_std::test]
+pub async fn test_try_finish_shared_builtin_scalar_name() {
+    use std::collections::{BTreeMap, HashMap};
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn hash_map(&self) -> HashMap<String, i32> {
+            HashMap::new()
+        }
+
+        async fn btree_map(&self) -> BTreeMap<String, i32> {
+            BTreeMap::new()
+        }
+    }
+
+    assert!(Schema::build(Query, EmptyMutation, EmptySubscription)
+        .try_finish()
+        .is_ok());
+
+    struct MyObject;
+
+    #[Scalar(name = "JSONObject")]
+    impl ScalarType for MyObject {
+        fn parse(_value: Value) -> InputValueResult<Self> {
+            Ok(MyObject)
+        }
+
+        fn to_value(&self) -> Value {
+            Value::Null
+        }
+    }
+
+    struct QueryWithCustomScalar;
+
+    #[Object]
+    impl QueryWithCustomScalar {
+        async fn hash_map(&self) -> HashMap<String, i32> {
+            HashMap::new()
+        }
+
+        async fn my_object(&self) -> MyObject {
+            MyObject
+        }
+    }
+
+    let errors = Schema::build(QueryWithCustomScalar, EmptyMutation, EmptySubscription)
+        .try_finish()
+        .err()
+        .unwrap();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        SchemaError::DuplicateTypeName { name, second, .. }
+            if name == "JSONObject" && second.ends_with("MyObject")
+    ));
+}
+
+#[cfg(all(feature = "bson", feature = "chrono"))]
+#[async_std::test]
+pub async fn test_try_finish_shared_date_time_scalar() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn chrono(&self) -> chrono::DateTime<chrono::Utc> {
+            chrono::Utc::now()
+        }
+
+        async fn bson(&self) -> bson::DateTime {
+            chrono::Utc::now().into()
+        }
+    }
+
+    assert!(Schema::build(Query, EmptyMutation, EmptySubscription)
+        .try_finish()
+        .is_ok());
+}
+
+#[async_std::test]
+pub async fn test_try_finish_interface_conformance() {
+    struct MyObj;
+
+    #[Object]
+    impl MyObj {
+        #[field(name = "identifier")]
+        async fn id(&self) -> i32 {
+            1
+        }
+
+        #[field(name = "value")]
+        async fn value_string(&self) -> Option<String> {
+            None
+        }
+
+        #[field(skip)]
+        async fn value(&self, _ctx: &Context<'_>) -> FieldResult<i32> {
+            Ok(1)
+        }
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "i32"), field(name = "value", type = "i32"))]
+    enum Node {
+        MyObj(MyObj),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn node(&self) -> Node {
+            MyObj.into()
+        }
+    }
+
+    assert_eq!(
+        Schema::build(Query, EmptyMutation, EmptySubscription)
+            .try_finish()
+            .err()
+            .unwrap(),
+        vec![
+            SchemaError::MissingInterfaceField {
+                interface: "Node".to_string(),
+                ty: "MyObj".to_string(),
+                field: "id".to_string(),
+            },
+            SchemaError::InterfaceFieldTypeMismatch {
+                interface: "Node".to_string(),
+                ty: "MyObj".to_string(),
+                field: "value".to_string(),
+                expected: "Int!".to_string(),
+                found: "String".to_string(),
+            },
+        ]
+        .into()
+    );
+}
+
+#[async_std::test]
+pub async fn test_try_finish_empty_types() {
+    #[derive(SimpleObject)]
+    struct EmptyObj {}
+
+    #[derive(InputObject)]
+    struct EmptyInput {}
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn obj(&self, _input: EmptyInput) -> EmptyObj {
+            EmptyObj {}
+        }
+    }
+
+    assert_eq!(
+        Schema::build(Query, EmptyMutation, EmptySubscription)
+            .try_finish()
+            .err()
+            .unwrap(),
+        vec![
+            SchemaError::NoFields {
+                name: "EmptyInput".to_string(),
+            },
+            SchemaError::NoFields {
+                name: "EmptyObj".to_string(),
+            },
+        ]
+        .into()
+    );
+}
+
+#[async_std::test]
+pub async fn test_try_finish_transitive_interfaces() {
+    #[derive(SimpleObject)]
+    struct Image {
+        id: i32,
+        url: String,
+    }
+
+    #[derive(SimpleObject)]
+    struct Link {
+        id: i32,
+        url: String,
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "&i32"))]
+    enum Node {
+        Image(Image),
+    }
+
+    #[derive(Interface)]
+    #[graphql(
+        implements = "Node",
+        field(name = "id", type = "&i32"),
+        field(name = "url", type = "&String")
+    )]
+    enum Resource {
+        Image(Image),
+        Link(Link),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn resource(&self) -> Resource {
+            Link {
+                id: 1,
+                url: "a".to_string(),
+            }
+            .into()
+        }
+    }
+
+    assert_eq!(
+        Schema::build(Query, EmptyMutation, EmptySubscription)
+            .try_finish()
+            .err()
+            .unwrap(),
+        vec![SchemaError::MissingTransitiveInterface {
+            ty: "Link".to_string(),
+            interface: "Resource".to_string(),
+            transitive_interface: "Node".to_string(),
+        }]
+        .into()
+    );
+}
@@ -0,0 +1,314 @@
+use async_graphql::extensions::{Extension, RedactedRequest, ResolveInfo};
+use async_graphql::*;
+use std::sync::{Arc, Mutex};
+
+#[derive(InputObject)]
+struct Credentials {
+    username: String,
+    #[field(secret)]
+    password: String,
+}
+
+struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    async fn login(&self, credentials: Credentials) -> String {
+        credentials.username
+    }
+
+    async fn change_password(
+        &self,
+        username: String,
+        #[arg(secret)] old_password: String,
+        #[arg(secret)] new_password: Option<String>,
+    ) -> String {
+        let _ = (old_password, new_password);
+        username
+    }
+}
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+type Captured = Arc<Mutex<Vec<(String, Variables)>>>;
+
+struct Capture(Captured);
+
+impl Extension for Capture {
+    fn redacted_request(&mut self, request: &RedactedRequest<'_>) {
+        self.0
+            .lock()
+            .unwrap()
+            .push((request.query(), request.variables()));
+    }
+}
+
+async fn capture(request: Request) -> (String, serde_json::Value) {
+    let captured = Captured::default();
+    let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .extension({
+            let captured = captured.clone();
+            move || Capture(captured.clone())
+        })
+        .finish();
+    assert!(schema.execute(request).await.is_ok());
+    let (query, variables) = captured.lock().unwrap().pop().unwrap();
+    (query, serde_json::to_value(variables).unwrap())
+}
+
+#[async_std::test]
+pub async fn test_secret_arguments() {
+    let (query, variables) = capture(Request::new(
+        r#"mutation { changePassword(username: "alice", oldPassword: "hunter2", newPassword: "p\"w") }"#,
+    ))
+    .await;
+    assert_eq!(
+        query,
+        r#"mutation { changePassword(username: "alice", oldPassword: "<redacted>", newPassword: "<redacted>") }"#
+    );
+    assert_eq!(variables, serde_json::json!({}));
+
+    let (query, variables) = capture(
+        Request::new(
+            r#"mutation($user: String!, $old: String!, $new: String = "default") {
+                changePassword(username: $user, oldPassword: $old, newPassword: $new)
+            }"#,
+        )
+        .variables(Variables::from_json(serde_json::json!({
+            "user": "alice",
+            "old": "hunter2",
+        }))),
+    )
+    .await;
+    assert_eq!(
+        query,
+        r#"mutation($user: String!, $old: String!, $new: String = "<redacted>") {
+                changePassword(username: $user, oldPassword: $old, newPassword: $new)
+            }"#
+    );
+    assert_eq!(
+        variables,
+        serde_json::json!({ "user": "alice", "old": "<redacted>" })
+    );
+}
+
+#[async_std::test]
+pub async fn test_secret_input_fields() {
+    let (query, variables) = capture(Request::new(
+        r#"mutation { login(credentials: { username: "alice", password: "hunter2" }) }"#,
+    ))
+    .await;
+    assert_eq!(
+        query,
+        r#"mutation { login(credentials: {username: "alice",password: "<redacted>",}) }"#
+    );
+    assert_eq!(variables, serde_json::json!({}));
+
+    let (query, variables) = capture(
+        Request::new(
+            r#"mutation($credentials: Credentials!) { login(credentials: $credentials) }"#,
+        )
+        .variables(Variables::from_json(serde_json::json!({
+            "credentials": { "username": "alice", "password": "hunter2" },
+        }))),
+    )
+    .await;
+    assert_eq!(
+        query,
+        r#"mutation($credentials: Credentials!) { login(credentials: $credentials) }"#
+    );
+    assert_eq!(
+        variables,
+        serde_json::json!({
+            "credentials": { "username": "alice", "password": "<redacted>" },
+        })
+    );
+
+    let (query, variables) = capture(
+        Request::new(
+            r#"mutation($password: String!) {
+                login(credentials: { username: "alice", password: $password })
+            }"#,
+        )
+        .variables(Variables::from_json(serde_json::json!({
+            "password": "hunter2",
+        }))),
+    )
+    .await;
+    assert!(query.contains(r#"login(credentials: { username: "alice", password: $password })"#));
+    assert_eq!(variables, serde_json::json!({ "password": "<redacted>" }));
+}
+
+#[async_std::test]
+pub async fn test_secret_in_fragments() {
+    let (query, _) = capture(Request::new(
+        r#"mutation { ...F } fragment F on MutationRoot { changePassword(username: "alice", oldPassword: "a", newPassword: "b") }"#,
+    ))
+    .await;
+    assert_eq!(
+        query,
+        r#"mutation { ...F } fragment F on MutationRoot { changePassword(username: "alice", oldPassword: "<redacted>", newPassword: "<redacted>") }"#
+    );
+}
+
+#[async_std::test]
+pub async fn test_secret_after_non_ascii() {
+    let (query, _) = capture(Request::new(
+        r#"mutation { changePassword(username: "élise", oldPassword: "hunter2", newPassword: "hunter3") }"#,
+    ))
+    .await;
+    assert_eq!(
+        query,
+        r#"mutation { changePassword(username: "élise", oldPassword: "<redacted>", newPassword: "<redacted>") }"#
+    );
+}
+
+#[async_std::test]
+pub async fn test_secret_after_non_ascii_lines() {
+    let (query, _) = capture(Request::new(
+        "# ünïcödé 😀\nmutation {\n  # 😀😀\n  changePassword(username: \"😀\", oldPassword: \"hunter2\", newPassword: \"é\")\n}",
+    ))
+    .await;
+    assert_eq!(
+        query,
+        "# ünïcödé 😀\nmutation {\n  # 😀😀\n  changePassword(username: \"😀\", oldPassword: \"<redacted>\", newPassword: \"<redacted>\")\n}"
+    );
+}
+
+type CapturedParse = Arc<Mutex<Vec<(String, serde_json::Value)>>>;
+
+struct CaptureParse(CapturedParse);
+
+impl Extension for CaptureParse {
+    fn parse_start(&mut self, query_source: &str, variables: &Variables) {
+        self.0.lock().unwrap().push((
+            query_source.to_string(),
+            serde_json::to_value(variables).unwrap(),
+        ));
+    }
+}
+
+#[async_std::test]
+pub async fn test_secret_parse_start() {
+    let captured = CapturedParse::default();
+    let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .extension({
+            let captured = captured.clone();
+            move || CaptureParse(captured.clone())
+        })
+        .finish();
+
+    let resp = schema
+        .execute(
+            Request::new(
+                r#"mutation($old: String!) { changePassword(username: "alice", oldPassword: $old, newPassword: "b") }"#,
+            )
+            .variables(Variables::from_json(serde_json::json!({ "old": "a" }))),
+        )
+        .await;
+    assert!(resp.is_ok());
+    assert_eq!(
+        captured.lock().unwrap().pop().unwrap(),
+        (
+            r#"mutation($old: String!) { changePassword(username: "alice", oldPassword: $old, newPassword: "<redacted>") }"#
+                .to_string(),
+            serde_json::json!({ "old": "<redacted>" })
+        )
+    );
+
+    let resp = schema
+        .execute(
+            Request::new(r#"mutation { changePassword(oldPassword: "hunter2" "#)
+                .variables(Variables::from_json(serde_json::json!({ "old": "a" }))),
+        )
+        .await;
+    assert!(resp.is_err());
+    assert_eq!(
+        captured.lock().unwrap().pop().unwrap(),
+        (
+            "<redacted>".to_string(),
+            serde_json::json!({ "old": "<redacted>" })
+        )
+    );
+}
+
+struct Account;
+
+#[Object]
+impl Account {
+    async fn name(&self) -> &str {
+        "alice"
+    }
+
+    async fn token(&self) -> &str {
+        "abc"
+    }
+}
+
+struct AccountQuery;
+
+#[Object]
+impl AccountQuery {
+    async fn account(&self) -> Account {
+        Account
+    }
+
+    #[field(secret)]
+    async fn secret_account(&self) -> Account {
+        Account
+    }
+
+    #[field(secret)]
+    async fn tokens(&self) -> Vec<&str> {
+        vec!["abc"]
+    }
+}
+
+type CapturedSecret = Arc<Mutex<Vec<(String, bool)>>>;
+
+struct CaptureSecret(CapturedSecret);
+
+impl Extension for CaptureSecret {
+    fn resolve_start(&mut self, info: &ResolveInfo<'_>) {
+        self.0
+            .lock()
+            .unwrap()
+            .push((info.path_node.to_string(), info.is_secret));
+    }
+}
+
+#[async_std::test]
+pub async fn test_secret_resolve_info() {
+    let captured = CapturedSecret::default();
+    let schema = Schema::build(AccountQuery, EmptyMutation, EmptySubscription)
+        .extension({
+            let captured = captured.clone();
+            move || CaptureSecret(captured.clone())
+        })
+        .finish();
+    assert!(schema
+        .execute("{ account { name } secretAccount { name } tokens }")
+        .await
+        .is_ok());
+
+    let mut captured = captured.lock().unwrap().clone();
+    captured.sort();
+    assert_eq!(
+        captured,
+        vec![
+            ("account".to_string(), false),
+            ("account.name".to_string(), false),
+            ("secretAccount".to_string(), true),
+            ("secretAccount.name".to_string(), true),
+            ("tokens".to_string(), true),
+            ("tokens.0".to_string(), true),
+        ]
+    );
+}
@@ -32,7 +32,7 @@ pub async fn test_subscription() {
     {
         let mut stream = schema
             .execute_stream("subscription { values(start: 10, end: 20) }")
-            .map(|resp| resp.into_result().unwrap().data)
+            .map(|resp| resp.into_result().unwrap().data.into_json().unwrap())
             .boxed();
         for i in 10..20 {
             assert_eq!(
@@ -46,7 +46,7 @@ pub async fn test_subscription() {
     {
         let mut stream = schema
             .execute_stream("subscription { events(start: 10, end: 20) { a b } }")
-            .map(|resp| resp.into_result().unwrap().data)
+            .map(|resp| resp.into_result().unwrap().data.into_json().unwrap())
             .boxed();
         for i in 10..20 {
             assert_eq!(
@@ -92,13 +92,21 @@ pub async fn test_subscription_with_ctx_data() {
 
     {
         let mut stream = schema
-            .execute_stream(Request::new("subscription { values objects { value } }").data(100i32))
-            .map(|resp| resp.data)
+            .execute_stream(Request::new("subscription { values }").data(100i32))
+            .map(|resp| resp.data.into_json().unwrap())
             .boxed();
         assert_eq!(
             Some(serde_json::json!({ "values": 100 })),
             stream.next().await
         );
+        assert!(stream.next().await.is_none());
+    }
+
+    {
+        let mut stream = schema
+            .execute_stream(Request::new("subscription { objects { value } }").data(100i32))
+            .map(|resp| resp.data.into_json().unwrap())
+            .boxed();
         assert_eq!(
             Some(serde_json::json!({ "objects": { "value": 100 } })),
             stream.next().await
@@ -135,7 +143,7 @@ pub async fn test_subscription_with_token() {
             .execute_stream(
                 Request::new("subscription { values }").data(Token("123456".to_string())),
             )
-            .map(|resp| resp.into_result().unwrap().data)
+            .map(|resp| resp.into_result().unwrap().data.into_json().unwrap())
             .boxed();
         assert_eq!(
             Some(serde_json::json!({ "values": 100 })),
@@ -193,7 +201,7 @@ pub async fn test_subscription_inline_fragment() {
             }
             "#,
         )
-        .map(|resp| resp.data)
+        .map(|resp| resp.data.into_json().unwrap())
         .boxed();
     for i in 10..20 {
         assert_eq!(
@@ -248,7 +256,7 @@ pub async fn test_subscription_fragment() {
             }
             "#,
         )
-        .map(|resp| resp.data)
+        .map(|resp| resp.data.into_json().unwrap())
         .boxed();
     for i in 10i32..20 {
         assert_eq!(
@@ -304,7 +312,7 @@ pub async fn test_subscription_fragment2() {
             }
             "#,
         )
-        .map(|resp| resp.data)
+        .map(|resp| resp.data.into_json().unwrap())
         .boxed();
     for i in 10..20 {
         assert_eq!(
@@ -350,7 +358,7 @@ pub async fn test_subscription_error() {
     let mut stream = schema
         .execute_stream("subscription { events { value } }")
         .map(|resp| resp.into_result())
-        .map_ok(|resp| resp.data)
+        .map_ok(|resp| resp.data.into_json().unwrap())
         .boxed();
     for i in 0i32..5 {
         assert_eq!(
@@ -400,7 +408,7 @@ pub async fn test_subscription_fieldresult() {
     let mut stream = schema
         .execute_stream("subscription { values }")
         .map(|resp| resp.into_result())
-        .map_ok(|resp| resp.data)
+        .map_ok(|resp| resp.data.into_json().unwrap())
         .boxed();
     for i in 0i32..5 {
         assert_eq!(
@@ -425,3 +433,356 @@ pub async fn test_subscription_fieldresult() {
 
     assert!(stream.next().await.is_none());
 }
+
+#[async_std::test]
+pub async fn test_subscription_error_policy() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    fn values() -> impl Stream<Item = FieldResult<i32>> {
+        futures::stream::iter((0..4).map(|n| {
+            if n % 2 == 1 {
+                Err(format!("Error{}", n).into())
+            } else {
+                Ok(n)
+            }
+        }))
+    }
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn values(&self) -> impl Stream<Item = FieldResult<i32>> {
+            values()
+        }
+
+        #[field(error_policy = "skip")]
+        async fn skip_values(&self) -> impl Stream<Item = FieldResult<i32>> {
+            values()
+        }
+
+        #[field(error_policy = "terminate")]
+        async fn terminate_values(&self) -> impl Stream<Item = FieldResult<i32>> {
+            values()
+        }
+    }
+
+    async fn collect(
+        schema: &Schema<QueryRoot, EmptyMutation, SubscriptionRoot>,
+        query: &str,
+    ) -> Vec<std::result::Result<serde_json::Value, String>> {
+        schema
+            .execute_stream(query)
+            .map(|resp| match resp.into_result() {
+                Ok(resp) => Ok(resp.data.into_json().unwrap()),
+                Err(Error::Query {
+                    err: QueryError::FieldError { err, .. },
+                    ..
+                }) => Err(err),
+                Err(err) => panic!("unexpected error: {:?}", err),
+            })
+            .collect()
+            .await
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+    assert_eq!(
+        collect(&schema, "subscription { values }").await,
+        vec![
+            Ok(serde_json::json!({ "values": 0 })),
+            Err("Error1".to_string())
+        ]
+    );
+    assert_eq!(
+        collect(&schema, "subscription { skipValues }").await,
+        vec![
+            Ok(serde_json::json!({ "skipValues": 0 })),
+            Ok(serde_json::json!({ "skipValues": 2 })),
+        ]
+    );
+
+    let schema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        .subscription_error_policy(SubscriptionErrorPolicy::Emit)
+        .finish();
+    assert_eq!(
+        collect(&schema, "subscription { values }").await,
+        vec![
+            Ok(serde_json::json!({ "values": 0 })),
+            Err("Error1".to_string()),
+            Ok(serde_json::json!({ "values": 2 })),
+            Err("Error3".to_string()),
+        ]
+    );
+    assert_eq!(
+        collect(&schema, "subscription { terminateValues }").await,
+        vec![
+            Ok(serde_json::json!({ "terminateValues": 0 })),
+            Err("Error1".to_string())
+        ]
+    );
+}
+
+#[async_std::test]
+pub async fn test_subscription_event_extension() {
+    use async_graphql::extensions::Extension;
+    use std::sync::{Arc, Mutex};
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        #[field(error_policy = "skip")]
+        async fn values(&self) -> impl Stream<Item = FieldResult<i32>> {
+            futures::stream::iter(vec![Ok(1), Err("TestError".into()), Ok(2)])
+        }
+    }
+
+    struct EventLogger(Arc<Mutex<Vec<String>>>);
+
+    impl Extension for EventLogger {
+        fn subscription_event_start(&mut self, field_name: &str) {
+            self.0.lock().unwrap().push(format!("start {}", field_name));
+        }
+
+        fn subscription_event_end(&mut self, field_name: &str) {
+            self.0.lock().unwrap().push(format!("end {}", field_name));
+        }
+
+        fn error(&mut self, err: &Error) {
+            if let Error::Query {
+                err: QueryError::FieldError { err, .. },
+                ..
+            } = err
+            {
+                self.0.lock().unwrap().push(format!("error {}", err));
+            }
+        }
+    }
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let schema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        .extension({
+            let log = log.clone();
+            move || EventLogger(log.clone())
+        })
+        .finish();
+
+    let data = schema
+        .execute_stream("subscription { a: values }")
+        .map(|resp| resp.into_result().unwrap().data)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(
+        data,
+        vec![serde_json::json!({ "a": 1 }), serde_json::json!({ "a": 2 })]
+    );
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            "start a",
+            "end a",
+            "start a",
+            "error TestError",
+            "end a",
+            "start a",
+            "end a",
+        ]
+    );
+}
+
+#[async_std::test]
+pub async fn test_subscription_error_policy_merged() {
+    #[derive(MergedObject, Default)]
+    struct QueryRoot(EmptyQuery);
+
+    #[derive(SimpleObject, Default)]
+    struct EmptyQuery {
+        value: i32,
+    }
+
+    fn values() -> impl Stream<Item = FieldResult<i32>> {
+        futures::stream::iter(vec![Ok(0), Err("Error1".into()), Ok(2)])
+    }
+
+    #[derive(Default)]
+    struct Subscription1;
+
+    #[Subscription]
+    impl Subscription1 {
+        #[field(error_policy = "emit")]
+        async fn emit_values(&self) -> impl Stream<Item = FieldResult<i32>> {
+            values()
+        }
+    }
+
+    #[derive(Default)]
+    struct Subscription2;
+
+    #[Subscription]
+    impl Subscription2 {
+        async fn values(&self) -> impl Stream<Item = FieldResult<i32>> {
+            values()
+        }
+    }
+
+    #[derive(MergedSubscription, Default)]
+    struct SubscriptionRoot(Subscription1, Subscription2);
+
+    let schema = Schema::new(
+        QueryRoot::default(),
+        EmptyMutation,
+        SubscriptionRoot::default(),
+    );
+
+    let responses = schema
+        .execute_stream("subscription { emitValues }")
+        .map(|resp| resp.is_err())
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(responses, vec![false, true, false]);
+
+    let responses = schema
+        .execute_stream("subscription { values }")
+        .map(|resp| resp.is_err())
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(responses, vec![false, true]);
+}
+
+#[async_std::test]
+pub async fn test_subscription_error_policy_manual_impl() {
+    use async_graphql::registry::{MetaField, MetaType, Registry};
+    use std::borrow::Cow;
+    use std::pin::Pin;
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    // Implemented without the derive, so the stream gets the default policy of the schema.
+    struct SubscriptionRoot;
+
+    impl Type for SubscriptionRoot {
+        fn type_name() -> Cow<'static, str> {
+            Cow::Borrowed("SubscriptionRoot")
+        }
+
+        fn create_type_info(registry: &mut Registry) -> String {
+            registry.create_type::<Self, _>(|registry| {
+                let mut fields = async_graphql::indexmap::IndexMap::new();
+                fields.insert(
+                    "values".to_string(),
+                    MetaField {
+                        name: "values".to_string(),
+                        description: None,
+                        args: Default::default(),
+                        ty: i32::create_type_info(registry),
+                        deprecation: None,
+                        cache_control: Default::default(),
+                        external: false,
+                        requires: None,
+                        provides: None,
+                        timeout: None,
+                        is_secret: false,
+                        default_names: None,
+                    },
+                );
+                MetaType::Object {
+                    name: "SubscriptionRoot".to_string(),
+                    description: None,
+                    fields,
+                    cache_control: Default::default(),
+                    extends: false,
+                    keys: None,
+                }
+            })
+        }
+    }
+
+    impl SubscriptionType for SubscriptionRoot {
+        fn create_field_stream<'a>(
+            &'a self,
+            ctx: &'a Context<'a>,
+        ) -> Pin<Box<dyn Stream<Item = Result<Value>> + Send + 'a>> {
+            let pos = ctx.item.pos;
+            Box::pin(futures::stream::iter((0..3).map(move |n| {
+                if n == 1 {
+                    Err(FieldError::from("Error1").into_error(pos))
+                } else {
+                    Ok(Value::from_json(serde_json::json!({ "values": n })).unwrap())
+                }
+            })))
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+    let responses = schema
+        .execute_stream("subscription { values }")
+        .map(|resp| resp.is_err())
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(responses, vec![false, true]);
+
+    let schema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        .subscription_error_policy(SubscriptionErrorPolicy::Skip)
+        .finish();
+    let responses = schema
+        .execute_stream("subscription { values }")
+        .map(|resp| resp.into_result().unwrap().data)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(
+        responses,
+        vec![
+            serde_json::json!({ "values": 0 }),
+            serde_json::json!({ "values": 2 })
+        ]
+    );
+}
+
+#[cfg(feature = "apollo_tracing")]
+#[async_std::test]
+pub async fn test_subscription_apollo_tracing() {
+    use async_graphql::extensions::ApolloTracing;
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures::stream::iter(0..3)
+        }
+    }
+
+    let schema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        .extension(ApolloTracing::default)
+        .finish();
+
+    // Each event is reported with the resolvers of that event only.
+    let resolvers = schema
+        .execute_stream("subscription { values }")
+        .map(|resp| {
+            let extensions = resp.extensions.unwrap();
+            extensions["tracing"]["execution"]["resolvers"]
+                .as_array()
+                .unwrap()
+                .len()
+        })
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(resolvers, vec![1, 1, 1]);
+}
@@ -1,6 +1,6 @@
 use async_graphql::*;
 use futures::channel::mpsc;
-use futures::{SinkExt, Stream, StreamExt};
+use futures::{Future, SinkExt, Stream, StreamExt};
 
 #[async_std::test]
 pub async fn test_subscription_ws_transport() {
@@ -312,3 +312,252 @@ pub async fn test_query_over_websocket() {
         serde_json::from_str(&stream.next().await.unwrap()).unwrap()
     );
 }
+
+#[async_std::test]
+pub async fn test_subscription_ws_multiplexing() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn chatty(&self) -> impl Stream<Item = i32> {
+            futures::stream::repeat(1)
+        }
+
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures::stream::iter(0..3)
+        }
+
+        async fn burst(&self) -> impl Stream<Item = i32> {
+            futures::stream::iter(0..100)
+        }
+    }
+
+    fn start(id: &str, query: &str) -> String {
+        serde_json::to_string(&serde_json::json!({
+            "type": "start",
+            "id": id,
+            "payload": { "query": query },
+        }))
+        .unwrap()
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+
+    // A chatty operation does not starve the others.
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema.clone(), rx);
+    tx.send(start("1", "subscription { chatty }"))
+        .await
+        .unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap()).unwrap(),
+        serde_json::json!({
+            "type": "data",
+            "id": "1",
+            "payload": { "data": { "chatty": 1 } },
+        })
+    );
+    tx.send(start("2", "subscription { values }"))
+        .await
+        .unwrap();
+    let mut values = Vec::new();
+    for _ in 0..100 {
+        let message =
+            serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap()).unwrap();
+        if message["id"] == "2" {
+            values.push(message);
+        }
+    }
+    assert_eq!(
+        values,
+        vec![
+            serde_json::json!({ "type": "data", "id": "2", "payload": { "data": { "values": 0 } } }),
+            serde_json::json!({ "type": "data", "id": "2", "payload": { "data": { "values": 1 } } }),
+            serde_json::json!({ "type": "data", "id": "2", "payload": { "data": { "values": 2 } } }),
+            serde_json::json!({ "type": "complete", "id": "2" }),
+        ]
+    );
+
+    tx.send(serde_json::to_string(&serde_json::json!({ "type": "stop", "id": "1" })).unwrap())
+        .await
+        .unwrap();
+    loop {
+        let message =
+            serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap()).unwrap();
+        if message["type"] == "complete" {
+            assert_eq!(
+                message,
+                serde_json::json!({ "type": "complete", "id": "1" })
+            );
+            break;
+        }
+    }
+    assert!(futures::poll!(stream.next()).is_pending());
+
+    // Limit the number of concurrent operations.
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema.clone(), rx).max_operations(1);
+    tx.send(start("1", "subscription { chatty }"))
+        .await
+        .unwrap();
+    tx.send(start("2", "subscription { values }"))
+        .await
+        .unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap()).unwrap(),
+        serde_json::json!({
+            "type": "error",
+            "id": "2",
+            "payload": {
+                "message": "Too many operations, the limit is 1.",
+                "extensions": null,
+            },
+        })
+    );
+
+    fn parse(message: String) -> serde_json::Value {
+        serde_json::from_str(&message).unwrap()
+    }
+
+    // Runs the connection until the consumer receives the `complete` message of the operation.
+    async fn run_until_complete<
+        F: Future<Output = std::result::Result<(), mpsc::SendError>> + Unpin,
+    >(
+        run: F,
+        messages: impl Stream<Item = String> + Unpin,
+    ) -> Vec<serde_json::Value> {
+        let messages = messages
+            .map(parse)
+            .take_while(|message| futures::future::ready(message["type"] != "complete"))
+            .collect::<Vec<_>>();
+        match futures::future::select(run, messages).await {
+            futures::future::Either::Right((messages, _)) => messages,
+            futures::future::Either::Left((res, _)) => panic!("connection closed: {:?}", res),
+        }
+    }
+
+    let burst = (0..100)
+        .map(|i| serde_json::json!({ "type": "data", "id": "1", "payload": { "data": { "burst": i } } }))
+        .collect::<Vec<_>>();
+
+    // A fast consumer gets every message of a burst, whatever the policy.
+    for &backpressure in &[
+        http::Backpressure::Wait,
+        http::Backpressure::DropOldest,
+        http::Backpressure::Close,
+    ] {
+        let (mut tx, rx) = mpsc::unbounded();
+        let stream = http::WebSocket::new(schema.clone(), rx)
+            .buffer_size(2)
+            .backpressure(backpressure);
+        tx.send(start("1", "subscription { burst }")).await.unwrap();
+        let messages = stream
+            .map(parse)
+            .take_while(|message| futures::future::ready(message["type"] != "complete"))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(messages, burst);
+
+        let (mut tx, rx) = mpsc::unbounded();
+        let (sink, messages) = mpsc::unbounded();
+        tx.send(start("1", "subscription { burst }")).await.unwrap();
+        let run = http::WebSocket::new(schema.clone(), rx)
+            .buffer_size(2)
+            .backpressure(backpressure)
+            .run(sink);
+        assert_eq!(run_until_complete(Box::pin(run), messages).await, burst);
+    }
+
+    // A stalled consumer loses the oldest messages.
+    let (mut tx, rx) = mpsc::unbounded();
+    let (sink, messages) = mpsc::channel(0);
+    tx.send(start("1", "subscription { burst }")).await.unwrap();
+    let mut run = Box::pin(
+        http::WebSocket::new(schema.clone(), rx)
+            .buffer_size(2)
+            .backpressure(http::Backpressure::DropOldest)
+            .run(sink),
+    );
+    assert!(futures::poll!(run.as_mut()).is_pending());
+    let messages = run_until_complete(run, messages).await;
+    assert!(messages.len() < 100);
+    assert_eq!(messages.first(), burst.first());
+    assert_eq!(messages.last(), burst.last());
+
+    // A stalled consumer with the `Wait` policy gets every message once it catches up.
+    let (mut tx, rx) = mpsc::unbounded();
+    let (sink, messages) = mpsc::channel(0);
+    tx.send(start("1", "subscription { burst }")).await.unwrap();
+    let mut run = Box::pin(
+        http::WebSocket::new(schema.clone(), rx)
+            .buffer_size(2)
+            .run(sink),
+    );
+    assert!(futures::poll!(run.as_mut()).is_pending());
+    assert_eq!(run_until_complete(run, messages).await, burst);
+
+    // Client messages are not read while the replies cannot be sent.
+    let (mut tx, rx) = mpsc::unbounded();
+    let (sink, _messages) = mpsc::channel(0);
+    let read = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    for _ in 0..10 {
+        tx.send("invalid".to_string()).await.unwrap();
+    }
+    let mut run = Box::pin(
+        http::WebSocket::new(
+            schema.clone(),
+            rx.inspect({
+                let read = read.clone();
+                move |_| {
+                    read.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                }
+            }),
+        )
+        .buffer_size(2)
+        .run(sink),
+    );
+    assert!(futures::poll!(run.as_mut()).is_pending());
+    assert!(futures::poll!(run.as_mut()).is_pending());
+    // One reply is in the sink and two are buffered.
+    assert_eq!(read.load(std::sync::atomic::Ordering::SeqCst), 3);
+
+    // A stalled consumer is disconnected.
+    let (mut tx, rx) = mpsc::unbounded();
+    let (sink, mut messages) = mpsc::channel(0);
+    tx.send(start("1", "subscription { chatty }"))
+        .await
+        .unwrap();
+    let mut run = Box::pin(
+        http::WebSocket::new(schema, rx)
+            .buffer_size(2)
+            .backpressure(http::Backpressure::Close)
+            .run(sink),
+    );
+    assert!(futures::poll!(run.as_mut()).is_pending());
+    let (res, messages) = futures::join!(run, async move {
+        let mut received = Vec::new();
+        while let Some(message) = messages.next().await {
+            received.push(parse(message));
+        }
+        received
+    });
+    assert!(res.is_ok());
+    assert_eq!(
+        messages,
+        vec![
+            serde_json::json!({ "type": "data", "id": "1", "payload": { "data": { "chatty": 1 } } }),
+            serde_json::json!({
+                "type": "connection_error",
+                "payload": {
+                    "message": "The client is too slow to receive the messages.",
+                    "extensions": null,
+                },
+            }),
+        ]
+    );
+}
@@ -0,0 +1,138 @@
+use async_graphql::*;
+use std::time::Duration;
+
+struct Detail;
+
+#[Object]
+impl Detail {
+    async fn slow(&self) -> i32 {
+        async_std::task::sleep(Duration::from_millis(500)).await;
+        1
+    }
+
+    async fn fast(&self) -> i32 {
+        2
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+
+    async fn detail(&self) -> Detail {
+        Detail
+    }
+
+    #[field(timeout = "50ms")]
+    async fn limited(&self) -> i32 {
+        async_std::task::sleep(Duration::from_millis(500)).await;
+        1
+    }
+
+    #[field(timeout = "1s")]
+    async fn budget(&self, ctx: &Context<'_>) -> bool {
+        ctx.remaining_time().unwrap() <= Duration::from_secs(1)
+    }
+
+    async fn has_deadline(&self, ctx: &Context<'_>) -> bool {
+        ctx.deadline().is_some()
+    }
+}
+
+#[async_std::test]
+pub async fn test_request_timeout() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute(
+                Request::new("{ value detail { fast slow } }").timeout(Duration::from_millis(50))
+            )
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Query {
+            pos: Pos {
+                line: 1,
+                column: 23
+            },
+            path: Some(serde_json::json!(["detail", "slow"])),
+            err: QueryError::Timeout,
+        }
+    );
+
+    assert_eq!(
+        schema
+            .execute(Request::new("{ value detail { fast } }").timeout(Duration::from_millis(50)))
+            .await
+            .data,
+        serde_json::json!({
+            "value": 10,
+            "detail": { "fast": 2 },
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_schema_timeout() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .timeout(Duration::from_millis(50))
+        .finish();
+
+    assert_eq!(
+        schema
+            .execute("{ detail { slow } }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Query {
+            pos: Pos {
+                line: 1,
+                column: 12
+            },
+            path: Some(serde_json::json!(["detail", "slow"])),
+            err: QueryError::Timeout,
+        }
+    );
+
+    assert_eq!(
+        schema.execute("{ hasDeadline }").await.data,
+        serde_json::json!({ "hasDeadline": true })
+    );
+
+    // The timeout of the request overrides the one of the schema.
+    assert_eq!(
+        schema
+            .execute(Request::new("{ detail { slow } }").timeout(Duration::from_secs(5)))
+            .await
+            .data,
+        serde_json::json!({ "detail": { "slow": 1 } })
+    );
+}
+
+#[async_std::test]
+pub async fn test_field_timeout() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute("{ limited }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        Error::Query {
+            pos: Pos { line: 1, column: 3 },
+            path: Some(serde_json::json!(["limited"])),
+            err: QueryError::Timeout,
+        }
+    );
+
+    assert_eq!(
+        schema.execute("{ budget hasDeadline }").await.data,
+        serde_json::json!({ "budget": true, "hasDeadline": false })
+    );
+}
@@ -0,0 +1,284 @@
+#![cfg(feature = "multipart")]
+
+use async_graphql::http::{
+    MemoryStorage, MultipartOptions, SinkStorage, UploadInfo, UploadStorage,
+};
+use async_graphql::parser::types::UploadContent;
+use async_graphql::*;
+use bytes::Bytes;
+use futures::channel::mpsc;
+use futures::stream::BoxStream;
+use futures::{SinkExt, StreamExt};
+use std::io::{self, Read};
+use std::sync::{Arc, Mutex};
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+struct Mutation;
+
+#[Object]
+impl Mutation {
+    async fn upload(&self, files: Vec<Upload>) -> Vec<String> {
+        files
+            .into_iter()
+            .map(|file| {
+                let filename = file.filename().to_string();
+                let size = file.size().unwrap();
+                let stored = match file.content() {
+                    UploadContent::File(_) => "file",
+                    UploadContent::Memory(_) => "memory",
+                    UploadContent::Custom { .. } => return format!("{} custom {}", filename, size),
+                };
+                let mut data = String::new();
+                file.into_read().read_to_string(&mut data).unwrap();
+                format!("{} {} {}", filename, stored, data)
+            })
+            .collect()
+    }
+}
+
+const CONTENT_TYPE: &str = "multipart/form-data; boundary=boundary";
+
+fn multipart_body(files: &[(&str, &str)]) -> futures::io::Cursor<Vec<u8>> {
+    let operations = serde_json::json!({
+        "query": "mutation ($files: [Upload!]!) { upload(files: $files) }",
+        "variables": { "files": vec![serde_json::Value::Null; files.len()] },
+    });
+    let map = (0..files.len())
+        .map(|idx| {
+            (
+                idx.to_string(),
+                serde_json::json!([format!("variables.files.{}", idx)]),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>();
+
+    let mut body = format!(
+        "--boundary\r\nContent-Disposition: form-data; name=\"operations\"\r\n\r\n{}\r\n\
+         --boundary\r\nContent-Disposition: form-data; name=\"map\"\r\n\r\n{}\r\n",
+        operations,
+        serde_json::to_string(&map).unwrap(),
+    );
+    for (idx, (filename, data)) in files.iter().enumerate() {
+        body.push_str(&format!(
+            "--boundary\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\
+             Content-Type: text/plain\r\n\r\n{}\r\n",
+            idx, filename, data
+        ));
+    }
+    body.push_str("--boundary--\r\n");
+    futures::io::Cursor::new(body.into_bytes())
+}
+
+async fn upload(
+    files: &[(&str, &str)],
+    opts: MultipartOptions,
+) -> std::result::Result<serde_json::Value, ParseRequestError> {
+    let request = http::receive_body(Some(CONTENT_TYPE), multipart_body(files), opts).await?;
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    Ok(schema
+        .execute(request)
+        .await
+        .into_result()
+        .unwrap()
+        .data
+        .into_json()
+        .unwrap())
+}
+
+#[async_std::test]
+pub async fn test_upload_temp_file_storage() {
+    assert_eq!(
+        upload(&[("a.txt", "hello")], MultipartOptions::default())
+            .await
+            .unwrap(),
+        serde_json::json!({ "upload": ["a.txt file hello"] })
+    );
+}
+
+#[async_std::test]
+pub async fn test_upload_memory_storage() {
+    let files = [("a.txt", "hello"), ("b.txt", "hello world")];
+
+    assert_eq!(
+        upload(
+            &files[..1],
+            MultipartOptions::default().storage(MemoryStorage::new(10))
+        )
+        .await
+        .unwrap(),
+        serde_json::json!({ "upload": ["a.txt memory hello"] })
+    );
+
+    assert!(matches!(
+        upload(
+            &files,
+            MultipartOptions::default().storage(MemoryStorage::new(10))
+        )
+        .await,
+        Err(ParseRequestError::PayloadTooLarge)
+    ));
+
+    assert_eq!(
+        upload(
+            &files,
+            MultipartOptions::default().storage(MemoryStorage::new(10).fallback_to_temp_file())
+        )
+        .await
+        .unwrap(),
+        serde_json::json!({ "upload": ["a.txt memory hello", "b.txt file hello world"] })
+    );
+}
+
+#[async_std::test]
+pub async fn test_upload_sink_storage() {
+    type Received = Arc<Mutex<Vec<(UploadInfo, mpsc::UnboundedReceiver<Bytes>)>>>;
+
+    let received: Received = Default::default();
+    let storage = SinkStorage::new({
+        let received = received.clone();
+        move |info| {
+            let (tx, rx) = mpsc::unbounded();
+            received.lock().unwrap().push((info, rx));
+            futures::future::ok(tx.sink_map_err(|err| io::Error::new(io::ErrorKind::Other, err)))
+        }
+    });
+
+    assert_eq!(
+        upload(
+            &[("a.txt", "hello"), ("b.txt", "world!")],
+            MultipartOptions::default().storage(storage)
+        )
+        .await
+        .unwrap(),
+        serde_json::json!({ "upload": ["a.txt custom 5", "b.txt custom 6"] })
+    );
+
+    let received = std::mem::take(&mut *received.lock().unwrap());
+    let mut files = Vec::new();
+    for (info, rx) in received {
+        let data = rx.map(|chunk| chunk.to_vec()).concat().await;
+        files.push((
+            info.name,
+            info.filename,
+            info.content_type,
+            String::from_utf8(data).unwrap(),
+        ));
+    }
+    assert_eq!(
+        files,
+        vec![
+            (
+                "0".to_string(),
+                "a.txt".to_string(),
+                Some("text/plain".to_string()),
+                "hello".to_string()
+            ),
+            (
+                "1".to_string(),
+                "b.txt".to_string(),
+                Some("text/plain".to_string()),
+                "world!".to_string()
+            ),
+        ]
+    );
+}
+
+#[async_std::test]
+pub async fn test_upload_custom_storage() {
+    struct UppercaseStorage;
+
+    #[async_trait::async_trait]
+    impl UploadStorage for UppercaseStorage {
+        async fn store(
+            &self,
+            info: &UploadInfo,
+            mut chunks: BoxStream<'_, io::Result<Bytes>>,
+        ) -> io::Result<UploadContent> {
+            if info.filename.ends_with(".exe") {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    ParseRequestError::NotUpload,
+                ));
+            }
+            if info.filename.ends_with(".bin") {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "not text"));
+            }
+            let mut data = Vec::new();
+            while let Some(chunk) = chunks.next().await {
+                data.extend(chunk?.to_ascii_uppercase());
+            }
+            Ok(UploadContent::Memory(data.into()))
+        }
+    }
+
+    assert_eq!(
+        upload(
+            &[("a.txt", "hello")],
+            MultipartOptions::default().storage(UppercaseStorage)
+        )
+        .await
+        .unwrap(),
+        serde_json::json!({ "upload": ["a.txt memory HELLO"] })
+    );
+
+    assert!(matches!(
+        upload(
+            &[("a.exe", "hello")],
+            MultipartOptions::default().storage(UppercaseStorage)
+        )
+        .await,
+        Err(ParseRequestError::NotUpload)
+    ));
+
+    match upload(
+        &[("a.bin", "hello")],
+        MultipartOptions::default().storage(UppercaseStorage),
+    )
+    .await
+    {
+        Err(ParseRequestError::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::InvalidData),
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
+
+#[async_std::test]
+pub async fn test_upload_limits() {
+    let files = [("a.txt", "hello"), ("b.txt", "world")];
+
+    assert_eq!(
+        upload(&files, MultipartOptions::default().max_num_files(2))
+            .await
+            .unwrap(),
+        serde_json::json!({ "upload": ["a.txt file hello", "b.txt file world"] })
+    );
+
+    assert!(matches!(
+        upload(&files, MultipartOptions::default().max_num_files(1)).await,
+        Err(ParseRequestError::PayloadTooLarge)
+    ));
+
+    // The limit is large enough for the "operations" and "map" fields.
+    let large = "x".repeat(300);
+    assert_eq!(
+        upload(&files, MultipartOptions::default().max_file_size(200))
+            .await
+            .unwrap(),
+        serde_json::json!({ "upload": ["a.txt file hello", "b.txt file world"] })
+    );
+    assert!(matches!(
+        upload(
+            &[("a.txt", "hello"), ("b.txt", large.as_str())],
+            MultipartOptions::default().max_file_size(200)
+        )
+        .await,
+        Err(ParseRequestError::PayloadTooLarge)
+    ));
+}
@@ -0,0 +1,175 @@
+use async_graphql::parser::types::{Field, Name, OperationDefinition};
+use async_graphql::validation::{Visitor, VisitorContext};
+use async_graphql::*;
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn items(&self, first: Option<i32>) -> Vec<i32> {
+        (0..first.unwrap_or(10)).collect()
+    }
+}
+
+struct NamedOperations;
+
+impl<'a> Visitor<'a> for NamedOperations {
+    fn enter_operation_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        name: Option<&'a Name>,
+        operation_definition: &'a Positioned<OperationDefinition>,
+    ) {
+        if name.is_none() {
+            ctx.report_error(
+                vec![operation_definition.pos],
+                "Every operation must be named",
+            );
+        }
+    }
+}
+
+struct LimitFirst(i32);
+
+impl<'a> Visitor<'a> for LimitFirst {
+    fn enter_field(&mut self, ctx: &mut VisitorContext<'a>, field: &'a Positioned<Field>) {
+        if let Some(first) = field.node.get_argument("first") {
+            if let parser::types::Value::Number(n) = &first.node {
+                if n.as_i64().map_or(true, |n| n > self.0 as i64) {
+                    ctx.report_error(
+                        vec![first.pos],
+                        format!("\"first\" must not be greater than {}", self.0),
+                    );
+                }
+            }
+        }
+    }
+}
+
+struct NoNegativeNumbers;
+
+impl<'a> Visitor<'a> for NoNegativeNumbers {
+    fn enter_input_value(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        pos: Pos,
+        _expected_type: &Option<registry::MetaTypeName<'a>>,
+        value: &'a parser::types::Value,
+    ) {
+        if let parser::types::Value::Number(n) = value {
+            if n.as_i64().map_or(false, |n| n < 0) {
+                ctx.report_error(vec![pos], "Numbers must not be negative");
+            }
+        }
+    }
+}
+
+struct AllowIntrospection;
+
+struct IntrospectionNeedsPermission;
+
+impl<'a> Visitor<'a> for IntrospectionNeedsPermission {
+    fn enter_field(&mut self, ctx: &mut VisitorContext<'a>, field: &'a Positioned<Field>) {
+        if field.node.name.node == "__schema" && ctx.data_opt::<AllowIntrospection>().is_none() {
+            ctx.report_error(vec![field.pos], "Introspection is not allowed");
+        }
+    }
+}
+
+#[async_std::test]
+pub async fn test_custom_validation_rules() {
+    for mode in &[ValidationMode::Strict, ValidationMode::Fast] {
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .validation_mode(*mode)
+            .validation_rule(|| NamedOperations)
+            .validation_rule(|| LimitFirst(100))
+            .finish();
+
+        assert_eq!(
+            schema
+                .execute("query Items { items(first: 3) }")
+                .await
+                .into_result()
+                .unwrap()
+                .data
+                .into_json()
+                .unwrap(),
+            serde_json::json!({ "items": [0, 1, 2] })
+        );
+
+        assert_eq!(
+            schema
+                .execute("{ items(first: 3) }")
+                .await
+                .into_result()
+                .unwrap_err(),
+            Error::Rule {
+                errors: vec![RuleError {
+                    locations: vec![Pos { line: 1, column: 1 }],
+                    message: "Every operation must be named".to_string(),
+                }]
+                .into()
+            }
+        );
+
+        assert_eq!(
+            schema
+                .execute("query Items { items(first: 101) }")
+                .await
+                .into_result()
+                .unwrap_err(),
+            Error::Rule {
+                errors: vec![RuleError {
+                    locations: vec![Pos {
+                        line: 1,
+                        column: 28
+                    }],
+                    message: "\"first\" must not be greater than 100".to_string(),
+                }]
+                .into()
+            }
+        );
+    }
+}
+
+#[async_std::test]
+pub async fn test_custom_validation_rule_input_values() {
+    for mode in &[ValidationMode::Strict, ValidationMode::Fast] {
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .validation_mode(*mode)
+            .validation_rule(|| NoNegativeNumbers)
+            .finish();
+
+        assert!(schema.execute("{ items(first: 3) }").await.is_ok());
+        assert_eq!(
+            schema
+                .execute("{ items(first: -3) }")
+                .await
+                .into_result()
+                .unwrap_err(),
+            Error::Rule {
+                errors: vec![RuleError {
+                    locations: vec![Pos { line: 1, column: 3 }],
+                    message: "Numbers must not be negative".to_string(),
+                }]
+                .into()
+            }
+        );
+    }
+}
+
+#[async_std::test]
+pub async fn test_custom_validation_rule_with_data() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .validation_rule(|| IntrospectionNeedsPermission)
+        .finish();
+
+    assert!(schema
+        .execute("{ __schema { queryType { name } } }")
+        .await
+        .is_err());
+    assert!(schema
+        .execute(Request::new("{ __schema { queryType { name } } }").data(AllowIntrospection))
+        .await
+        .is_ok());
+}
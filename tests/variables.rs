@@ -262,6 +262,73 @@ pub async fn test_variables_enum() {
         "value3": "C",
     })));
 
+    // nullable variables without a default value are not allowed where `MyEnum!` is expected
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err(),
+        Error::Rule {
+            errors: vec![
+                RuleError {
+                    locations: vec![
+                        Pos {
+                            line: 2,
+                            column: 38
+                        },
+                        Pos {
+                            line: 3,
+                            column: 17
+                        }
+                    ],
+                    message: r#"Variable "value1" of type "MyEnum" used in position expecting type "MyEnum!""#
+                        .to_string(),
+                },
+                RuleError {
+                    locations: vec![
+                        Pos {
+                            line: 2,
+                            column: 55
+                        },
+                        Pos {
+                            line: 4,
+                            column: 17
+                        }
+                    ],
+                    message: r#"Variable "value2" of type "MyEnum" used in position expecting type "MyEnum!""#
+                        .to_string(),
+                },
+                RuleError {
+                    locations: vec![
+                        Pos {
+                            line: 2,
+                            column: 72
+                        },
+                        Pos {
+                            line: 5,
+                            column: 17
+                        }
+                    ],
+                    message: r#"Variable "value3" of type "MyEnum" used in position expecting type "MyEnum!""#
+                        .to_string(),
+                },
+            ]
+            .into(),
+        }
+    );
+
+    let query = Request::new(
+        r#"
+            query QueryWithVariables($value1: MyEnum!, $value2: MyEnum!, $value3: MyEnum!) {
+                a: value(value: $value1)
+                b: value(value: $value2)
+                c: value(value: $value3)
+            }
+        "#,
+    )
+    .variables(Variables::from_json(serde_json::json!({
+        "value1": "A",
+        "value2": "B",
+        "value3": "C",
+    })));
+
     assert_eq!(
         schema.execute(query).await.into_result().unwrap().data,
         serde_json::json!({
@@ -271,3 +338,60 @@ pub async fn test_variables_enum() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_variables_in_allowed_position() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        pub async fn value(&self, value: i32) -> i32 {
+            value
+        }
+
+        pub async fn opt_value(&self, value: Option<i32>) -> Option<i32> {
+            value
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+
+    for query in &[
+        "query($v: Int!) { value(value: $v) }",
+        "query($v: Int!) { optValue(value: $v) }",
+        "query($v: Int) { optValue(value: $v) }",
+        "query($v: Int = 10) { value(value: $v) }",
+    ] {
+        let query = Request::new(*query).variables(Variables::from_json(serde_json::json!({
+            "v": 10,
+        })));
+        assert!(schema.execute(query).await.is_ok());
+    }
+
+    let query = Request::new("query($v: Int) { value(value: $v) }")
+        .variables(Variables::from_json(serde_json::json!({ "v": 10 })));
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err(),
+        Error::Rule {
+            errors: vec![RuleError {
+                locations: vec![
+                    Pos { line: 1, column: 7 },
+                    Pos {
+                        line: 1,
+                        column: 18
+                    }
+                ],
+                message: r#"Variable "v" of type "Int" used in position expecting type "Int!""#
+                    .to_string(),
+            }]
+            .into(),
+        }
+    );
+
+    let query = Request::new(
+        r#"query($v: Int) { ...F }
+        fragment F on QueryRoot { value(value: $v) }"#,
+    )
+    .variables(Variables::from_json(serde_json::json!({ "v": 10 })));
+    assert!(schema.execute(query).await.is_err());
+}